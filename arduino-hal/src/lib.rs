@@ -1,5 +1,6 @@
 #![no_std]
 #![feature(doc_cfg)]
+#![cfg_attr(feature = "time", feature(abi_avr_interrupt))]
 
 //! `arduino-hal`
 //! =============
@@ -144,36 +145,96 @@ pub mod adc {
 pub use adc::Adc;
 
 /// I2C bus controller.
-#[cfg(feature = "mcu-atmega")]
+#[cfg(feature = "board-selected")]
 pub mod i2c {
     pub use crate::hal::i2c::*;
 
+    #[cfg(feature = "mcu-atmega")]
     pub type I2c = crate::hal::i2c::I2c<crate::DefaultClock>;
+    #[cfg(feature = "mcu-atmega")]
+    pub type InterruptI2c<const N: usize> =
+        crate::hal::i2c::InterruptI2c<crate::DefaultClock, N>;
+
+    /// I2C controller using the USI.
+    #[cfg(feature = "mcu-attiny")]
+    pub type I2c = crate::hal::usi::UsiI2c<crate::DefaultClock>;
+
+    /// Check the [`avr_hal_generic::i2c::SoftI2c`] documentation.
+    pub type SoftI2c<SDA, SCL> = crate::hal::i2c::SoftI2c<SDA, SCL, crate::DefaultClock>;
 }
 #[doc(no_inline)]
-#[cfg(feature = "mcu-atmega")]
+#[cfg(feature = "board-selected")]
 pub use i2c::I2c;
 
 /// SPI controller.
-#[cfg(feature = "mcu-atmega")]
+#[cfg(feature = "board-selected")]
 pub mod spi {
     pub use crate::hal::spi::*;
 
+    #[cfg(feature = "mcu-atmega")]
     pub type Spi = crate::hal::spi::Spi;
+    #[cfg(feature = "mcu-atmega")]
+    pub type SpiPeripheral = crate::hal::spi::SpiPeripheral;
+
+    /// SPI controller using the USI.  It has no hardware chip-select, so any output pin can be
+    /// used as `CSPIN`.
+    #[cfg(feature = "mcu-attiny")]
+    pub type Spi<CSPIN> = crate::hal::usi::UsiSpi<CSPIN>;
+
+    /// Check the [`avr_hal_generic::spi::RefCellDevice`] documentation.
+    #[cfg(feature = "mcu-atmega")]
+    pub type RefCellDevice<'a, CS> =
+        crate::hal::spi::RefCellDevice<'a, Spi, CS, crate::DefaultClock>;
+
+    /// Check the [`avr_hal_generic::spi::CriticalSectionDevice`] documentation.
+    #[cfg(feature = "mcu-atmega")]
+    pub type CriticalSectionDevice<'a, CS> =
+        crate::hal::spi::CriticalSectionDevice<'a, Spi, CS, crate::DefaultClock>;
+
+    /// Check the [`avr_hal_generic::spi::SoftSpi`] documentation.
+    pub type SoftSpi<SCKPIN, MOSIPIN, MISOPIN> =
+        crate::hal::spi::SoftSpi<SCKPIN, MOSIPIN, MISOPIN, crate::DefaultClock>;
 }
 #[doc(no_inline)]
-#[cfg(feature = "mcu-atmega")]
+#[cfg(feature = "board-selected")]
 pub use spi::Spi;
 
 #[cfg(feature = "mcu-atmega")]
+pub mod usart_spi {
+    pub use crate::hal::usart_spi::*;
+}
+
+#[cfg(feature = "board-selected")]
 pub mod usart {
-    pub use crate::hal::usart::{Baudrate, UsartOps};
+    pub use crate::hal::usart::Error;
+    #[cfg(feature = "mcu-atmega")]
+    pub use crate::hal::usart::{
+        AutobaudError, Baudrate, DataBits, FrameFormat, Parity, Rs485DriverEnable, StopBits,
+        UsartBuffers, UsartOps,
+    };
+
+    /// Check the [`avr_hal_generic::usart::SoftSerial`] documentation.
+    pub type SoftSerial<RX, TX> = crate::hal::usart::SoftSerial<RX, TX, crate::DefaultClock>;
 
+    #[cfg(feature = "mcu-atmega")]
+    pub type UsartSettings = crate::hal::usart::UsartSettings<crate::DefaultClock>;
+    #[cfg(feature = "mcu-atmega")]
+    pub type DetectedBaudrate = crate::hal::usart::DetectedBaudrate<crate::DefaultClock>;
+
+    #[cfg(feature = "mcu-atmega")]
     pub type Usart<USART, RX, TX> = crate::hal::usart::Usart<USART, RX, TX, crate::DefaultClock>;
+    #[cfg(feature = "mcu-atmega")]
     pub type UsartWriter<USART, RX, TX> =
         crate::hal::usart::UsartWriter<USART, RX, TX, crate::DefaultClock>;
+    #[cfg(feature = "mcu-atmega")]
     pub type UsartReader<USART, RX, TX> =
         crate::hal::usart::UsartReader<USART, RX, TX, crate::DefaultClock>;
+    #[cfg(feature = "mcu-atmega")]
+    pub type BufferedUsart<USART, RX, TX, const RXN: usize, const TXN: usize> =
+        crate::hal::usart::BufferedUsart<USART, RX, TX, crate::DefaultClock, RXN, TXN>;
+    #[cfg(feature = "mcu-atmega")]
+    pub type Rs485<USART, RX, TX, DE> =
+        crate::hal::usart::Rs485<USART, RX, TX, crate::DefaultClock, DE>;
 }
 
 #[doc(no_inline)]
@@ -197,10 +258,67 @@ pub mod simple_pwm {
     pub use attiny_hal::simple_pwm::*;
 }
 
-#[cfg(feature = "mcu-atmega")]
+/// External interrupts (`INTn`).
+#[cfg(feature = "board-selected")]
+pub mod exint {
+    pub use crate::hal::exint::{ExintOps, ExternalInterrupt, Trigger};
+}
+
+/// Input capture units of the 16-bit timers.
+#[cfg(feature = "board-selected")]
+pub mod input_capture {
+    pub use crate::hal::input_capture::{Capture, Edge, InputCaptureOps, Measurement, Settings};
+
+    /// Check the [`avr_hal_generic::input_capture::InputCapture`] documentation.
+    pub type InputCapture<TC, ICP> =
+        crate::hal::input_capture::InputCapture<TC, ICP, crate::DefaultClock>;
+}
+
+/// Pin-change interrupts (`PCINTn`).
+#[cfg(feature = "board-selected")]
+pub mod pcint {
+    pub use crate::hal::pcint::*;
+}
+
+/// Hobby servos driven by the 16-bit PWM timers.
+#[cfg(feature = "board-selected")]
+pub mod servo {
+    pub use crate::hal::servo::{Servo, ServoSettings};
+
+    /// Check the [`avr_hal_generic::servo::ServoTimer`] documentation.
+    pub type ServoTimer<T> = crate::hal::servo::ServoTimer<T, crate::DefaultClock>;
+}
+
+/// Timer/Counter peripherals.
+#[cfg(feature = "board-selected")]
+pub mod timer {
+    pub use crate::hal::timer::{Event, Mode, Prescaler, TimerOps};
+
+    /// Check the [`avr_hal_generic::timer::Timer`] documentation.
+    pub type Timer<TC> = crate::hal::timer::Timer<TC, crate::DefaultClock>;
+}
+
+#[cfg(feature = "time")]
+#[doc(cfg(feature = "time"))]
+pub mod time;
+
+/// Square-wave tone generators, similar to `tone()` from Arduino.
+#[cfg(feature = "board-selected")]
+pub mod tone {
+    pub use crate::hal::tone::{ToneError, ToneOps};
+
+    /// Check the [`avr_hal_generic::tone::Tone`] documentation.
+    pub type Tone<TC, PIN> = crate::hal::tone::Tone<TC, PIN, crate::DefaultClock>;
+
+    /// Check the [`avr_hal_generic::tone::SoftTone`] documentation.
+    pub type SoftTone<TC, PIN> = crate::hal::tone::SoftTone<TC, PIN, crate::DefaultClock>;
+}
+
+#[cfg(feature = "board-selected")]
 pub mod prelude {
     pub use crate::hal::prelude::*;
 
+    #[cfg(feature = "mcu-atmega")]
     cfg_if::cfg_if! {
         if #[cfg(any(
             feature = "arduino-diecimila",
@@ -340,3 +458,26 @@ macro_rules! default_serial {
         )
     };
 }
+
+/// Convenience macro to instantiate a [`SoftSerial`][usart::SoftSerial] for this board.
+///
+/// The board has no USART, so the serial port is bit-banged with `#0` as TX and `#2` as RX.
+///
+/// **Note**: `#0` and `#2` are also the SDA/SCL pins of the USI in I2C mode and the DI/USCK pins
+/// in SPI mode, so this serial port cannot be used together with [`I2c`] or [`Spi`].  In that
+/// case, create the [`SoftSerial`][usart::SoftSerial] on the remaining pins instead, e.g.
+/// `SoftSerial::new(pins.d3.into_pull_up_input(), pins.d4.into_output(), 9600)`.
+///
+/// # Example
+/// ```no_run
+/// let dp = arduino_hal::Peripherals::take().unwrap();
+/// let pins = arduino_hal::pins!(dp);
+/// let serial = arduino_hal::default_serial!(dp, pins, 9600);
+/// ```
+#[cfg(any(feature = "trinket"))]
+#[macro_export]
+macro_rules! default_serial {
+    ($p:expr, $pins:expr, $baud:expr) => {
+        $crate::usart::SoftSerial::new($pins.d2.into_pull_up_input(), $pins.d0.into_output(), $baud)
+    };
+}
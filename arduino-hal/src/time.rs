@@ -0,0 +1,358 @@
+//! System time keeping, similar to `millis()` and `micros()` from Arduino.
+//!
+//! This module is only available when one of the `time-tc0`, `time-tc1` or `time-tc2` features is
+//! enabled.  The feature selects the timer which is claimed for time keeping: it is run in CTC mode
+//! with a period of one millisecond and its `COMPA` interrupt is used to update a global counter.
+//! The interrupt service routine is installed by this module, so your application must not define
+//! its own handler for that interrupt.
+//!
+//! The resolution of [`micros()`] is 64 clock cycles (4µs at 16 MHz, 8µs at 8 MHz).
+//!
+//! # Example
+//! ```no_run
+//! let dp = arduino_hal::Peripherals::take().unwrap();
+//!
+//! arduino_hal::time::init(dp.TC0);
+//! // The time is only counted while interrupts are enabled.
+//! unsafe { avr_device::interrupt::enable() };
+//!
+//! let start = arduino_hal::time::Instant::now();
+//! // ...
+//! let elapsed = start.elapsed().as_millis();
+//! ```
+use crate::clock::Clock;
+use avr_hal_generic::timer::{Event, Mode, Prescaler};
+use core::cell::{Cell, RefCell};
+use core::ops;
+
+#[cfg(any(
+    all(feature = "time-tc0", feature = "time-tc1"),
+    all(feature = "time-tc0", feature = "time-tc2"),
+    all(feature = "time-tc1", feature = "time-tc2"),
+))]
+compile_error!("Only one of the `time-tc0`, `time-tc1` and `time-tc2` features may be enabled.");
+
+#[cfg(not(any(feature = "time-tc0", feature = "time-tc1", feature = "time-tc2")))]
+compile_error!(
+    "The `time` module needs a timer, please enable one of the `time-tc0`, `time-tc1` or `time-tc2` features."
+);
+
+#[cfg(all(feature = "time-tc1", feature = "trinket"))]
+compile_error!("TC1 of the ATtiny85 is not supported for time keeping, please use `time-tc0`.");
+
+#[cfg(all(
+    feature = "time-tc2",
+    any(
+        feature = "arduino-leonardo",
+        feature = "sparkfun-promicro",
+        feature = "trinket"
+    )
+))]
+compile_error!("This board does not have a TC2 timer, please select a different one for time keeping.");
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "time-tc0")] {
+        /// The timer peripheral which is claimed for time keeping.
+        pub type SystemTimer = crate::pac::TC0;
+        type Counter = u8;
+    } else if #[cfg(feature = "time-tc1")] {
+        /// The timer peripheral which is claimed for time keeping.
+        pub type SystemTimer = crate::pac::TC1;
+        type Counter = u16;
+    } else if #[cfg(feature = "time-tc2")] {
+        /// The timer peripheral which is claimed for time keeping.
+        pub type SystemTimer = crate::pac::TC2;
+        type Counter = u8;
+    }
+}
+
+const PRESCALER: Prescaler = Prescaler::Prescale64;
+const TICKS_PER_MILLI: u32 = crate::DefaultClock::FREQ / PRESCALER.divider() / 1000;
+const MICROS_PER_TICK: u32 = 1000 / TICKS_PER_MILLI;
+
+const _: () = assert!(
+    TICKS_PER_MILLI <= 256 && 1000 % TICKS_PER_MILLI == 0,
+    "The clock speed of this board is not supported for time keeping"
+);
+
+static MILLIS_COUNTER: avr_device::interrupt::Mutex<Cell<u32>> =
+    avr_device::interrupt::Mutex::new(Cell::new(0));
+
+static SYSTEM_TIMER: avr_device::interrupt::Mutex<
+    RefCell<Option<crate::timer::Timer<SystemTimer>>>,
+> = avr_device::interrupt::Mutex::new(RefCell::new(None));
+
+/// Claim the [`SystemTimer`] and start counting time.
+///
+/// The counters are reset to zero.  Time only advances while interrupts are enabled globally.
+pub fn init(tc: SystemTimer) {
+    let mut timer = crate::timer::Timer::new(tc, Mode::Ctc, PRESCALER);
+    timer.set_compare_a((TICKS_PER_MILLI - 1) as Counter);
+    timer.set_counter(0);
+
+    avr_device::interrupt::free(|cs| {
+        MILLIS_COUNTER.borrow(cs).set(0);
+        timer.clear_pending(Event::CompareMatchA);
+        timer.listen(Event::CompareMatchA);
+        SYSTEM_TIMER.borrow(cs).replace(Some(timer));
+    });
+}
+
+/// Milliseconds passed since [`init()`].
+///
+/// The value wraps around after approximately 49.7 days.
+pub fn millis() -> u32 {
+    avr_device::interrupt::free(|cs| MILLIS_COUNTER.borrow(cs).get())
+}
+
+/// Microseconds passed since [`init()`].
+///
+/// The value wraps around after approximately 71.6 minutes.  Returns `0` if [`init()`] was not
+/// called yet.
+pub fn micros() -> u32 {
+    avr_device::interrupt::free(|cs| {
+        let timer = SYSTEM_TIMER.borrow(cs).borrow();
+        let timer = match timer.as_ref() {
+            Some(timer) => timer,
+            None => return 0,
+        };
+
+        let mut millis = MILLIS_COUNTER.borrow(cs).get();
+        let ticks = timer.counter() as u32;
+
+        // If the compare match happened while interrupts were disabled, the counter has already
+        // restarted at zero but the interrupt did not yet get a chance to increment the
+        // millisecond counter.  A large tick value means the match happened after reading it.
+        if timer.is_pending(Event::CompareMatchA) && ticks < TICKS_PER_MILLI / 2 {
+            millis = millis.wrapping_add(1);
+        }
+
+        millis.wrapping_mul(1000).wrapping_add(ticks * MICROS_PER_TICK)
+    })
+}
+
+fn tick() {
+    avr_device::interrupt::free(|cs| {
+        let counter = MILLIS_COUNTER.borrow(cs);
+        counter.set(counter.get().wrapping_add(1));
+    })
+}
+
+macro_rules! impl_system_timer_isr {
+    ($mcu:ident) => {
+        #[cfg(feature = "time-tc0")]
+        #[avr_device::interrupt($mcu)]
+        fn TIMER0_COMPA() {
+            tick();
+        }
+
+        #[cfg(feature = "time-tc1")]
+        #[avr_device::interrupt($mcu)]
+        fn TIMER1_COMPA() {
+            tick();
+        }
+
+        #[cfg(feature = "time-tc2")]
+        #[avr_device::interrupt($mcu)]
+        fn TIMER2_COMPA() {
+            tick();
+        }
+    };
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(any(
+        feature = "arduino-nano",
+        feature = "arduino-uno",
+        feature = "sparkfun-promini-3v3",
+        feature = "sparkfun-promini-5v",
+        feature = "trinket-pro",
+    ))] {
+        impl_system_timer_isr!(atmega328p);
+    } else if #[cfg(any(feature = "arduino-diecimila", feature = "nano168"))] {
+        impl_system_timer_isr!(atmega168);
+    } else if #[cfg(any(feature = "arduino-leonardo", feature = "sparkfun-promicro"))] {
+        impl_system_timer_isr!(atmega32u4);
+    } else if #[cfg(feature = "arduino-mega2560")] {
+        impl_system_timer_isr!(atmega2560);
+    } else if #[cfg(feature = "arduino-mega1280")] {
+        impl_system_timer_isr!(atmega1280);
+    } else if #[cfg(feature = "trinket")] {
+        impl_system_timer_isr!(attiny85);
+    }
+}
+
+/// A point in time, measured by the system timer.
+///
+/// Instants are based on [`micros()`] and thus wrap around after approximately 71.6 minutes.
+/// Differences between two instants and adding or subtracting a [`Duration`] are computed with
+/// wrapping arithmetic, so they are correct as long as the instants are less than 71.6 minutes
+/// apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instant {
+    micros: u32,
+}
+
+impl Instant {
+    /// The current point in time.
+    pub fn now() -> Self {
+        Self { micros: micros() }
+    }
+
+    /// The time passed from `earlier` to `self`.
+    pub fn duration_since(self, earlier: Instant) -> Duration {
+        Duration::from_micros(self.micros.wrapping_sub(earlier.micros))
+    }
+
+    /// The time passed since this instant.
+    pub fn elapsed(self) -> Duration {
+        Instant::now().duration_since(self)
+    }
+
+    /// The raw value of [`micros()`] at this instant.
+    pub fn as_micros(self) -> u32 {
+        self.micros
+    }
+}
+
+impl ops::Add<Duration> for Instant {
+    type Output = Instant;
+
+    fn add(self, rhs: Duration) -> Instant {
+        Instant {
+            micros: self.micros.wrapping_add(rhs.micros),
+        }
+    }
+}
+
+impl ops::AddAssign<Duration> for Instant {
+    fn add_assign(&mut self, rhs: Duration) {
+        *self = *self + rhs;
+    }
+}
+
+impl ops::Sub<Duration> for Instant {
+    type Output = Instant;
+
+    fn sub(self, rhs: Duration) -> Instant {
+        Instant {
+            micros: self.micros.wrapping_sub(rhs.micros),
+        }
+    }
+}
+
+impl ops::SubAssign<Duration> for Instant {
+    fn sub_assign(&mut self, rhs: Duration) {
+        *self = *self - rhs;
+    }
+}
+
+impl ops::Sub<Instant> for Instant {
+    type Output = Duration;
+
+    fn sub(self, rhs: Instant) -> Duration {
+        self.duration_since(rhs)
+    }
+}
+
+/// A span of time with microsecond precision, as measured by the system timer.
+///
+/// Durations are stored as a number of microseconds in a `u32`, so they are limited to
+/// [`Duration::MAX`] (approximately 71.6 minutes), the same range in which differences between two
+/// [`Instant`]s are correct.  Constructors and arithmetic saturate at zero and [`Duration::MAX`]
+/// instead of overflowing, use [`Duration::checked_add()`] and [`Duration::checked_sub()`] to
+/// detect this.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Duration {
+    micros: u32,
+}
+
+impl Duration {
+    /// A duration of zero.
+    pub const ZERO: Duration = Duration::from_micros(0);
+
+    /// The longest representable duration, `u32::MAX` microseconds.
+    pub const MAX: Duration = Duration::from_micros(u32::MAX);
+
+    /// Create a duration from a number of microseconds.
+    pub const fn from_micros(micros: u32) -> Self {
+        Self { micros }
+    }
+
+    /// Create a duration from a number of milliseconds.
+    ///
+    /// Saturates at [`Duration::MAX`] for more than 4294967 milliseconds.
+    pub const fn from_millis(millis: u32) -> Self {
+        Self::from_micros(millis.saturating_mul(1000))
+    }
+
+    /// Create a duration from a number of seconds.
+    ///
+    /// Saturates at [`Duration::MAX`] for more than 4294 seconds.
+    pub const fn from_secs(secs: u32) -> Self {
+        Self::from_micros(secs.saturating_mul(1_000_000))
+    }
+
+    /// Total number of whole microseconds in this duration.
+    pub const fn as_micros(self) -> u32 {
+        self.micros
+    }
+
+    /// Total number of whole milliseconds in this duration.
+    pub const fn as_millis(self) -> u32 {
+        self.micros / 1000
+    }
+
+    /// Total number of whole seconds in this duration.
+    pub const fn as_secs(self) -> u32 {
+        self.micros / 1_000_000
+    }
+
+    /// Add two durations, returning `None` if the result exceeds [`Duration::MAX`].
+    pub const fn checked_add(self, rhs: Duration) -> Option<Duration> {
+        match self.micros.checked_add(rhs.micros) {
+            Some(micros) => Some(Duration::from_micros(micros)),
+            None => None,
+        }
+    }
+
+    /// Subtract two durations, returning `None` if `rhs` is longer than `self`.
+    pub const fn checked_sub(self, rhs: Duration) -> Option<Duration> {
+        match self.micros.checked_sub(rhs.micros) {
+            Some(micros) => Some(Duration::from_micros(micros)),
+            None => None,
+        }
+    }
+
+    /// Add two durations, saturating at [`Duration::MAX`].
+    pub const fn saturating_add(self, rhs: Duration) -> Duration {
+        Duration::from_micros(self.micros.saturating_add(rhs.micros))
+    }
+
+    /// Subtract two durations, saturating at zero.
+    pub const fn saturating_sub(self, rhs: Duration) -> Duration {
+        Duration::from_micros(self.micros.saturating_sub(rhs.micros))
+    }
+}
+
+impl ops::Add for Duration {
+    type Output = Duration;
+
+    fn add(self, rhs: Duration) -> Duration {
+        self.saturating_add(rhs)
+    }
+}
+
+impl ops::Sub for Duration {
+    type Output = Duration;
+
+    fn sub(self, rhs: Duration) -> Duration {
+        self.saturating_sub(rhs)
+    }
+}
+
+impl From<Duration> for core::time::Duration {
+    fn from(d: Duration) -> Self {
+        core::time::Duration::from_micros(d.micros as u64)
+    }
+}
@@ -62,6 +62,19 @@ fn busy_loop(_c: u16) {
     unimplemented!("Implementation is only available for avr targets!")
 }
 
+/// Busy-wait for about `cycles` CPU cycles, in steps of 4 cycles.
+///
+/// Meant for short bit timings in peripheral drivers, where the call overhead is part of the
+/// timing anyway.
+#[doc(hidden)]
+#[inline(always)]
+pub fn delay_cycles(cycles: u16) {
+    // `busy_loop(0)` would wrap around.
+    if cycles >= 4 {
+        busy_loop(cycles / 4);
+    }
+}
+
 // Clock-Specific Delay Implementations ----------------------------------- {{{
 impl delay_v0::DelayUs<u16> for Delay<crate::clock::MHz24> {
     fn delay_us(&mut self, mut us: u16) {
@@ -0,0 +1,284 @@
+//! External Interrupt Implementation
+//!
+//! Check the documentation of [`ExternalInterrupt`] for details.
+
+use crate::port;
+
+/// Condition on which an external interrupt is triggered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Trigger {
+    /// Trigger continuously while the pin is held low.
+    LowLevel = 0b00,
+    /// Trigger on any logical change of the pin.
+    AnyEdge = 0b01,
+    /// Trigger on a falling edge of the pin.
+    FallingEdge = 0b10,
+    /// Trigger on a rising edge of the pin.
+    RisingEdge = 0b11,
+}
+
+/// Condition on which an external interrupt without the "any edge" mode is triggered
+///
+/// Used by the interrupts which reserve this mode, like `INT0`-`INT3` of the ATmega128A.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum LevelOrEdgeTrigger {
+    /// Trigger continuously while the pin is held low.
+    LowLevel = 0b00,
+    /// Trigger on a falling edge of the pin.
+    FallingEdge = 0b10,
+    /// Trigger on a rising edge of the pin.
+    RisingEdge = 0b11,
+}
+
+/// Condition on which an edge-only external interrupt is triggered
+///
+/// Used by the interrupts with a single sense control bit, like `INT2` of the ATmega32A.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum EdgeTrigger {
+    /// Trigger on a falling edge of the pin.
+    FallingEdge = 0b0,
+    /// Trigger on a rising edge of the pin.
+    RisingEdge = 0b1,
+}
+
+/// Encoding of trigger conditions in the sense control (`ISCn`) bits of an external interrupt.
+pub trait SenseControl: Copy {
+    /// Mask of the sense control bits, shifted to the lowest bit.
+    const MASK: u8;
+
+    /// Value of the sense control bits for this condition.
+    fn bits(self) -> u8;
+}
+
+impl SenseControl for Trigger {
+    const MASK: u8 = 0b11;
+
+    fn bits(self) -> u8 {
+        self as u8
+    }
+}
+
+impl SenseControl for LevelOrEdgeTrigger {
+    const MASK: u8 = 0b11;
+
+    fn bits(self) -> u8 {
+        self as u8
+    }
+}
+
+impl SenseControl for EdgeTrigger {
+    const MASK: u8 = 0b1;
+
+    fn bits(self) -> u8 {
+        self as u8
+    }
+}
+
+/// Internal trait for low-level external interrupt peripherals.
+///
+/// This trait defines the common interface for the external interrupts (`INTn`) of all MCUs.  It
+/// is implemented for the peripheral which controls the external interrupts, once for each pin
+/// that can act as an interrupt source.  It is used as an intermediate abstraction ontop of which
+/// the [`ExternalInterrupt`] API is built.  **Prefer using the [`ExternalInterrupt`] API instead
+/// of this trait.**
+pub trait ExintOps<H, PIN> {
+    /// Conditions on which the interrupt can be triggered.
+    ///
+    /// This is [`Trigger`] for most interrupts.  Interrupts which only support some of the
+    /// conditions use [`LevelOrEdgeTrigger`] or [`EdgeTrigger`] instead, so the others are
+    /// rejected at compile time.
+    type Trigger: SenseControl;
+
+    /// Configure the condition on which the interrupt is triggered.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_trigger(&mut self, trigger: Self::Trigger);
+
+    /// Enable/Disable the interrupt.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_interrupt(&mut self, state: bool);
+
+    /// Check whether the interrupt flag is set.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_is_pending(&self) -> bool;
+
+    /// Clear the interrupt flag.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_clear_pending(&mut self);
+}
+
+/// An input pin which is configured as an external interrupt source.
+///
+/// Created by calling
+/// [`Pin::enable_external_interrupt()`][crate::port::Pin::enable_external_interrupt] on an input
+/// pin which is connected to one of the `INTn` interrupts of the MCU.  Only those pins implement
+/// the necessary [`ExintOps`] trait, so using any other pin is a compile-time error.
+///
+/// # Example
+/// (for Arduino Uno)
+/// ```
+/// let dp = arduino_hal::Peripherals::take().unwrap();
+/// let pins = arduino_hal::pins!(dp);
+/// let mut exint = dp.EXINT;
+///
+/// // D2 is connected to INT0
+/// let button = pins.d2.into_pull_up_input();
+/// let mut int0 = button.enable_external_interrupt(&mut exint, Trigger::FallingEdge);
+///
+/// // The INT0 interrupt handler is now called on every falling edge of D2.
+///
+/// // Turn D2 back into a normal input pin:
+/// let button = int0.disable(&mut exint);
+/// ```
+pub struct ExternalInterrupt<H, EXINT, PIN, IMODE> {
+    pin: port::Pin<port::mode::Input<IMODE>, PIN>,
+    _h: core::marker::PhantomData<H>,
+    _exint: core::marker::PhantomData<EXINT>,
+}
+
+impl<H, EXINT, PIN, IMODE> ExternalInterrupt<H, EXINT, PIN, IMODE>
+where
+    EXINT: ExintOps<H, PIN>,
+    PIN: port::PinOps,
+    IMODE: port::mode::InputMode,
+{
+    pub(crate) fn new(
+        pin: port::Pin<port::mode::Input<IMODE>, PIN>,
+        exint: &mut EXINT,
+        trigger: EXINT::Trigger,
+    ) -> Self {
+        // Changing the trigger condition can set the interrupt flag spuriously, so the interrupt
+        // is only enabled after clearing the flag.
+        exint.raw_interrupt(false);
+        exint.raw_set_trigger(trigger);
+        exint.raw_clear_pending();
+        exint.raw_interrupt(true);
+
+        Self {
+            pin,
+            _h: core::marker::PhantomData,
+            _exint: core::marker::PhantomData,
+        }
+    }
+
+    /// Change the condition on which the interrupt is triggered.
+    pub fn set_trigger(&mut self, exint: &mut EXINT, trigger: EXINT::Trigger) {
+        exint.raw_interrupt(false);
+        exint.raw_set_trigger(trigger);
+        exint.raw_clear_pending();
+        exint.raw_interrupt(true);
+    }
+
+    /// Check whether the interrupt flag is set.
+    ///
+    /// The hardware clears the flag automatically when the interrupt handler is executed.  This
+    /// is mostly useful when interrupts are disabled globally.
+    pub fn is_pending(&self, exint: &EXINT) -> bool {
+        exint.raw_is_pending()
+    }
+
+    /// Clear the interrupt flag.
+    pub fn clear_pending(&mut self, exint: &mut EXINT) {
+        exint.raw_clear_pending();
+    }
+
+    /// Access the underlying input pin, e.g. to read its current level.
+    pub fn pin(&self) -> &port::Pin<port::mode::Input<IMODE>, PIN> {
+        &self.pin
+    }
+
+    /// Disable the interrupt and return the input pin.
+    pub fn disable(self, exint: &mut EXINT) -> port::Pin<port::mode::Input<IMODE>, PIN> {
+        exint.raw_interrupt(false);
+        exint.raw_clear_pending();
+        self.pin
+    }
+}
+
+/// Implement [`ExintOps`] for the external interrupts of an MCU.
+///
+/// For each interrupt, the pin, the sense control register together with the bit offset of the
+/// `ISCn` bits, the mask register and the flag register together with the bit position of the
+/// interrupt in them are given.  A sense control register outside of the peripheral is given
+/// with `in` and the peripheral it belongs to.  Interrupts which do not support all [`Trigger`]
+/// conditions give the type of their conditions with `trigger`.
+#[macro_export]
+macro_rules! impl_exint {
+    (@sense $self:ident) => { $self };
+    (@sense $self:ident $SENSE:ty) => {
+        // SAFETY: The sense control bits are only accessed inside critical sections.
+        unsafe { &*<$SENSE>::ptr() }
+    };
+    (@trigger) => { $crate::exint::Trigger };
+    (@trigger $Trigger:ty) => { $Trigger };
+    (
+        hal: $HAL:ty,
+        peripheral: $EXINT:ty,
+        interrupts: {
+            $(
+                $pin:ty: {
+                    sense: $isc:ident[$shift:literal] $(in $SENSE:ty)?,
+                    $(trigger: $Trigger:ty,)?
+                    mask: $msk:ident,
+                    flag: $flag:ident,
+                    bit: $bit:literal,
+                },
+            )+
+        },
+    ) => {
+        $(
+            impl $crate::exint::ExintOps<$HAL, $pin> for $EXINT {
+                type Trigger = $crate::impl_exint!(@trigger $($Trigger)?);
+
+                #[inline]
+                fn raw_set_trigger(&mut self, trigger: Self::Trigger) {
+                    use $crate::exint::SenseControl;
+
+                    // SAFETY: Only the sense control bits of this interrupt are changed.
+                    $crate::avr_device::interrupt::free(|_| {
+                        $crate::impl_exint!(@sense self $($SENSE)?).$isc.modify(|r, w| unsafe {
+                            w.bits(
+                                r.bits() & !(<Self::Trigger as SenseControl>::MASK << $shift)
+                                    | trigger.bits() << $shift,
+                            )
+                        })
+                    });
+                }
+
+                #[inline]
+                fn raw_interrupt(&mut self, state: bool) {
+                    // SAFETY: Only the mask bit of this interrupt is changed.  The mask register
+                    // is shared by all external interrupts, so the read-modify-write sequence is
+                    // wrapped in a critical section.
+                    $crate::avr_device::interrupt::free(|_| {
+                        self.$msk.modify(|r, w| unsafe {
+                            if state {
+                                w.bits(r.bits() | 1 << $bit)
+                            } else {
+                                w.bits(r.bits() & !(1 << $bit))
+                            }
+                        })
+                    });
+                }
+
+                #[inline]
+                fn raw_is_pending(&self) -> bool {
+                    self.$flag.read().bits() & (1 << $bit) != 0
+                }
+
+                #[inline]
+                fn raw_clear_pending(&mut self) {
+                    // SAFETY: Flags are cleared by writing a logical one, so writing zeros to all
+                    // other bits leaves them untouched.
+                    self.$flag.write(|w| unsafe { w.bits(1 << $bit) });
+                }
+            }
+        )+
+    };
+}
@@ -1,11 +1,14 @@
 //! I2C Implementations
 //!
-//! Check the documentation of [`I2c`] for details.
+//! Check the documentation of [`I2c`] and [`SoftI2c`] for details.
 
 use embedded_hal::i2c::SevenBitAddress;
 
 use crate::port;
+use crate::prelude::*;
+use core::cell::RefCell;
 use core::marker::PhantomData;
+use core::task::{Poll, Waker};
 
 /// TWI Status Codes
 pub mod twi_status {
@@ -118,6 +121,10 @@ pub enum Error {
     BusError,
     /// An unknown error occured.  The bus might be in an unknown state.
     Unknown,
+    /// The peripheral did not finish in time, see [`I2c::set_timeout()`].
+    ///
+    /// A device might be holding the bus.  Use [`I2c::recover_bus()`] to release it.
+    Timeout,
 }
 
 impl embedded_hal::i2c::Error for Error {
@@ -131,7 +138,7 @@ impl embedded_hal::i2c::Error for Error {
                 embedded_hal::i2c::NoAcknowledgeSource::Data,
             ),
             Error::BusError => embedded_hal::i2c::ErrorKind::Bus,
-            Error::Unknown => embedded_hal::i2c::ErrorKind::Other,
+            Error::Unknown | Error::Timeout => embedded_hal::i2c::ErrorKind::Other,
         }
     }
 }
@@ -152,6 +159,30 @@ pub enum Direction {
     Read,
 }
 
+/// Busy-wait until `ready` returns `true`, giving up after about `timeout` CPU cycles.
+///
+/// Without a timeout, this waits forever.
+#[doc(hidden)]
+#[inline]
+pub fn wait_until(timeout: Option<u32>, mut ready: impl FnMut() -> bool) -> Result<(), Error> {
+    // Approximate duration of one iteration of the loop below
+    const CYCLES_PER_POLL: u32 = 10;
+
+    match timeout {
+        None => while !ready() {},
+        Some(cycles) => {
+            let mut polls = cycles / CYCLES_PER_POLL;
+            while !ready() {
+                if polls == 0 {
+                    return Err(Error::Timeout);
+                }
+                polls -= 1;
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Internal trait for low-level I2C peripherals.
 ///
 /// This trait defines the common interface for all I2C peripheral variants.  It is used as an
@@ -168,22 +199,30 @@ pub trait I2cOps<H, SDA, SCL> {
     /// If a previous transaction was not stopped via `raw_stop()`, this should generate a repeated
     /// start condition.
     ///
+    /// Each wait for the peripheral must give up with [`Error::Timeout`] after about `timeout` CPU
+    /// cycles.  This also applies to `raw_write()`, `raw_read()` and `raw_stop()`.
+    ///
     /// **Warning**: This is a low-level method and should not be called directly from user code.
-    fn raw_start(&mut self, address: u8, direction: Direction) -> Result<(), Error>;
+    fn raw_start(
+        &mut self,
+        address: u8,
+        direction: Direction,
+        timeout: Option<u32>,
+    ) -> Result<(), Error>;
 
     /// Write some bytes to the bus.
     ///
     /// This method must only be called after a transaction in write mode was successfully started.
     ///
     /// **Warning**: This is a low-level method and should not be called directly from user code.
-    fn raw_write(&mut self, bytes: &[u8]) -> Result<(), Error>;
+    fn raw_write(&mut self, bytes: &[u8], timeout: Option<u32>) -> Result<(), Error>;
 
     /// Read some bytes from the bus.
     ///
     /// This method must only be called after a transaction in read mode was successfully started.
     ///
     /// **Warning**: This is a low-level method and should not be called directly from user code.
-    fn raw_read(&mut self, buffer: &mut [u8]) -> Result<(), Error>;
+    fn raw_read(&mut self, buffer: &mut [u8], timeout: Option<u32>) -> Result<(), Error>;
 
     /// Send a stop-condition and release the bus.
     ///
@@ -191,7 +230,14 @@ pub trait I2cOps<H, SDA, SCL> {
     /// does not need to block until the stop condition has actually occured.
     ///
     /// **Warning**: This is a low-level method and should not be called directly from user code.
-    fn raw_stop(&mut self) -> Result<(), Error>;
+    fn raw_stop(&mut self, timeout: Option<u32>) -> Result<(), Error>;
+
+    /// Disable the peripheral such that the pins can be driven directly.
+    ///
+    /// `raw_setup()` enables the peripheral again.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_disable(&mut self);
 }
 
 /// I2C driver
@@ -217,6 +263,8 @@ pub struct I2c<H, I2C: I2cOps<H, SDA, SCL>, SDA, SCL, CLOCK> {
     sda: SDA,
     #[allow(dead_code)]
     scl: SCL,
+    speed: u32,
+    timeout: Option<u32>,
     _clock: PhantomData<CLOCK>,
     _h: PhantomData<H>,
 }
@@ -247,6 +295,8 @@ where
             p,
             sda: sda.forget_imode(),
             scl: scl.forget_imode(),
+            speed,
+            timeout: None,
             _clock: PhantomData,
             _h: PhantomData,
         };
@@ -270,6 +320,8 @@ where
             p,
             sda: sda.forget_imode(),
             scl: scl.forget_imode(),
+            speed,
+            timeout: None,
             _clock: PhantomData,
             _h: PhantomData,
         };
@@ -278,6 +330,96 @@ where
     }
 }
 
+impl<H, I2C, SDAPIN, SCLPIN, CLOCK>
+    I2c<H, I2C, port::Pin<port::mode::Input, SDAPIN>, port::Pin<port::mode::Input, SCLPIN>, CLOCK>
+where
+    I2C: I2cOps<H, port::Pin<port::mode::Input, SDAPIN>, port::Pin<port::mode::Input, SCLPIN>>,
+    SDAPIN: port::PinOps,
+    SCLPIN: port::PinOps,
+    CLOCK: crate::clock::Clock,
+    crate::delay::Delay<CLOCK>: embedded_hal::delay::DelayNs,
+{
+    /// Release a bus which is held by a device.
+    ///
+    /// When the controller is reset in the middle of a read transfer, a device can be left
+    /// driving SDA low while it waits for the remaining clock pulses.  To release the bus, the
+    /// peripheral is disabled and SCL is toggled by hand until SDA is released, at most nine times
+    /// (one byte and the acknowledge bit).  Then a stop condition is generated and the peripheral
+    /// is set up again.
+    ///
+    /// Returns [`Error::BusError`] if SDA or SCL are still held low afterwards.
+    pub fn recover_bus(&mut self) -> Result<(), Error> {
+        use embedded_hal::delay::DelayNs;
+        let mut delay = crate::delay::Delay::<CLOCK>::new();
+
+        // Half a clock period at 100 kHz
+        const HALF_PERIOD_US: u32 = 5;
+
+        self.p.raw_disable();
+
+        // The pins are driven like open-drain outputs, keeping their pull-up configuration for
+        // the released state.
+        let sda_pull_up = unsafe { self.sda.pin.out_get() };
+        let scl_pull_up = unsafe { self.scl.pin.out_get() };
+
+        for _ in 0..9 {
+            if self.sda.is_high() {
+                break;
+            }
+            unsafe {
+                self.scl.pin.out_clear();
+                self.scl.pin.make_output();
+            }
+            delay.delay_us(HALF_PERIOD_US);
+            unsafe { self.scl.pin.make_input(scl_pull_up) };
+            delay.delay_us(HALF_PERIOD_US);
+        }
+
+        // Stop condition: SDA rises while SCL is high
+        unsafe {
+            self.scl.pin.out_clear();
+            self.scl.pin.make_output();
+            delay.delay_us(HALF_PERIOD_US);
+            self.sda.pin.out_clear();
+            self.sda.pin.make_output();
+            delay.delay_us(HALF_PERIOD_US);
+            self.scl.pin.make_input(scl_pull_up);
+            delay.delay_us(HALF_PERIOD_US);
+            self.sda.pin.make_input(sda_pull_up);
+            delay.delay_us(HALF_PERIOD_US);
+        }
+
+        let released = self.sda.is_high() && self.scl.is_high();
+        self.p.raw_setup::<CLOCK>(self.speed);
+
+        if released {
+            Ok(())
+        } else {
+            Err(Error::BusError)
+        }
+    }
+}
+
+impl<H, I2C: I2cOps<H, SDA, SCL>, SDA, SCL, CLOCK> I2c<H, I2C, SDA, SCL, CLOCK> {
+    /// Limit how long to wait for the bus, in CPU cycles.
+    ///
+    /// A device holding SCL low (clock stretching) or a disturbed bus can keep the peripheral from
+    /// finishing a transfer.  With a timeout, such a transfer fails with [`Error::Timeout`] instead
+    /// of blocking forever.  The limit applies to each step of a transfer (start condition,
+    /// address and every single byte) and is only approximate.
+    ///
+    /// `None` disables the timeout, which is the default.
+    ///
+    /// # Example
+    /// ```
+    /// // Give up after about 10 ms
+    /// i2c.set_timeout(Some(arduino_hal::DefaultClock::FREQ / 100));
+    /// ```
+    pub fn set_timeout(&mut self, cycles: Option<u32>) {
+        self.timeout = cycles;
+    }
+}
+
 impl<H, I2C: I2cOps<H, SDA, SCL>, SDA, SCL, CLOCK> I2c<H, I2C, SDA, SCL, CLOCK>
 where
     CLOCK: crate::clock::Clock,
@@ -285,9 +427,9 @@ where
 {
     /// Test whether a device answers on a certain address.
     pub fn ping_device(&mut self, address: u8, direction: Direction) -> Result<bool, Error> {
-        match self.p.raw_start(address, direction) {
+        match self.p.raw_start(address, direction, self.timeout) {
             Ok(_) => {
-                self.p.raw_stop()?;
+                self.p.raw_stop(self.timeout)?;
                 Ok(true)
             }
             Err(Error::AddressNack) => Ok(false),
@@ -316,54 +458,66 @@ where
         w: &mut W,
         direction: Direction,
     ) -> Result<(), W::Error> {
-        use embedded_hal_v0::blocking::delay::DelayMs;
-        let mut delay = crate::delay::Delay::<CLOCK>::new();
+        i2cdetect::<W, CLOCK>(w, |address| self.ping_device(address, direction))
+    }
+}
+
+/// Scan the bus using `ping` and print the summary shared by [`I2c::i2cdetect()`] and
+/// [`SoftI2c::i2cdetect()`].
+fn i2cdetect<W: ufmt::uWrite, CLOCK>(
+    w: &mut W,
+    mut ping: impl FnMut(u8) -> Result<bool, Error>,
+) -> Result<(), W::Error>
+where
+    crate::delay::Delay<CLOCK>: embedded_hal_v0::blocking::delay::DelayMs<u16>,
+{
+    use embedded_hal_v0::blocking::delay::DelayMs;
+    let mut delay = crate::delay::Delay::<CLOCK>::new();
 
-        w.write_str(
-            "\
+    w.write_str(
+        "\
 -    0  1  2  3  4  5  6  7  8  9  a  b  c  d  e  f\r\n\
 00:      ",
-        )?;
+    )?;
 
-        fn u4_to_hex(b: u8) -> char {
-            match b {
-                x if x < 0xa => (0x30 + x).into(),
-                x if x < 0x10 => (0x57 + x).into(),
-                _ => '?',
-            }
+    fn u4_to_hex(b: u8) -> char {
+        match b {
+            x if x < 0xa => (0x30 + x).into(),
+            x if x < 0x10 => (0x57 + x).into(),
+            _ => '?',
         }
+    }
+
+    for address in 0x02..=0x77 {
+        let (ah, al) = (u4_to_hex(address >> 4), u4_to_hex(address & 0xf));
 
-        for address in 0x02..=0x77 {
-            let (ah, al) = (u4_to_hex(address >> 4), u4_to_hex(address & 0xf));
+        if address % 0x10 == 0 {
+            w.write_str("\r\n")?;
+            w.write_char(ah)?;
+            w.write_str("0:")?;
+        }
 
-            if address % 0x10 == 0 {
-                w.write_str("\r\n")?;
+        match ping(address) {
+            Ok(true) => {
+                w.write_char(' ')?;
                 w.write_char(ah)?;
-                w.write_str("0:")?;
+                w.write_char(al)?;
             }
-
-            match self.ping_device(address, direction) {
-                Ok(true) => {
-                    w.write_char(' ')?;
-                    w.write_char(ah)?;
-                    w.write_char(al)?;
-                }
-                Ok(false) => {
-                    w.write_str(" --")?;
-                }
-                Err(e) => {
-                    w.write_str(" E")?;
-                    w.write_char(u4_to_hex(e as u8))?;
-                }
+            Ok(false) => {
+                w.write_str(" --")?;
+            }
+            Err(e) => {
+                w.write_str(" E")?;
+                w.write_char(u4_to_hex(e as u8))?;
             }
-
-            delay.delay_ms(10u16);
         }
 
-        w.write_str("\r\n")?;
-
-        Ok(())
+        delay.delay_ms(10u16);
     }
+
+    w.write_str("\r\n")?;
+
+    Ok(())
 }
 
 impl<H, I2C: I2cOps<H, SDA, SCL>, SDA, SCL, CLOCK> embedded_hal_v0::blocking::i2c::Write
@@ -372,9 +526,9 @@ impl<H, I2C: I2cOps<H, SDA, SCL>, SDA, SCL, CLOCK> embedded_hal_v0::blocking::i2
     type Error = Error;
 
     fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
-        self.p.raw_start(address, Direction::Write)?;
-        self.p.raw_write(bytes)?;
-        self.p.raw_stop()?;
+        self.p.raw_start(address, Direction::Write, self.timeout)?;
+        self.p.raw_write(bytes, self.timeout)?;
+        self.p.raw_stop(self.timeout)?;
         Ok(())
     }
 }
@@ -385,9 +539,9 @@ impl<H, I2C: I2cOps<H, SDA, SCL>, SDA, SCL, CLOCK> embedded_hal_v0::blocking::i2
     type Error = Error;
 
     fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
-        self.p.raw_start(address, Direction::Read)?;
-        self.p.raw_read(buffer)?;
-        self.p.raw_stop()?;
+        self.p.raw_start(address, Direction::Read, self.timeout)?;
+        self.p.raw_read(buffer, self.timeout)?;
+        self.p.raw_stop(self.timeout)?;
         Ok(())
     }
 }
@@ -403,11 +557,11 @@ impl<H, I2C: I2cOps<H, SDA, SCL>, SDA, SCL, CLOCK> embedded_hal_v0::blocking::i2
         bytes: &[u8],
         buffer: &mut [u8],
     ) -> Result<(), Self::Error> {
-        self.p.raw_start(address, Direction::Write)?;
-        self.p.raw_write(bytes)?;
-        self.p.raw_start(address, Direction::Read)?;
-        self.p.raw_read(buffer)?;
-        self.p.raw_stop()?;
+        self.p.raw_start(address, Direction::Write, self.timeout)?;
+        self.p.raw_write(bytes, self.timeout)?;
+        self.p.raw_start(address, Direction::Read, self.timeout)?;
+        self.p.raw_read(buffer, self.timeout)?;
+        self.p.raw_stop(self.timeout)?;
         Ok(())
     }
 }
@@ -425,125 +579,1134 @@ impl<H, I2C: I2cOps<H, SDA, SCL>, SDA, SCL, CLOCK> embedded_hal::i2c::I2c<SevenB
             match operation {
                 embedded_hal::i2c::Operation::Read(buffer) => {
                     if idx == 0 || previous_direction != Direction::Read {
-                        self.p.raw_start(address, Direction::Read)?;
+                        self.p.raw_start(address, Direction::Read, self.timeout)?;
                     }
-                    self.p.raw_read(buffer)?;
+                    self.p.raw_read(buffer, self.timeout)?;
                     previous_direction = Direction::Read;
                 }
                 embedded_hal::i2c::Operation::Write(bytes) => {
                     if idx == 0 || previous_direction != Direction::Write {
-                        self.p.raw_start(address, Direction::Write)?;
+                        self.p.raw_start(address, Direction::Write, self.timeout)?;
                     }
-                    self.p.raw_write(bytes)?;
+                    self.p.raw_write(bytes, self.timeout)?;
                     previous_direction = Direction::Write;
                 }
             }
         }
         if operations.len() > 0 {
-            self.p.raw_stop()?;
+            self.p.raw_stop(self.timeout)?;
         }
 
         Ok(())
     }
 }
 
-#[macro_export]
-macro_rules! impl_i2c_twi {
-    (
-        hal: $HAL:ty,
-        peripheral: $I2C:ty,
-        sda: $sdapin:ty,
-        scl: $sclpin:ty,
-    ) => {
-        impl
-            $crate::i2c::I2cOps<
-                $HAL,
-                $crate::port::Pin<$crate::port::mode::Input, $sdapin>,
-                $crate::port::Pin<$crate::port::mode::Input, $sclpin>,
-            > for $I2C
-        {
-            #[inline]
-            fn raw_setup<CLOCK: $crate::clock::Clock>(&mut self, speed: u32) {
-                // Calculate TWBR register value
-                let twbr = ((CLOCK::FREQ / speed) - 16) / 2;
-                self.twbr.write(|w| unsafe { w.bits(twbr as u8) });
+/// Internal trait for low-level I2C peripherals which can run transfers from their interrupt.
+///
+/// Each method starts the next step of a transfer, whose completion is signalled by the TWI
+/// interrupt.  The [`twi_status`] code then tells the outcome of the step.  It is used as an
+/// intermediate abstraction ontop of which the [`InterruptI2c`] API is built.  **Prefer using
+/// the [`InterruptI2c`] API instead of this trait.**
+pub trait I2cInterruptOps<H, SDA, SCL> {
+    /// Send a (repeated) start condition.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_send_start(&mut self);
 
-                // Disable prescaler
-                self.twsr.write(|w| w.twps().prescaler_1());
+    /// Send an address or data byte.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_send_byte(&mut self, byte: u8);
+
+    /// Receive a byte and acknowledge it if `ack` is set.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_receive_byte(&mut self, ack: bool);
+
+    /// Send a stop condition and disable the interrupt.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_send_stop(&mut self);
+
+    /// Release the bus without a stop condition and disable the interrupt.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_release_bus(&mut self);
+
+    /// Status code of the last step, one of the [`twi_status`] constants.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_status(&self) -> u8;
+
+    /// The last received byte.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_received_byte(&self) -> u8;
+}
+
+/// Consecutive operations in the same direction, transferred without a repeated start
+#[derive(Clone, Copy)]
+struct Segment {
+    direction: Direction,
+    len: usize,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TransferStatus {
+    Idle,
+    Running,
+    Done(Result<(), Error>),
+}
+
+struct TransferState<const N: usize> {
+    data: [u8; N],
+    segments: [Segment; MAX_SEGMENTS],
+    segment_count: usize,
+    address: u8,
+    /// Index of the segment which is currently transferred
+    segment: usize,
+    /// Bytes left in the current segment
+    remaining: usize,
+    /// Position of the next byte in `data`
+    pos: usize,
+    status: TransferStatus,
+    /// Task waiting for the transfer to finish
+    waker: Option<Waker>,
+}
+
+/// Maximum number of direction changes (repeated starts) plus one in an [`InterruptI2c`]
+/// transaction.
+const MAX_SEGMENTS: usize = 8;
+
+impl<const N: usize> TransferState<N> {
+    /// Continue with the next segment or finish the transaction.
+    fn next_segment<H, I2C: I2cInterruptOps<H, SDA, SCL>, SDA, SCL>(
+        &mut self,
+        p: &mut I2C,
+    ) -> Option<Result<(), Error>> {
+        self.segment += 1;
+        if self.segment < self.segment_count {
+            self.remaining = self.segments[self.segment].len;
+            p.raw_send_start();
+            None
+        } else {
+            p.raw_send_stop();
+            Some(Ok(()))
+        }
+    }
+
+    /// Handle the outcome of the last step and start the next one.
+    ///
+    /// Returns the result once the transaction has finished.
+    fn step<H, I2C: I2cInterruptOps<H, SDA, SCL>, SDA, SCL>(
+        &mut self,
+        p: &mut I2C,
+    ) -> Option<Result<(), Error>> {
+        match p.raw_status() {
+            twi_status::TW_START | twi_status::TW_REP_START => {
+                let dirbit = match self.segments[self.segment].direction {
+                    Direction::Write => 0,
+                    Direction::Read => 1,
+                };
+                p.raw_send_byte((self.address << 1) | dirbit);
+                None
+            }
+            twi_status::TW_MT_SLA_ACK | twi_status::TW_MT_DATA_ACK => {
+                if self.remaining == 0 {
+                    return self.next_segment(p);
+                }
+                p.raw_send_byte(self.data[self.pos]);
+                self.pos += 1;
+                self.remaining -= 1;
+                None
+            }
+            twi_status::TW_MR_SLA_ACK => {
+                p.raw_receive_byte(self.remaining > 1);
+                None
+            }
+            twi_status::TW_MR_DATA_ACK => {
+                self.data[self.pos] = p.raw_received_byte();
+                self.pos += 1;
+                self.remaining -= 1;
+                p.raw_receive_byte(self.remaining > 1);
+                None
+            }
+            twi_status::TW_MR_DATA_NACK => {
+                // The last byte of this segment was not acknowledged on purpose.
+                self.data[self.pos] = p.raw_received_byte();
+                self.pos += 1;
+                self.remaining = 0;
+                self.next_segment(p)
+            }
+            twi_status::TW_MT_SLA_NACK | twi_status::TW_MR_SLA_NACK => {
+                p.raw_send_stop();
+                Some(Err(Error::AddressNack))
+            }
+            twi_status::TW_MT_DATA_NACK => {
+                p.raw_send_stop();
+                Some(Err(Error::DataNack))
             }
+            // Same code as `TW_MR_ARB_LOST`
+            twi_status::TW_MT_ARB_LOST => {
+                p.raw_release_bus();
+                Some(Err(Error::ArbitrationLost))
+            }
+            twi_status::TW_BUS_ERROR => {
+                p.raw_send_stop();
+                Some(Err(Error::BusError))
+            }
+            _ => {
+                p.raw_send_stop();
+                Some(Err(Error::Unknown))
+            }
+        }
+    }
+}
 
-            #[inline]
-            fn raw_start(&mut self, address: u8, direction: Direction) -> Result<(), Error> {
-                // Write start condition
-                self.twcr
-                    .write(|w| w.twen().set_bit().twint().set_bit().twsta().set_bit());
-                // wait()
-                while self.twcr.read().twint().bit_is_clear() {}
+/// Transfer buffer shared between an [`InterruptI2c`] and its interrupt handler.
+///
+/// The buffer must live in a `static` so the interrupt handler can access it.  Use the
+/// `impl_interrupt_i2c!` macro of your MCU HAL to create it together with the handler.  It needs
+/// to hold all bytes written and read in one transaction.
+///
+/// The buffer is tied to the `I2C` peripheral whose interrupt handler runs the transactions, so an
+/// [`InterruptI2c`] can only be created with the buffer of its own TWI.
+pub struct I2cBuffer<I2C, const N: usize> {
+    state: avr_device::interrupt::Mutex<RefCell<TransferState<N>>>,
+    _i2c: PhantomData<fn() -> I2C>,
+}
 
-                // Validate status
-                match self.twsr.read().tws().bits() {
-                    $crate::i2c::twi_status::TW_START | $crate::i2c::twi_status::TW_REP_START => (),
-                    $crate::i2c::twi_status::TW_MT_ARB_LOST
-                    | $crate::i2c::twi_status::TW_MR_ARB_LOST => {
-                        return Err($crate::i2c::Error::ArbitrationLost);
-                    }
-                    $crate::i2c::twi_status::TW_BUS_ERROR => {
-                        return Err($crate::i2c::Error::BusError);
-                    }
-                    _ => {
-                        return Err($crate::i2c::Error::Unknown);
-                    }
+impl<I2C, const N: usize> I2cBuffer<I2C, N> {
+    /// Create an empty buffer.
+    pub const fn new() -> Self {
+        Self {
+            _i2c: PhantomData,
+            state: avr_device::interrupt::Mutex::new(RefCell::new(TransferState {
+                data: [0; N],
+                segments: [Segment {
+                    direction: Direction::Write,
+                    len: 0,
+                }; MAX_SEGMENTS],
+                segment_count: 0,
+                address: 0,
+                segment: 0,
+                remaining: 0,
+                pos: 0,
+                status: TransferStatus::Idle,
+                waker: None,
+            })),
+        }
+    }
+
+    fn with<R>(&self, f: impl FnOnce(&mut TransferState<N>) -> R) -> R {
+        avr_device::interrupt::free(|cs| f(&mut self.state.borrow(cs).borrow_mut()))
+    }
+
+    /// Continue the running transaction.
+    ///
+    /// **Warning**: This is a low-level method which is called from the `TWI` interrupt handler
+    /// and should not be called directly from user code.
+    pub fn on_twi<H, SDA, SCL>(&self, p: &mut I2C)
+    where
+        I2C: I2cInterruptOps<H, SDA, SCL>,
+    {
+        self.with(|state| {
+            if state.status != TransferStatus::Running {
+                p.raw_release_bus();
+                return;
+            }
+
+            if let Some(result) = state.step(p) {
+                state.status = TransferStatus::Done(result);
+                if let Some(waker) = state.waker.take() {
+                    waker.wake();
                 }
+            }
+        });
+    }
+}
 
-                // Send slave address
-                let dirbit = if direction == $crate::i2c::Direction::Read {
-                    1
-                } else {
-                    0
-                };
-                let rawaddr = (address << 1) | dirbit;
-                self.twdr.write(|w| unsafe { w.bits(rawaddr) });
-                // transact()
-                self.twcr.write(|w| w.twen().set_bit().twint().set_bit());
-                while self.twcr.read().twint().bit_is_clear() {}
+/// I2C driver running transactions from the TWI interrupt
+///
+/// A transaction is started with [`InterruptI2c::start()`] and runs in the background while the
+/// application continues.  The written data is copied into a static [`I2cBuffer`] beforehand
+/// and the read data is collected there, so [`InterruptI2c::finish()`] copies it into the read
+/// buffers of the operations once the transaction has finished.  The buffer and the interrupt
+/// handler are created by the `impl_interrupt_i2c!` macro of your MCU HAL.
+///
+/// Adjacent operations in the same direction are merged just like with [`I2c`], a repeated
+/// start is only sent when the direction changes.  A transaction can contain at most 8 such
+/// parts.
+///
+/// `InterruptI2c` also implements the `embedded-hal-async` I2C trait, where tasks waiting for a
+/// transaction are woken from the interrupt handler.
+///
+/// # Example
+/// (for Arduino Uno)
+/// ```
+/// arduino_hal::hal::impl_interrupt_i2c! {
+///     static I2C_BUFFER: TWI<32>;
+/// }
+///
+/// let i2c = arduino_hal::I2c::new(
+///     dp.TWI,
+///     pins.a4.into_pull_up_input(),
+///     pins.a5.into_pull_up_input(),
+///     50000,
+/// );
+/// let mut i2c = InterruptI2c::new(i2c, &I2C_BUFFER);
+/// unsafe { avr_device::interrupt::enable() };
+///
+/// let mut data = [0u8; 16];
+/// let mut operations = [Operation::Write(&[0x00, 0x00]), Operation::Read(&mut data)];
+/// i2c.start(0x50, &operations).unwrap();
+///
+/// // Do something else in the meantime ...
+///
+/// nb::block!(i2c.finish(&mut operations)).unwrap();
+/// ```
+pub struct InterruptI2c<H, I2C: I2cOps<H, SDA, SCL> + 'static, SDA, SCL, CLOCK, const N: usize> {
+    i2c: I2c<H, I2C, SDA, SCL, CLOCK>,
+    buffer: &'static I2cBuffer<I2C, N>,
+}
 
-                // Check if the slave responded
-                match self.twsr.read().tws().bits() {
-                    $crate::i2c::twi_status::TW_MT_SLA_ACK
-                    | $crate::i2c::twi_status::TW_MR_SLA_ACK => (),
-                    $crate::i2c::twi_status::TW_MT_SLA_NACK
-                    | $crate::i2c::twi_status::TW_MR_SLA_NACK => {
-                        // Stop the transaction if it did not respond
-                        self.raw_stop()?;
-                        return Err($crate::i2c::Error::AddressNack);
+impl<H, I2C, SDA, SCL, CLOCK, const N: usize> InterruptI2c<H, I2C, SDA, SCL, CLOCK, N>
+where
+    I2C: I2cOps<H, SDA, SCL> + I2cInterruptOps<H, SDA, SCL> + 'static,
+{
+    /// Run transactions of the given I2C peripheral from its interrupt.
+    ///
+    /// Interrupts must be enabled globally for any data to be transferred.
+    pub fn new(i2c: I2c<H, I2C, SDA, SCL, CLOCK>, buffer: &'static I2cBuffer<I2C, N>) -> Self {
+        buffer.with(|state| {
+            state.status = TransferStatus::Idle;
+            state.waker = None;
+        });
+
+        Self { i2c, buffer }
+    }
+
+    /// Start a transaction with the device at `address`.
+    ///
+    /// The data of all write operations is copied, so `operations` does not need to be kept
+    /// around unchanged.  Returns [`nb::Error::WouldBlock`] while the previous transaction is still
+    /// running.  The result of a previous transaction which was not collected using
+    /// [`InterruptI2c::finish()`] is discarded.
+    ///
+    /// # Panics
+    /// Panics if the operations do not fit into the buffer or change direction more than 7 times.
+    pub fn start(
+        &mut self,
+        address: u8,
+        operations: &[embedded_hal::i2c::Operation<'_>],
+    ) -> nb::Result<(), core::convert::Infallible> {
+        if self.try_start(address, operations, None) {
+            Ok(())
+        } else {
+            Err(nb::Error::WouldBlock)
+        }
+    }
+
+    /// Whether the last transaction has finished.
+    pub fn is_done(&self) -> bool {
+        self.buffer.with(|state| state.status != TransferStatus::Running)
+    }
+
+    /// Collect the result of the last transaction.
+    ///
+    /// Returns [`nb::Error::WouldBlock`] while the transaction is still running.  Afterwards, the
+    /// received data is copied into the read operations, which must be the same as the ones passed
+    /// to [`InterruptI2c::start()`].
+    pub fn finish(
+        &mut self,
+        operations: &mut [embedded_hal::i2c::Operation<'_>],
+    ) -> nb::Result<(), Error> {
+        match self.try_finish(operations, None) {
+            Some(result) => result.map_err(nb::Error::Other),
+            None => Err(nb::Error::WouldBlock),
+        }
+    }
+
+    /// Wait for the last transaction to finish and return the blocking I2C driver.
+    pub fn release(self) -> I2c<H, I2C, SDA, SCL, CLOCK> {
+        while !self.is_done() {}
+        self.i2c
+    }
+
+    /// Set up and start a transaction unless one is already running.
+    ///
+    /// If one is running, `waker` (if any) is registered to be woken when it finished.
+    fn try_start(
+        &mut self,
+        address: u8,
+        operations: &[embedded_hal::i2c::Operation<'_>],
+        waker: Option<&Waker>,
+    ) -> bool {
+        let p = &mut self.i2c.p;
+        self.buffer.with(|state| {
+            if state.status == TransferStatus::Running {
+                state.waker = waker.cloned();
+                return false;
+            }
+
+            let mut pos = 0;
+            state.segment_count = 0;
+            for operation in operations {
+                let (direction, len) = match operation {
+                    embedded_hal::i2c::Operation::Write(bytes) => {
+                        assert!(pos + bytes.len() <= N, "I2C transaction exceeds the buffer");
+                        state.data[pos..pos + bytes.len()].copy_from_slice(bytes);
+                        (Direction::Write, bytes.len())
                     }
-                    $crate::i2c::twi_status::TW_MT_ARB_LOST
-                    | $crate::i2c::twi_status::TW_MR_ARB_LOST => {
-                        return Err($crate::i2c::Error::ArbitrationLost);
+                    // Nothing to receive, as opposed to an empty write which addresses the device.
+                    embedded_hal::i2c::Operation::Read(buffer) if buffer.is_empty() => continue,
+                    embedded_hal::i2c::Operation::Read(buffer) => {
+                        assert!(pos + buffer.len() <= N, "I2C transaction exceeds the buffer");
+                        (Direction::Read, buffer.len())
                     }
-                    $crate::i2c::twi_status::TW_BUS_ERROR => {
-                        return Err($crate::i2c::Error::BusError);
+                };
+                pos += len;
+
+                match state.segment_count.checked_sub(1) {
+                    Some(last) if state.segments[last].direction == direction => {
+                        state.segments[last].len += len;
                     }
                     _ => {
-                        return Err($crate::i2c::Error::Unknown);
+                        assert!(
+                            state.segment_count < MAX_SEGMENTS,
+                            "too many direction changes in I2C transaction"
+                        );
+                        state.segments[state.segment_count] = Segment { direction, len };
+                        state.segment_count += 1;
                     }
                 }
+            }
 
-                Ok(())
+            if state.segment_count == 0 {
+                state.status = TransferStatus::Done(Ok(()));
+                return true;
             }
 
-            #[inline]
-            fn raw_write(&mut self, bytes: &[u8]) -> Result<(), Error> {
-                for byte in bytes {
-                    self.twdr.write(|w| unsafe { w.bits(*byte) });
-                    // transact()
-                    self.twcr.write(|w| w.twen().set_bit().twint().set_bit());
-                    while self.twcr.read().twint().bit_is_clear() {}
+            state.address = address;
+            state.segment = 0;
+            state.remaining = state.segments[0].len;
+            state.pos = 0;
+            state.status = TransferStatus::Running;
+            p.raw_send_start();
+            true
+        })
+    }
 
-                    match self.twsr.read().tws().bits() {
-                        $crate::i2c::twi_status::TW_MT_DATA_ACK => (),
-                        $crate::i2c::twi_status::TW_MT_DATA_NACK => {
-                            self.raw_stop()?;
+    /// Copy the received data into `operations` once the transaction has finished.
+    ///
+    /// While it is running, `waker` (if any) is registered to be woken when it finished.
+    fn try_finish(
+        &mut self,
+        operations: &mut [embedded_hal::i2c::Operation<'_>],
+        waker: Option<&Waker>,
+    ) -> Option<Result<(), Error>> {
+        self.buffer.with(|state| {
+            let result = match state.status {
+                TransferStatus::Idle => return Some(Ok(())),
+                TransferStatus::Running => {
+                    state.waker = waker.cloned();
+                    return None;
+                }
+                TransferStatus::Done(result) => result,
+            };
+            state.status = TransferStatus::Idle;
+
+            if result.is_ok() {
+                let mut pos = 0;
+                for operation in operations.iter_mut() {
+                    match operation {
+                        embedded_hal::i2c::Operation::Write(bytes) => pos += bytes.len(),
+                        embedded_hal::i2c::Operation::Read(buffer) => {
+                            let len = buffer.len().min(N.saturating_sub(pos));
+                            buffer[..len].copy_from_slice(&state.data[pos..pos + len]);
+                            pos += len;
+                        }
+                    }
+                }
+            }
+            Some(result)
+        })
+    }
+}
+
+impl<H, I2C, SDA, SCL, CLOCK, const N: usize> embedded_hal::i2c::ErrorType
+    for InterruptI2c<H, I2C, SDA, SCL, CLOCK, N>
+where
+    I2C: I2cOps<H, SDA, SCL> + 'static,
+{
+    type Error = Error;
+}
+
+impl<H, I2C, SDA, SCL, CLOCK, const N: usize> embedded_hal::i2c::I2c<SevenBitAddress>
+    for InterruptI2c<H, I2C, SDA, SCL, CLOCK, N>
+where
+    I2C: I2cOps<H, SDA, SCL> + I2cInterruptOps<H, SDA, SCL> + 'static,
+{
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [embedded_hal::i2c::Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        nb::block!(self.start(address, operations)).unwrap_infallible();
+        nb::block!(self.finish(operations))
+    }
+}
+
+impl<H, I2C, SDA, SCL, CLOCK, const N: usize> embedded_hal_async::i2c::I2c<SevenBitAddress>
+    for InterruptI2c<H, I2C, SDA, SCL, CLOCK, N>
+where
+    I2C: I2cOps<H, SDA, SCL> + I2cInterruptOps<H, SDA, SCL> + 'static,
+{
+    async fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [embedded_hal::i2c::Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        core::future::poll_fn(|cx| match self.try_start(address, operations, Some(cx.waker())) {
+            true => Poll::Ready(()),
+            false => Poll::Pending,
+        })
+        .await;
+        core::future::poll_fn(|cx| match self.try_finish(operations, Some(cx.waker())) {
+            Some(result) => Poll::Ready(result),
+            None => Poll::Pending,
+        })
+        .await
+    }
+}
+
+/// Address(es) an [`I2cTarget`] responds to
+///
+/// A plain `u8` converts into a `TargetAddress` for just that 7-bit address.
+#[derive(ufmt::derive::uDebug, Debug, Clone, Copy, Eq, PartialEq)]
+pub struct TargetAddress {
+    /// 7-bit address of this device
+    pub address: u8,
+    /// Address bits which are ignored when matching an incoming address (`TWAMR`).
+    ///
+    /// This allows responding to a whole range of addresses.  It is ignored on MCUs without a
+    /// `TWAMR` register (ATmega8, ATmega32A and ATmega128A).
+    pub mask: u8,
+    /// Whether to also respond to the general call address `0x00`
+    pub general_call: bool,
+}
+
+impl TargetAddress {
+    /// Respond to a single 7-bit address only.
+    pub const fn new(address: u8) -> Self {
+        Self {
+            address,
+            mask: 0,
+            general_call: false,
+        }
+    }
+}
+
+impl From<u8> for TargetAddress {
+    fn from(address: u8) -> Self {
+        Self::new(address)
+    }
+}
+
+/// Events reported by [`I2cTarget::poll()`]
+#[derive(ufmt::derive::uDebug, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TargetEvent {
+    /// The controller addressed this device for writing.
+    ///
+    /// `general_call` is set if the general call address was used instead of the own address.
+    WriteStart { general_call: bool },
+    /// The controller wrote a byte to this device.
+    WriteReceived(u8),
+    /// The controller wants to read a byte from this device.
+    ///
+    /// The bus is held (clock stretching) until the byte is provided using
+    /// [`I2cTarget::respond()`].  `start` is set for the first byte directly after this device was
+    /// addressed.
+    ReadRequest { start: bool },
+    /// The transfer ended.
+    ///
+    /// For write transfers, this is reported for a stop or repeated start condition.  Read
+    /// transfers end when the controller does not acknowledge the last byte.
+    Stop,
+}
+
+/// Internal trait for low-level I2C peripherals operating as a target (slave).
+///
+/// This trait defines the common interface for all I2C peripheral variants which can be
+/// addressed by another controller.  It is used as an intermediate abstraction ontop of which
+/// the [`I2cTarget`] API is built.  **Prefer using the [`I2cTarget`] API instead of this trait.**
+pub trait I2cTargetOps<H, SDA, SCL> {
+    /// Enable the peripheral and start responding to the given address.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_target_setup(&mut self, address: TargetAddress);
+
+    /// Disable the peripheral such that the pins can be used for other purposes again.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_target_release(&mut self);
+
+    /// Check for the next bus event.
+    ///
+    /// This operation must be non-blocking and return [`nb::Error::WouldBlock`] if nothing
+    /// happened.  All events except [`TargetEvent::ReadRequest`] are acknowledged before
+    /// returning, so the bus continues.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_target_event(&mut self) -> nb::Result<TargetEvent, Error>;
+
+    /// Answer a [`TargetEvent::ReadRequest`] with the given byte.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_target_respond(&mut self, byte: u8);
+
+    /// Enable/Disable the interrupt which fires for every bus event.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_target_interrupt(&mut self, state: bool);
+}
+
+/// I2C target (slave) driver
+///
+/// The peripheral waits to be addressed by another controller on the bus.  Every step of a
+/// transfer is reported as a [`TargetEvent`] from [`I2cTarget::poll()`], which can either be
+/// called in a loop or from the TWI interrupt handler after calling [`I2cTarget::listen()`].  The
+/// interrupt keeps firing until the event was handled, so the handler must answer each
+/// [`TargetEvent::ReadRequest`] with [`I2cTarget::respond()`] right away.
+///
+/// # Example
+/// (for Arduino Uno)
+/// ```
+/// use arduino_hal::i2c::{I2cTarget, TargetEvent};
+///
+/// let dp = arduino_hal::Peripherals::take().unwrap();
+/// let pins = arduino_hal::pins!(dp);
+///
+/// let mut target = I2cTarget::new(
+///     dp.TWI,
+///     pins.a4.into_pull_up_input(),
+///     pins.a5.into_pull_up_input(),
+///     0x42,
+/// );
+///
+/// let mut register = 0u8;
+/// loop {
+///     match nb::block!(target.poll()) {
+///         Ok(TargetEvent::WriteReceived(value)) => register = value,
+///         Ok(TargetEvent::ReadRequest { .. }) => target.respond(register),
+///         _ => (),
+///     }
+/// }
+/// ```
+pub struct I2cTarget<H, I2C: I2cTargetOps<H, SDA, SCL>, SDA, SCL> {
+    p: I2C,
+    sda: SDA,
+    scl: SCL,
+    _h: PhantomData<H>,
+}
+
+impl<H, I2C, SDAPIN, SCLPIN>
+    I2cTarget<H, I2C, port::Pin<port::mode::Input, SDAPIN>, port::Pin<port::mode::Input, SCLPIN>>
+where
+    I2C: I2cTargetOps<
+        H,
+        port::Pin<port::mode::Input, SDAPIN>,
+        port::Pin<port::mode::Input, SCLPIN>,
+    >,
+    SDAPIN: port::PinOps,
+    SCLPIN: port::PinOps,
+{
+    /// Initialize an I2C peripheral as a target on the given pins.
+    ///
+    /// Note that the SDA and SCL pins are hardwired for each I2C peripheral and you *must* pass
+    /// the correct ones.  This is enforced at compile time.
+    ///
+    /// `address` is either a plain 7-bit address or a [`TargetAddress`] for also responding to
+    /// the general call address or a range of addresses.
+    ///
+    /// This method expects the internal pull-ups to be configured for both pins to comply with the
+    /// I2C specification.  If you have external pull-ups connected, use
+    /// [`I2cTarget::with_external_pullup`] instead.
+    pub fn new(
+        p: I2C,
+        sda: port::Pin<port::mode::Input<port::mode::PullUp>, SDAPIN>,
+        scl: port::Pin<port::mode::Input<port::mode::PullUp>, SCLPIN>,
+        address: impl Into<TargetAddress>,
+    ) -> Self {
+        let mut target = Self {
+            p,
+            sda: sda.forget_imode(),
+            scl: scl.forget_imode(),
+            _h: PhantomData,
+        };
+        target.p.raw_target_setup(address.into());
+        target
+    }
+
+    /// Initialize an I2C peripheral as a target on the given pins.
+    ///
+    /// Note that the SDA and SCL pins are hardwired for each I2C peripheral and you *must* pass
+    /// the correct ones.  This is enforced at compile time.
+    ///
+    /// This method expects that external resistors pull up SDA and SCL.
+    pub fn with_external_pullup(
+        p: I2C,
+        sda: port::Pin<port::mode::Input<port::mode::Floating>, SDAPIN>,
+        scl: port::Pin<port::mode::Input<port::mode::Floating>, SCLPIN>,
+        address: impl Into<TargetAddress>,
+    ) -> Self {
+        let mut target = Self {
+            p,
+            sda: sda.forget_imode(),
+            scl: scl.forget_imode(),
+            _h: PhantomData,
+        };
+        target.p.raw_target_setup(address.into());
+        target
+    }
+}
+
+impl<H, I2C: I2cTargetOps<H, SDA, SCL>, SDA, SCL> I2cTarget<H, I2C, SDA, SCL> {
+    /// Check for the next bus event.
+    ///
+    /// Returns [`nb::Error::WouldBlock`] if nothing happened since the last call.
+    pub fn poll(&mut self) -> nb::Result<TargetEvent, Error> {
+        self.p.raw_target_event()
+    }
+
+    /// Answer a [`TargetEvent::ReadRequest`] with the given byte.
+    ///
+    /// This must only be called once for each read request.
+    pub fn respond(&mut self, byte: u8) {
+        self.p.raw_target_respond(byte);
+    }
+
+    /// Enable the TWI interrupt for all bus events.
+    pub fn listen(&mut self) {
+        self.p.raw_target_interrupt(true);
+    }
+
+    /// Disable the TWI interrupt.
+    pub fn unlisten(&mut self) {
+        self.p.raw_target_interrupt(false);
+    }
+
+    /// Disable this peripheral and release the pins.
+    pub fn release(mut self) -> (I2C, SDA, SCL) {
+        self.p.raw_target_release();
+        (self.p, self.sda, self.scl)
+    }
+}
+
+/// Bit-banged I2C controller on arbitrary pins
+///
+/// This drives the bus in software, for MCUs without a TWI peripheral or when the TWI pins are
+/// needed elsewhere.  Both pins are used in open-drain mode, so external pull-up resistors on SDA
+/// and SCL are required.
+///
+/// The bus clock is derived from [`Delay`][crate::delay::Delay], so the requested `speed` (in Hz,
+/// usually 100 kHz for standard mode or 400 kHz for fast mode) is an upper limit.  The actual
+/// clock is somewhat slower due to the overhead of toggling the pins, especially in fast mode.
+/// Devices are allowed to stretch the clock by holding SCL low; [`SoftI2c::set_timeout()`]
+/// limits how long to wait for them.
+///
+/// # Example
+/// (for Arduino Uno)
+/// ```
+/// let dp = arduino_hal::Peripherals::take().unwrap();
+/// let pins = arduino_hal::pins!(dp);
+///
+/// let mut i2c = arduino_hal::i2c::SoftI2c::new(
+///     pins.d2.into_opendrain_high(),
+///     pins.d3.into_opendrain_high(),
+///     100_000,
+/// );
+///
+/// // i2c implements the embedded-hal traits so it can be used with generic drivers.
+/// ```
+pub struct SoftI2c<SDA, SCL, CLOCK> {
+    sda: port::Pin<port::mode::OpenDrain, SDA>,
+    scl: port::Pin<port::mode::OpenDrain, SCL>,
+    half_period_ns: u32,
+    timeout: Option<u32>,
+    started: bool,
+    _clock: PhantomData<CLOCK>,
+}
+
+impl<SDA, SCL, CLOCK> SoftI2c<SDA, SCL, CLOCK>
+where
+    SDA: port::PinOps,
+    SCL: port::PinOps,
+    crate::delay::Delay<CLOCK>: embedded_hal::delay::DelayNs,
+{
+    /// Initialize a software I2C bus on the given pins.
+    ///
+    /// Both lines are released, so the bus is idle afterwards.
+    pub fn new(
+        mut sda: port::Pin<port::mode::OpenDrain, SDA>,
+        mut scl: port::Pin<port::mode::OpenDrain, SCL>,
+        speed: u32,
+    ) -> Self {
+        sda.set_high();
+        scl.set_high();
+        Self {
+            sda,
+            scl,
+            half_period_ns: 500_000_000 / speed.max(1),
+            timeout: None,
+            started: false,
+            _clock: PhantomData,
+        }
+    }
+
+    /// Limit how long to wait for a device stretching the clock, in CPU cycles.
+    ///
+    /// Works like [`I2c::set_timeout()`].  `None` disables the timeout, which is the default.
+    pub fn set_timeout(&mut self, cycles: Option<u32>) {
+        self.timeout = cycles;
+    }
+
+    /// Test whether a device answers on a certain address.
+    pub fn ping_device(&mut self, address: u8, direction: Direction) -> Result<bool, Error> {
+        let result = self.start(address, direction).and_then(|_| {
+            if direction == Direction::Read {
+                // The device drives SDA until it sees a NACK.
+                self.read_byte(false)?;
+            }
+            Ok(())
+        });
+        match self.finish(result) {
+            Ok(_) => Ok(true),
+            Err(Error::AddressNack) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Release the pins.
+    pub fn release(
+        self,
+    ) -> (
+        port::Pin<port::mode::OpenDrain, SDA>,
+        port::Pin<port::mode::OpenDrain, SCL>,
+    ) {
+        (self.sda, self.scl)
+    }
+
+    fn delay(&self) {
+        use embedded_hal::delay::DelayNs;
+        crate::delay::Delay::<CLOCK>::new().delay_ns(self.half_period_ns);
+    }
+
+    /// Release SCL and wait until no device stretches the clock anymore.
+    fn release_scl(&mut self) -> Result<(), Error> {
+        self.scl.set_high();
+        let scl = &self.scl;
+        wait_until(self.timeout, || scl.is_high())
+    }
+
+    /// Generate a (repeated) start condition and send the address.
+    fn start(&mut self, address: u8, direction: Direction) -> Result<(), Error> {
+        if self.started {
+            // SDA must be high before SCL rises for the repeated start condition.
+            self.sda.set_high();
+            self.delay();
+            self.release_scl()?;
+            self.delay();
+        } else if self.sda.is_low() || self.scl.is_low() {
+            // Another controller is using the bus.
+            return Err(Error::ArbitrationLost);
+        }
+
+        // Start condition: SDA falls while SCL is high
+        self.sda.set_low();
+        self.started = true;
+        self.delay();
+        self.scl.set_low();
+
+        if self.write_byte(address << 1 | direction as u8)? {
+            Ok(())
+        } else {
+            Err(Error::AddressNack)
+        }
+    }
+
+    /// Generate a stop condition.
+    fn stop(&mut self) -> Result<(), Error> {
+        self.started = false;
+
+        // Stop condition: SDA rises while SCL is high
+        self.sda.set_low();
+        self.delay();
+        self.release_scl()?;
+        self.delay();
+        self.sda.set_high();
+        self.delay();
+
+        if self.sda.is_low() {
+            return Err(Error::ArbitrationLost);
+        }
+        Ok(())
+    }
+
+    /// End a transfer with `result`.
+    ///
+    /// After arbitration was lost or a timeout occurred, both lines are just released.  Otherwise
+    /// a stop condition is generated.
+    fn finish<T>(&mut self, result: Result<T, Error>) -> Result<T, Error> {
+        match result {
+            Err(Error::ArbitrationLost) | Err(Error::Timeout) => {
+                self.sda.set_high();
+                self.scl.set_high();
+                self.started = false;
+                result
+            }
+            _ if self.started => {
+                let stop = self.stop();
+                result.and_then(|value| stop.map(|_| value))
+            }
+            _ => result,
+        }
+    }
+
+    fn write_bit(&mut self, bit: bool) -> Result<(), Error> {
+        if bit {
+            self.sda.set_high();
+        } else {
+            self.sda.set_low();
+        }
+        self.delay();
+        self.release_scl()?;
+
+        // Another controller pulling SDA low while it is released wins the arbitration.
+        if bit && self.sda.is_low() {
+            return Err(Error::ArbitrationLost);
+        }
+
+        self.delay();
+        self.scl.set_low();
+        Ok(())
+    }
+
+    fn read_bit(&mut self) -> Result<bool, Error> {
+        self.sda.set_high();
+        self.delay();
+        self.release_scl()?;
+        let bit = self.sda.is_high();
+        self.delay();
+        self.scl.set_low();
+        Ok(bit)
+    }
+
+    /// Send a byte and return whether the device acknowledged it.
+    fn write_byte(&mut self, byte: u8) -> Result<bool, Error> {
+        for i in (0..8).rev() {
+            self.write_bit(byte & (1 << i) != 0)?;
+        }
+        Ok(!self.read_bit()?)
+    }
+
+    /// Receive a byte and acknowledge it if `ack` is set.
+    fn read_byte(&mut self, ack: bool) -> Result<u8, Error> {
+        let mut byte = 0;
+        for _ in 0..8 {
+            byte = byte << 1 | self.read_bit()? as u8;
+        }
+        self.write_bit(!ack)?;
+        Ok(byte)
+    }
+
+    fn transfer(
+        &mut self,
+        address: u8,
+        operations: &mut [embedded_hal::i2c::Operation<'_>],
+    ) -> Result<(), Error> {
+        let mut previous_direction = None;
+        for idx in 0..operations.len() {
+            // Adjacent reads are merged, so only the last byte before a stop or repeated start
+            // condition is not acknowledged.
+            let next_is_read = matches!(
+                operations.get(idx + 1),
+                Some(embedded_hal::i2c::Operation::Read(_))
+            );
+
+            match &mut operations[idx] {
+                embedded_hal::i2c::Operation::Read(buffer) => {
+                    if previous_direction != Some(Direction::Read) {
+                        self.start(address, Direction::Read)?;
+                    }
+                    let len = buffer.len();
+                    for (i, byte) in buffer.iter_mut().enumerate() {
+                        *byte = self.read_byte(next_is_read || i + 1 < len)?;
+                    }
+                    previous_direction = Some(Direction::Read);
+                }
+                embedded_hal::i2c::Operation::Write(bytes) => {
+                    if previous_direction != Some(Direction::Write) {
+                        self.start(address, Direction::Write)?;
+                    }
+                    for &byte in bytes.iter() {
+                        if !self.write_byte(byte)? {
+                            return Err(Error::DataNack);
+                        }
+                    }
+                    previous_direction = Some(Direction::Write);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<SDA, SCL, CLOCK> SoftI2c<SDA, SCL, CLOCK>
+where
+    SDA: port::PinOps,
+    SCL: port::PinOps,
+    crate::delay::Delay<CLOCK>:
+        embedded_hal::delay::DelayNs + embedded_hal_v0::blocking::delay::DelayMs<u16>,
+{
+    /// Scan the bus for connected devices.
+    ///
+    /// The output is the same as for [`I2c::i2cdetect()`].
+    pub fn i2cdetect<W: ufmt::uWrite>(
+        &mut self,
+        w: &mut W,
+        direction: Direction,
+    ) -> Result<(), W::Error> {
+        i2cdetect::<W, CLOCK>(w, |address| self.ping_device(address, direction))
+    }
+}
+
+impl<SDA, SCL, CLOCK> embedded_hal::i2c::ErrorType for SoftI2c<SDA, SCL, CLOCK> {
+    type Error = Error;
+}
+
+impl<SDA, SCL, CLOCK> embedded_hal::i2c::I2c<SevenBitAddress> for SoftI2c<SDA, SCL, CLOCK>
+where
+    SDA: port::PinOps,
+    SCL: port::PinOps,
+    crate::delay::Delay<CLOCK>: embedded_hal::delay::DelayNs,
+{
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [embedded_hal::i2c::Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        let result = self.transfer(address, operations);
+        self.finish(result)
+    }
+}
+
+/// Implement [`I2cOps`] and [`I2cTargetOps`] for a TWI peripheral.
+///
+/// `address_mask_register` names the `TWAMR` register on MCUs which have one.
+#[macro_export]
+macro_rules! impl_i2c_twi {
+    (
+        hal: $HAL:ty,
+        peripheral: $I2C:ty,
+        sda: $sdapin:ty,
+        scl: $sclpin:ty,
+        $(address_mask_register: $twamr:ident,)?
+    ) => {
+        impl
+            $crate::i2c::I2cOps<
+                $HAL,
+                $crate::port::Pin<$crate::port::mode::Input, $sdapin>,
+                $crate::port::Pin<$crate::port::mode::Input, $sclpin>,
+            > for $I2C
+        {
+            #[inline]
+            fn raw_setup<CLOCK: $crate::clock::Clock>(&mut self, speed: u32) {
+                // Calculate TWBR register value
+                let twbr = ((CLOCK::FREQ / speed) - 16) / 2;
+                self.twbr.write(|w| unsafe { w.bits(twbr as u8) });
+
+                // Disable prescaler
+                self.twsr.write(|w| w.twps().prescaler_1());
+            }
+
+            #[inline]
+            fn raw_start(
+                &mut self,
+                address: u8,
+                direction: Direction,
+                timeout: Option<u32>,
+            ) -> Result<(), Error> {
+                // Write start condition
+                self.twcr
+                    .write(|w| w.twen().set_bit().twint().set_bit().twsta().set_bit());
+                // wait()
+                $crate::i2c::wait_until(timeout, || self.twcr.read().twint().bit_is_set())?;
+
+                // Validate status
+                match self.twsr.read().tws().bits() {
+                    $crate::i2c::twi_status::TW_START | $crate::i2c::twi_status::TW_REP_START => (),
+                    $crate::i2c::twi_status::TW_MT_ARB_LOST
+                    | $crate::i2c::twi_status::TW_MR_ARB_LOST => {
+                        return Err($crate::i2c::Error::ArbitrationLost);
+                    }
+                    $crate::i2c::twi_status::TW_BUS_ERROR => {
+                        return Err($crate::i2c::Error::BusError);
+                    }
+                    _ => {
+                        return Err($crate::i2c::Error::Unknown);
+                    }
+                }
+
+                // Send slave address
+                let dirbit = if direction == $crate::i2c::Direction::Read {
+                    1
+                } else {
+                    0
+                };
+                let rawaddr = (address << 1) | dirbit;
+                self.twdr.write(|w| unsafe { w.bits(rawaddr) });
+                // transact()
+                self.twcr.write(|w| w.twen().set_bit().twint().set_bit());
+                $crate::i2c::wait_until(timeout, || self.twcr.read().twint().bit_is_set())?;
+
+                // Check if the slave responded
+                match self.twsr.read().tws().bits() {
+                    $crate::i2c::twi_status::TW_MT_SLA_ACK
+                    | $crate::i2c::twi_status::TW_MR_SLA_ACK => (),
+                    $crate::i2c::twi_status::TW_MT_SLA_NACK
+                    | $crate::i2c::twi_status::TW_MR_SLA_NACK => {
+                        // Stop the transaction if it did not respond
+                        self.raw_stop(timeout)?;
+                        return Err($crate::i2c::Error::AddressNack);
+                    }
+                    $crate::i2c::twi_status::TW_MT_ARB_LOST
+                    | $crate::i2c::twi_status::TW_MR_ARB_LOST => {
+                        return Err($crate::i2c::Error::ArbitrationLost);
+                    }
+                    $crate::i2c::twi_status::TW_BUS_ERROR => {
+                        return Err($crate::i2c::Error::BusError);
+                    }
+                    _ => {
+                        return Err($crate::i2c::Error::Unknown);
+                    }
+                }
+
+                Ok(())
+            }
+
+            #[inline]
+            fn raw_write(&mut self, bytes: &[u8], timeout: Option<u32>) -> Result<(), Error> {
+                for byte in bytes {
+                    self.twdr.write(|w| unsafe { w.bits(*byte) });
+                    // transact()
+                    self.twcr.write(|w| w.twen().set_bit().twint().set_bit());
+                    $crate::i2c::wait_until(timeout, || self.twcr.read().twint().bit_is_set())?;
+
+                    match self.twsr.read().tws().bits() {
+                        $crate::i2c::twi_status::TW_MT_DATA_ACK => (),
+                        $crate::i2c::twi_status::TW_MT_DATA_NACK => {
+                            self.raw_stop(timeout)?;
                             return Err($crate::i2c::Error::DataNack);
                         }
                         $crate::i2c::twi_status::TW_MT_ARB_LOST => {
@@ -561,18 +1724,18 @@ macro_rules! impl_i2c_twi {
             }
 
             #[inline]
-            fn raw_read(&mut self, buffer: &mut [u8]) -> Result<(), Error> {
+            fn raw_read(&mut self, buffer: &mut [u8], timeout: Option<u32>) -> Result<(), Error> {
                 let last = buffer.len() - 1;
                 for (i, byte) in buffer.iter_mut().enumerate() {
                     if i != last {
                         self.twcr
                             .write(|w| w.twint().set_bit().twen().set_bit().twea().set_bit());
                         // wait()
-                        while self.twcr.read().twint().bit_is_clear() {}
+                        $crate::i2c::wait_until(timeout, || self.twcr.read().twint().bit_is_set())?;
                     } else {
                         self.twcr.write(|w| w.twint().set_bit().twen().set_bit());
                         // wait()
-                        while self.twcr.read().twint().bit_is_clear() {}
+                        $crate::i2c::wait_until(timeout, || self.twcr.read().twint().bit_is_set())?;
                     }
 
                     match self.twsr.read().tws().bits() {
@@ -595,11 +1758,208 @@ macro_rules! impl_i2c_twi {
             }
 
             #[inline]
-            fn raw_stop(&mut self) -> Result<(), Error> {
+            fn raw_stop(&mut self, _timeout: Option<u32>) -> Result<(), Error> {
                 self.twcr
                     .write(|w| w.twen().set_bit().twint().set_bit().twsto().set_bit());
                 Ok(())
             }
+
+            #[inline]
+            fn raw_disable(&mut self) {
+                self.twcr.reset();
+            }
+        }
+
+        impl
+            $crate::i2c::I2cInterruptOps<
+                $HAL,
+                $crate::port::Pin<$crate::port::mode::Input, $sdapin>,
+                $crate::port::Pin<$crate::port::mode::Input, $sclpin>,
+            > for $I2C
+        {
+            #[inline]
+            fn raw_send_start(&mut self) {
+                self.twcr.write(|w| {
+                    w.twen().set_bit().twie().set_bit().twint().set_bit().twsta().set_bit()
+                });
+            }
+
+            #[inline]
+            fn raw_send_byte(&mut self, byte: u8) {
+                self.twdr.write(|w| unsafe { w.bits(byte) });
+                self.twcr
+                    .write(|w| w.twen().set_bit().twie().set_bit().twint().set_bit());
+            }
+
+            #[inline]
+            fn raw_receive_byte(&mut self, ack: bool) {
+                self.twcr.write(|w| {
+                    w.twen().set_bit().twie().set_bit().twint().set_bit().twea().bit(ack)
+                });
+            }
+
+            #[inline]
+            fn raw_send_stop(&mut self) {
+                self.twcr
+                    .write(|w| w.twen().set_bit().twint().set_bit().twsto().set_bit());
+            }
+
+            #[inline]
+            fn raw_release_bus(&mut self) {
+                self.twcr.write(|w| w.twen().set_bit().twint().set_bit());
+            }
+
+            #[inline]
+            fn raw_status(&self) -> u8 {
+                self.twsr.read().tws().bits()
+            }
+
+            #[inline]
+            fn raw_received_byte(&self) -> u8 {
+                self.twdr.read().bits()
+            }
+        }
+
+        impl
+            $crate::i2c::I2cTargetOps<
+                $HAL,
+                $crate::port::Pin<$crate::port::mode::Input, $sdapin>,
+                $crate::port::Pin<$crate::port::mode::Input, $sclpin>,
+            > for $I2C
+        {
+            #[inline]
+            fn raw_target_setup(&mut self, address: $crate::i2c::TargetAddress) {
+                let twar = (address.address << 1) | address.general_call as u8;
+                self.twar.write(|w| unsafe { w.bits(twar) });
+                $(self.$twamr.write(|w| unsafe { w.bits(address.mask << 1) });)?
+
+                // Acknowledge our own address
+                self.twcr
+                    .write(|w| w.twen().set_bit().twea().set_bit().twint().set_bit());
+            }
+
+            #[inline]
+            fn raw_target_release(&mut self) {
+                self.twcr.reset();
+                self.twar.reset();
+                $(self.$twamr.reset();)?
+            }
+
+            #[inline]
+            fn raw_target_event(
+                &mut self,
+            ) -> $crate::nb::Result<$crate::i2c::TargetEvent, $crate::i2c::Error> {
+                use $crate::i2c::{twi_status, TargetEvent};
+
+                if self.twcr.read().twint().bit_is_clear() {
+                    return Err($crate::nb::Error::WouldBlock);
+                }
+
+                let event = match self.twsr.read().tws().bits() {
+                    twi_status::TW_SR_SLA_ACK | twi_status::TW_SR_ARB_LOST_SLA_ACK => {
+                        TargetEvent::WriteStart {
+                            general_call: false,
+                        }
+                    }
+                    twi_status::TW_SR_GCALL_ACK | twi_status::TW_SR_ARB_LOST_GCALL_ACK => {
+                        TargetEvent::WriteStart { general_call: true }
+                    }
+                    twi_status::TW_SR_DATA_ACK
+                    | twi_status::TW_SR_DATA_NACK
+                    | twi_status::TW_SR_GCALL_DATA_ACK
+                    | twi_status::TW_SR_GCALL_DATA_NACK => {
+                        TargetEvent::WriteReceived(self.twdr.read().bits())
+                    }
+                    twi_status::TW_SR_STOP
+                    | twi_status::TW_ST_DATA_NACK
+                    | twi_status::TW_ST_LAST_DATA => TargetEvent::Stop,
+                    // The bus is held until the response was written to TWDR.
+                    twi_status::TW_ST_SLA_ACK | twi_status::TW_ST_ARB_LOST_SLA_ACK => {
+                        return Ok(TargetEvent::ReadRequest { start: true });
+                    }
+                    twi_status::TW_ST_DATA_ACK => {
+                        return Ok(TargetEvent::ReadRequest { start: false });
+                    }
+                    twi_status::TW_BUS_ERROR => {
+                        // Release the bus lines without sending a stop condition
+                        self.twcr.modify(|_, w| w.twint().set_bit().twsto().set_bit());
+                        return Err($crate::nb::Error::Other($crate::i2c::Error::BusError));
+                    }
+                    _ => {
+                        self.twcr.modify(|_, w| {
+                            w.twint().set_bit().twea().set_bit().twsta().clear_bit()
+                        });
+                        return Err($crate::nb::Error::Other($crate::i2c::Error::Unknown));
+                    }
+                };
+
+                // Acknowledge and continue with the next byte
+                self.twcr.modify(|_, w| {
+                    w.twint()
+                        .set_bit()
+                        .twea()
+                        .set_bit()
+                        .twsta()
+                        .clear_bit()
+                        .twsto()
+                        .clear_bit()
+                });
+                Ok(event)
+            }
+
+            #[inline]
+            fn raw_target_respond(&mut self, byte: u8) {
+                self.twdr.write(|w| unsafe { w.bits(byte) });
+                self.twcr.modify(|_, w| {
+                    w.twint()
+                        .set_bit()
+                        .twea()
+                        .set_bit()
+                        .twsta()
+                        .clear_bit()
+                        .twsto()
+                        .clear_bit()
+                });
+            }
+
+            #[inline]
+            fn raw_target_interrupt(&mut self, state: bool) {
+                // Writing zero to TWINT leaves a pending event untouched.
+                self.twcr.modify(|_, w| w.twie().bit(state).twint().clear_bit());
+            }
+        }
+    };
+}
+
+/// Create the transfer buffer of an [`InterruptI2c`] and install its interrupt handler.
+///
+/// This is used by the `impl_interrupt_i2c!` macro of the MCU HALs, which looks up the interrupt
+/// vector and pins of each TWI peripheral.  The crate invoking it needs
+/// `#![feature(abi_avr_interrupt)]`.
+#[macro_export]
+macro_rules! impl_interrupt_i2c {
+    (
+        mcu: $mcu:ident,
+        hal: $HAL:ty,
+        peripherals: $Peripherals:ty,
+        peripheral: $I2C:ident: $I2cTy:ty,
+        sda: $sdapin:ty,
+        scl: $sclpin:ty,
+        vector: $vector:ident,
+        $vis:vis static $NAME:ident: [$n:expr],
+    ) => {
+        $vis static $NAME: $crate::i2c::I2cBuffer<$I2cTy, { $n }> = $crate::i2c::I2cBuffer::new();
+
+        #[$crate::avr_device::interrupt($mcu)]
+        fn $vector() {
+            // SAFETY: The peripheral is owned by the `InterruptI2c`, which only touches the
+            // registers inside critical sections while a transaction is running.
+            let mut p = unsafe { <$Peripherals>::steal() }.$I2C;
+            $NAME.on_twi::<
+                $HAL,
+                $crate::port::Pin<$crate::port::mode::Input, $sdapin>,
+                $crate::port::Pin<$crate::port::mode::Input, $sclpin>,
+            >(&mut p);
         }
     };
 }
@@ -0,0 +1,377 @@
+//! Input Capture Implementation
+//!
+//! Check the documentation of [`InputCapture`] for details.
+
+use crate::port;
+use crate::timer::{Mode, Prescaler, TimerOps};
+use core::marker::PhantomData;
+use unwrap_infallible::UnwrapInfallible;
+
+/// Edge of the input capture pin which triggers a capture
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edge {
+    /// Capture on a falling edge (high to low).
+    Falling,
+    /// Capture on a rising edge (low to high).
+    Rising,
+}
+
+impl Edge {
+    /// The opposite edge.
+    pub fn opposite(self) -> Self {
+        match self {
+            Edge::Falling => Edge::Rising,
+            Edge::Rising => Edge::Falling,
+        }
+    }
+}
+
+/// Settings for the input capture unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Settings {
+    /// Clock prescaler of the timer, determining the resolution and range of the measurements.
+    pub prescaler: Prescaler,
+    /// Initial edge which triggers a capture.
+    pub edge: Edge,
+    /// Enable the noise canceler.
+    ///
+    /// The noise canceler only accepts a change of the input after four equal samples, delaying
+    /// each capture by four clock cycles.
+    pub noise_canceler: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            prescaler: Prescaler::Prescale64,
+            edge: Edge::Rising,
+            noise_canceler: false,
+        }
+    }
+}
+
+/// A timestamped edge on the input capture pin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capture {
+    /// Value of the counter when the edge occurred.
+    pub ticks: u16,
+    /// The edge which was captured.
+    pub edge: Edge,
+}
+
+impl Capture {
+    /// Number of timer ticks passed from `earlier` to this capture.
+    ///
+    /// The timer is free-running and wraps around after 65536 ticks, so the result is only
+    /// correct if less time than that has passed between the two captures.
+    pub fn ticks_since(&self, earlier: &Capture) -> u16 {
+        self.ticks.wrapping_sub(earlier.ticks)
+    }
+}
+
+/// Result of a full pulse measurement, see [`InputCapture::measure_blocking()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Measurement {
+    /// Duration of the first part of the period (starting on the configured edge) in ticks.
+    pub width: u16,
+    /// Duration of the whole period in ticks.
+    pub period: u16,
+}
+
+impl Measurement {
+    /// Duty cycle of the measured signal in percent.
+    pub fn duty_cycle_percent(&self) -> u8 {
+        if self.period == 0 {
+            return 0;
+        }
+        (self.width as u32 * 100 / self.period as u32) as u8
+    }
+}
+
+/// Internal trait for the input capture units of 16-bit timers.
+///
+/// This trait is implemented for the timer peripherals together with the pin which is hard-wired
+/// to their input capture unit (`ICPn`).  It is used as an intermediate abstraction ontop of which
+/// the [`InputCapture`] API is built.  **Prefer using the [`InputCapture`] API instead of this
+/// trait.**
+pub trait InputCaptureOps<H, ICP>: TimerOps<H, Counter = u16> {
+    /// Select the edge which triggers a capture.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_edge(&mut self, edge: Edge);
+
+    /// Enable/Disable the noise canceler.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_noise_canceler(&mut self, state: bool);
+
+    /// Read the input capture register.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_read_capture(&self) -> u16;
+
+    /// Enable/Disable the capture interrupt (`TIMERn_CAPT`).
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_capture_interrupt(&mut self, state: bool);
+
+    /// Check whether the input capture flag is set.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_is_capture_pending(&self) -> bool;
+
+    /// Clear the input capture flag.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_clear_capture_pending(&mut self);
+}
+
+/// Input capture driver for 16-bit timers
+///
+/// The timer is run freely in normal mode.  Whenever the selected edge occurs on the input capture
+/// pin (`ICPn`), the hardware copies the current counter value into the input capture register.
+/// This makes it possible to timestamp edges precisely without busy-polling the pin.
+///
+/// Measurements are limited to 65536 timer ticks, choose the prescaler accordingly.  At 16 MHz,
+/// `Prescale8` gives a resolution of 0.5µs and a range of about 32ms.
+///
+/// # Example
+/// (for Arduino Uno)
+/// ```
+/// let dp = arduino_hal::Peripherals::take().unwrap();
+/// let pins = arduino_hal::pins!(dp);
+///
+/// let mut capture = arduino_hal::input_capture::InputCapture::new(
+///     dp.TC1,
+///     pins.d8.into_floating_input(),
+///     Settings {
+///         prescaler: Prescaler::Prescale8,
+///         ..Default::default()
+///     },
+/// );
+///
+/// let measurement = capture.measure_blocking();
+/// let high_us = capture.ticks_to_micros(measurement.width);
+/// ```
+pub struct InputCapture<H, TC, ICP, CLOCK> {
+    p: TC,
+    icp: port::Pin<port::mode::Input, ICP>,
+    prescaler: Prescaler,
+    edge: Edge,
+    _clock: PhantomData<CLOCK>,
+    _h: PhantomData<H>,
+}
+
+impl<H, TC, ICP, CLOCK> InputCapture<H, TC, ICP, CLOCK>
+where
+    TC: InputCaptureOps<H, ICP>,
+    ICP: port::PinOps,
+    CLOCK: crate::clock::Clock,
+{
+    /// Initialize the input capture unit of a timer and start the timer.
+    pub fn new<IMODE: port::mode::InputMode>(
+        p: TC,
+        icp: port::Pin<port::mode::Input<IMODE>, ICP>,
+        settings: Settings,
+    ) -> Self {
+        let mut capture = Self {
+            p,
+            icp: icp.forget_imode(),
+            prescaler: settings.prescaler,
+            edge: settings.edge,
+            _clock: PhantomData,
+            _h: PhantomData,
+        };
+        capture.p.raw_stop();
+        capture.p.raw_set_mode(Mode::Normal);
+        capture.p.raw_set_noise_canceler(settings.noise_canceler);
+        capture.set_edge(settings.edge);
+        capture.p.raw_write_counter(0);
+        capture.p.raw_start(settings.prescaler);
+        capture
+    }
+
+    /// Select the edge which triggers the next capture.
+    ///
+    /// Any pending capture is discarded, as changing the edge can trigger a spurious capture.
+    pub fn set_edge(&mut self, edge: Edge) {
+        self.edge = edge;
+        self.p.raw_set_edge(edge);
+        self.p.raw_clear_capture_pending();
+    }
+
+    /// The edge which currently triggers a capture.
+    pub fn edge(&self) -> Edge {
+        self.edge
+    }
+
+    /// Read the current value of the free-running counter.
+    pub fn now(&self) -> u16 {
+        self.p.raw_read_counter()
+    }
+
+    /// Return the next capture if one occurred.
+    ///
+    /// This polls the input capture flag which is cleared automatically when the capture
+    /// interrupt handler is executed.  Use [`InputCapture::last_capture()`] from the interrupt
+    /// handler instead.
+    pub fn read(&mut self) -> nb::Result<Capture, core::convert::Infallible> {
+        if !self.p.raw_is_capture_pending() {
+            return Err(nb::Error::WouldBlock);
+        }
+        let capture = self.last_capture();
+        self.p.raw_clear_capture_pending();
+        Ok(capture)
+    }
+
+    /// Read the most recent capture, without checking whether it is new.
+    ///
+    /// This is intended for use in the `TIMERn_CAPT` interrupt handler.
+    pub fn last_capture(&self) -> Capture {
+        Capture {
+            ticks: self.p.raw_read_capture(),
+            edge: self.edge,
+        }
+    }
+
+    /// Enable the capture interrupt (`TIMERn_CAPT`).
+    pub fn listen(&mut self) {
+        self.p.raw_capture_interrupt(true);
+    }
+
+    /// Disable the capture interrupt (`TIMERn_CAPT`).
+    pub fn unlisten(&mut self) {
+        self.p.raw_capture_interrupt(false);
+    }
+
+    /// Measure the width and period of the signal on the input capture pin.
+    ///
+    /// Waits for the configured edge, the opposite edge and the configured edge again.  The width
+    /// of the [`Measurement`] is the time the signal stays at the level following the configured
+    /// edge (i.e. the high time when capturing on [`Edge::Rising`]).
+    ///
+    /// **Note**: This blocks forever if there is no signal.
+    pub fn measure_blocking(&mut self) -> Measurement {
+        let edge = self.edge;
+
+        self.set_edge(edge);
+        let start = nb::block!(self.read()).unwrap_infallible();
+        self.set_edge(edge.opposite());
+        let middle = nb::block!(self.read()).unwrap_infallible();
+        self.set_edge(edge);
+        let end = nb::block!(self.read()).unwrap_infallible();
+
+        Measurement {
+            width: middle.ticks_since(&start),
+            period: end.ticks_since(&start),
+        }
+    }
+
+    /// Measure the duration of a single pulse in ticks.
+    ///
+    /// Waits for the configured edge, then for the opposite edge.  This is useful for sensors
+    /// which report a value as the length of a pulse, like ultrasonic distance sensors.
+    ///
+    /// **Note**: This blocks forever if there is no pulse.
+    pub fn pulse_width_blocking(&mut self) -> u16 {
+        let edge = self.edge;
+
+        self.set_edge(edge);
+        let start = nb::block!(self.read()).unwrap_infallible();
+        self.set_edge(edge.opposite());
+        let end = nb::block!(self.read()).unwrap_infallible();
+        self.set_edge(edge);
+
+        end.ticks_since(&start)
+    }
+
+    /// Frequency of the timer ticks in Hz.
+    pub fn tick_hz(&self) -> u32 {
+        CLOCK::FREQ / self.prescaler.divider()
+    }
+
+    /// Convert a number of timer ticks into microseconds.
+    pub fn ticks_to_micros(&self, ticks: u16) -> u32 {
+        let divider = self.prescaler.divider();
+        if CLOCK::FREQ % 1_000_000 == 0 {
+            ticks as u32 * divider / (CLOCK::FREQ / 1_000_000)
+        } else {
+            (ticks as u64 * divider as u64 * 1_000_000 / CLOCK::FREQ as u64) as u32
+        }
+    }
+
+    /// Frequency in Hz of a signal with a period of `ticks` timer ticks.
+    ///
+    /// Returns `0` for a period of zero ticks.
+    pub fn ticks_to_hz(&self, ticks: u16) -> u32 {
+        if ticks == 0 {
+            return 0;
+        }
+        self.tick_hz() / ticks as u32
+    }
+
+    /// Stop the timer and release the peripheral and the input capture pin.
+    pub fn release(mut self) -> (TC, port::Pin<port::mode::Input, ICP>) {
+        self.p.raw_capture_interrupt(false);
+        self.p.raw_stop();
+        (self.p, self.icp)
+    }
+}
+
+/// Implement [`InputCaptureOps`] for the input capture unit of a 16-bit timer.
+///
+/// `pin` is the `ICPn` pin of the timer and `icr` its capture register.  The remaining fields
+/// name the registers and bits for the capture edge (`ICESn`) and noise canceler (`ICNCn`), the
+/// capture interrupt enable (`ICIEn`) and the capture flag (`ICFn`).
+#[macro_export]
+macro_rules! impl_input_capture {
+    (
+        hal: $HAL:ty,
+        peripheral: $TC:ty,
+        pin: $ICP:ty,
+        icr: $icr:ident,
+        tccrb: $tccrb:ident { edge: $ices:ident, noise_canceler: $icnc:ident },
+        timsk: $timsk:ident { capture: $icie:ident },
+        tifr: $tifr:ident { capture: $icf:ident },
+    ) => {
+        impl $crate::input_capture::InputCaptureOps<$HAL, $ICP> for $TC {
+            #[inline]
+            fn raw_set_edge(&mut self, edge: $crate::input_capture::Edge) {
+                self.$tccrb.modify(|_, w| match edge {
+                    $crate::input_capture::Edge::Falling => w.$ices().clear_bit(),
+                    $crate::input_capture::Edge::Rising => w.$ices().set_bit(),
+                });
+            }
+
+            #[inline]
+            fn raw_set_noise_canceler(&mut self, state: bool) {
+                self.$tccrb.modify(|_, w| w.$icnc().bit(state));
+            }
+
+            #[inline]
+            fn raw_read_capture(&self) -> u16 {
+                self.$icr.read().bits()
+            }
+
+            #[inline]
+            fn raw_capture_interrupt(&mut self, state: bool) {
+                // SAFETY: The interrupt mask register is shared between multiple timers on some
+                // MCUs.  The read-modify-write sequence is thus wrapped in a critical section.
+                $crate::avr_device::interrupt::free(|_| {
+                    self.$timsk.modify(|_, w| w.$icie().bit(state))
+                });
+            }
+
+            #[inline]
+            fn raw_is_capture_pending(&self) -> bool {
+                self.$tifr.read().$icf().bit_is_set()
+            }
+
+            #[inline]
+            fn raw_clear_capture_pending(&mut self) {
+                // Flags are cleared by writing a logical one.
+                self.$tifr.write(|w| w.$icf().set_bit());
+            }
+        }
+    };
+}
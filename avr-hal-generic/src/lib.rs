@@ -15,11 +15,19 @@ pub mod adc;
 pub mod clock;
 pub mod delay;
 pub mod eeprom;
+pub mod exint;
 pub mod i2c;
+pub mod input_capture;
+pub mod pcint;
 pub mod port;
+pub mod servo;
 pub mod simple_pwm;
 pub mod spi;
+pub mod timer;
+pub mod tone;
 pub mod usart;
+pub mod usart_spi;
+pub mod usi;
 pub mod wdt;
 
 /// Prelude containing all HAL traits
@@ -0,0 +1,311 @@
+//! Pin-Change Interrupt Implementation
+//!
+//! Check the documentation of [`PcintBank`] for details.
+
+use crate::port;
+use core::marker::PhantomData;
+
+/// Internal trait for the low-level registers of a pin-change interrupt bank.
+///
+/// Each `PCINTn` interrupt vector of an MCU is shared by a bank of up to eight pins.  This trait is
+/// implemented for a marker type per bank and used as an intermediate abstraction ontop of which
+/// the [`PcintBank`] API is built.  **Prefer using the [`PcintBank`] API instead of this trait.**
+pub trait PcintBankOps<H> {
+    /// Peripheral which contains the pin-change interrupt registers.
+    type Peripheral;
+
+    /// Enable/Disable the interrupt of this bank.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_interrupt(state: bool);
+
+    /// Enable/Disable the pins selected by `mask` as sources of this bank's interrupt.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_listen(mask: u8, state: bool);
+
+    /// Read the mask of pins which are currently enabled as interrupt sources.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_mask() -> u8;
+
+    /// Read the current input levels of all pins in this bank, one bit per pin.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_read_pins() -> u8;
+
+    /// Check whether the interrupt flag of this bank is set.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_is_pending() -> bool;
+
+    /// Clear the interrupt flag of this bank.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_clear_pending();
+}
+
+/// A pin which can trigger the pin-change interrupt of bank `BANK`.
+pub trait PcintPin<H, BANK> {
+    /// Bit of this pin in the bank's mask register.
+    const MASK: u8;
+}
+
+/// Pins of a [`PcintBank`] which changed their level.
+///
+/// Returned by [`PcintBank::changed()`].
+#[derive(Clone, Copy)]
+pub struct PcintChanges<H, BANK> {
+    changed: u8,
+    levels: u8,
+    _bank: PhantomData<(H, BANK)>,
+}
+
+impl<H, BANK> PcintChanges<H, BANK> {
+    /// Whether no pin changed.
+    pub fn is_empty(&self) -> bool {
+        self.changed == 0
+    }
+
+    /// Whether the given pin changed its level.
+    pub fn contains<MODE, PIN: PcintPin<H, BANK>>(&self, _pin: &port::Pin<MODE, PIN>) -> bool {
+        self.changed & PIN::MASK != 0
+    }
+
+    /// Whether the given pin was high when the changes were collected.
+    ///
+    /// Together with [`PcintChanges::contains()`], this tells the direction of the change.
+    pub fn is_high<MODE, PIN: PcintPin<H, BANK>>(&self, _pin: &port::Pin<MODE, PIN>) -> bool {
+        self.levels & PIN::MASK != 0
+    }
+
+    /// Mask of all pins which changed, using the bit positions of the bank's mask register.
+    pub fn bits(&self) -> u8 {
+        self.changed
+    }
+}
+
+/// Pin-change interrupt bank
+///
+/// Every pin-change interrupt vector (`PCINTn`) is triggered by any level change on the pins of
+/// its bank which are selected with [`PcintBank::listen()`].  Only pins which belong to the bank
+/// can be passed to it, using any other pin is a compile-time error.
+///
+/// As multiple pins share one interrupt, [`PcintBank::changed()`] can be used in the interrupt
+/// handler to find out which of them caused it.
+///
+/// # Example
+/// (for Arduino Uno)
+/// ```
+/// let dp = arduino_hal::Peripherals::take().unwrap();
+/// let pins = arduino_hal::pins!(dp);
+/// let mut exint = dp.EXINT;
+///
+/// let clk = pins.d2.into_pull_up_input();
+/// let dt = pins.d3.into_pull_up_input();
+///
+/// // D2 and D3 are both in bank PCINT2
+/// let mut bank = arduino_hal::pcint::PcintBank::new(&mut exint);
+/// bank.listen(&clk);
+/// bank.listen(&dt);
+///
+/// // Usually in the PCINT2 interrupt handler:
+/// let changes = bank.changed();
+/// if changes.contains(&clk) && !changes.is_high(&clk) {
+///     // falling edge on CLK
+/// }
+/// ```
+pub struct PcintBank<H, EXINT, BANK> {
+    levels: u8,
+    _h: PhantomData<H>,
+    _exint: PhantomData<EXINT>,
+    _bank: PhantomData<BANK>,
+}
+
+impl<H, EXINT, BANK> PcintBank<H, EXINT, BANK>
+where
+    BANK: PcintBankOps<H, Peripheral = EXINT>,
+{
+    /// Enable the interrupt of this bank.
+    ///
+    /// No pins are selected initially, use [`PcintBank::listen()`] to add them.
+    pub fn new(_exint: &mut EXINT) -> Self {
+        BANK::raw_listen(0xff, false);
+        BANK::raw_clear_pending();
+        BANK::raw_interrupt(true);
+
+        Self {
+            levels: BANK::raw_read_pins(),
+            _h: PhantomData,
+            _exint: PhantomData,
+            _bank: PhantomData,
+        }
+    }
+
+    /// Trigger the interrupt of this bank when the given pin changes its level.
+    pub fn listen<MODE, PIN: PcintPin<H, BANK>>(&mut self, _pin: &port::Pin<MODE, PIN>) {
+        // Start tracking the pin from its current level so the next call to `changed()` does not
+        // report a spurious change.
+        let levels = BANK::raw_read_pins();
+        self.levels = (self.levels & !PIN::MASK) | (levels & PIN::MASK);
+        BANK::raw_listen(PIN::MASK, true);
+    }
+
+    /// Stop triggering the interrupt of this bank when the given pin changes its level.
+    pub fn unlisten<MODE, PIN: PcintPin<H, BANK>>(&mut self, _pin: &port::Pin<MODE, PIN>) {
+        BANK::raw_listen(PIN::MASK, false);
+    }
+
+    /// Report which of the selected pins changed their level since the last call.
+    ///
+    /// Very short pulses can be missed if a pin changes back before this is called.
+    pub fn changed(&mut self) -> PcintChanges<H, BANK> {
+        let levels = BANK::raw_read_pins();
+        let changed = (levels ^ self.levels) & BANK::raw_mask();
+        self.levels = levels;
+
+        PcintChanges {
+            changed,
+            levels,
+            _bank: PhantomData,
+        }
+    }
+
+    /// Check whether the interrupt flag of this bank is set.
+    ///
+    /// The hardware clears the flag automatically when the interrupt handler is executed.  This
+    /// is mostly useful when interrupts are disabled globally.
+    pub fn is_pending(&self) -> bool {
+        BANK::raw_is_pending()
+    }
+
+    /// Clear the interrupt flag of this bank.
+    pub fn clear_pending(&mut self) {
+        BANK::raw_clear_pending();
+    }
+
+    /// Disable the interrupt of this bank and deselect all pins.
+    pub fn release(self) {
+        BANK::raw_interrupt(false);
+        BANK::raw_listen(0xff, false);
+        BANK::raw_clear_pending();
+    }
+}
+
+/// Implement the pin-change interrupt banks of an MCU.
+///
+/// For each bank, the bit in the control and flag registers, the mask register and the mapping of
+/// pins to bits in the mask register are given.  This creates a marker type for each bank.
+///
+/// The mask register is looked up in the `EXINT` peripheral unless another peripheral is given
+/// with `mask: pcmsk in crate::pac::CPU`.  The pin types are taken from `crate::port`, so the
+/// table is usually placed right after the `impl_port_traditional!` invocation of the MCU.
+#[macro_export]
+macro_rules! impl_pcint {
+    (@mask $EXINT:ty) => {
+        // SAFETY: The mask register is only modified inside critical sections.
+        unsafe { &*<$EXINT>::ptr() }
+    };
+    (@mask $EXINT:ty, $MASK:ty) => {
+        $crate::impl_pcint!(@mask $MASK)
+    };
+    (
+        hal: $HAL:ty,
+        peripheral: $EXINT:ty,
+        banks: {
+            $(
+                $(#[$bank_attr:meta])*
+                $Bank:ident: {
+                    control: $pcicr:ident[$pcie:literal],
+                    flag: $pcifr:ident[$pcif:literal],
+                    mask: $pcmsk:ident $(in $MASK:ty)?,
+                    pins: {
+                        $($port:ident: [$($pin:literal => $bit:literal),+],)+
+                    },
+                },
+            )+
+        },
+    ) => {
+        $crate::paste::paste! {
+            $(
+                $(#[$bank_attr])*
+                pub enum $Bank {}
+
+                impl $crate::pcint::PcintBankOps<$HAL> for $Bank {
+                    type Peripheral = $EXINT;
+
+                    #[inline]
+                    fn raw_interrupt(state: bool) {
+                        // SAFETY: The control register is shared by all banks, so the
+                        // read-modify-write sequence only changes this bank's bit and is wrapped
+                        // in a critical section.
+                        $crate::avr_device::interrupt::free(|_| unsafe {
+                            (*<$EXINT>::ptr()).$pcicr.modify(|r, w| {
+                                if state {
+                                    w.bits(r.bits() | 1 << $pcie)
+                                } else {
+                                    w.bits(r.bits() & !(1 << $pcie))
+                                }
+                            })
+                        });
+                    }
+
+                    #[inline]
+                    fn raw_listen(mask: u8, state: bool) {
+                        // SAFETY: The mask register belongs to this bank only.
+                        $crate::avr_device::interrupt::free(|_| {
+                            let pcmsk = &$crate::impl_pcint!(@mask $EXINT $(, $MASK)?).$pcmsk;
+                            pcmsk.modify(|r, w| unsafe {
+                                if state {
+                                    w.bits(r.bits() | mask)
+                                } else {
+                                    w.bits(r.bits() & !mask)
+                                }
+                            })
+                        });
+                    }
+
+                    #[inline]
+                    fn raw_mask() -> u8 {
+                        $crate::impl_pcint!(@mask $EXINT $(, $MASK)?).$pcmsk.read().bits()
+                    }
+
+                    #[inline]
+                    fn raw_read_pins() -> u8 {
+                        let mut levels = 0;
+                        $($(
+                            // SAFETY: Reading the input register has no side effects.
+                            if unsafe {
+                                (*<crate::pac::[<PORT $port>]>::ptr())
+                                    .[<pin $port:lower>]
+                                    .read()
+                                    .bits()
+                            } & (1 << $pin) != 0 {
+                                levels |= 1 << $bit;
+                            }
+                        )+)+
+                        levels
+                    }
+
+                    #[inline]
+                    fn raw_is_pending() -> bool {
+                        unsafe { (*<$EXINT>::ptr()).$pcifr.read().bits() & (1 << $pcif) != 0 }
+                    }
+
+                    #[inline]
+                    fn raw_clear_pending() {
+                        // SAFETY: Flags are cleared by writing a logical one, so writing zeros to
+                        // all other bits leaves them untouched.
+                        unsafe { (*<$EXINT>::ptr()).$pcifr.write(|w| w.bits(1 << $pcif)) };
+                    }
+                }
+
+                $($(
+                    impl $crate::pcint::PcintPin<$HAL, $Bank> for crate::port::[<P $port $pin>] {
+                        const MASK: u8 = 1 << $bit;
+                    }
+                )+)+
+            )+
+        }
+    };
+}
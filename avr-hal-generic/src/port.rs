@@ -498,6 +498,35 @@ impl<PIN: PinOps, IMODE: mode::InputMode> Pin<mode::Input<IMODE>, PIN> {
     }
 }
 
+/// # External Interrupts
+///
+/// Pins which are connected to one of the `INTn` external interrupts of the MCU can be configured
+/// as an interrupt source.  For those pins, `enable_external_interrupt()` is available:
+///
+/// ```
+/// let dp = atmega_hal::Peripherals::take().unwrap();
+/// let pins = atmega_hal::pins!(dp);
+/// let mut exint = dp.EXINT;
+///
+/// let button = pins.pd2.into_pull_up_input();
+/// let int0 = button.enable_external_interrupt(&mut exint, Trigger::FallingEdge);
+/// ```
+///
+/// See [`ExternalInterrupt`][crate::exint::ExternalInterrupt] for details.
+impl<PIN: PinOps, IMODE: mode::InputMode> Pin<mode::Input<IMODE>, PIN> {
+    /// Enable the external interrupt connected to this pin, triggering on the given condition.
+    pub fn enable_external_interrupt<H, EXINT>(
+        self,
+        exint: &mut EXINT,
+        trigger: EXINT::Trigger,
+    ) -> crate::exint::ExternalInterrupt<H, EXINT, PIN, IMODE>
+    where
+        EXINT: crate::exint::ExintOps<H, PIN>,
+    {
+        crate::exint::ExternalInterrupt::new(self, exint, trigger)
+    }
+}
+
 /// # Analog Input
 ///
 /// Some pins can be configured as ADC channels.  For those pins, `analog_read()` can be used to
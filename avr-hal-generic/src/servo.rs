@@ -0,0 +1,211 @@
+//! Hobby Servo Implementation
+//!
+//! Check the documentation of [`ServoTimer`] for details.
+
+use core::marker::PhantomData;
+
+use crate::clock::Clock;
+use crate::port::{mode, Pin};
+use crate::simple_pwm::{IntoPwmPin, Pwm16Mode, Pwm16Timer, PwmPinOps};
+use crate::timer::Prescaler;
+
+/// Pulse width limits of a servo.
+///
+/// The datasheets of most servos specify a range of 1ms to 2ms, but many models can move further
+/// than that.  The exact limits need to be found by experimentation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServoSettings {
+    /// Pulse width in microseconds for an angle of 0°.
+    pub min_pulse_us: u16,
+    /// Pulse width in microseconds for an angle of 180°.
+    pub max_pulse_us: u16,
+}
+
+impl Default for ServoSettings {
+    fn default() -> Self {
+        Self {
+            min_pulse_us: 1000,
+            max_pulse_us: 2000,
+        }
+    }
+}
+
+/// A 16-bit timer which generates the 50 Hz frame for up to three servos.
+///
+/// The timer runs in fast PWM mode with `ICRn` as TOP.  The prescaler and TOP are derived from the
+/// core clock `CLOCK` to get the best possible pulse width resolution (0.5µs at 16 MHz).  Each
+/// `OCnx` pin of the timer can drive one servo.
+///
+/// # Example
+/// (for Arduino Uno)
+/// ```
+/// let dp = arduino_hal::Peripherals::take().unwrap();
+/// let pins = arduino_hal::pins!(dp);
+///
+/// let mut timer = ServoTimer::<Timer1Pwm16>::new(dp.TC1);
+///
+/// let mut servo = timer.attach(pins.d9.into_output(), ServoSettings::default());
+/// servo.set_angle(90);
+/// ```
+pub struct ServoTimer<T, CLOCK> {
+    timer: T,
+    _clock: PhantomData<CLOCK>,
+}
+
+impl<T, CLOCK> ServoTimer<T, CLOCK>
+where
+    T: Pwm16Timer,
+    CLOCK: Clock,
+{
+    /// Frequency of the servo frame in Hz.
+    pub const FRAME_HZ: u32 = 50;
+
+    /// Length of the servo frame in microseconds.
+    pub const FRAME_US: u16 = (1_000_000 / Self::FRAME_HZ) as u16;
+
+    /// Prescaler and TOP value of the servo frame for `CLOCK`.
+    ///
+    /// Evaluated at compile time, so using a `ServoTimer` with a clock which cannot generate the
+    /// frame is a compile-time error.
+    const PARAMETERS: (Prescaler, u16) =
+        match Pwm16Mode::Fast.parameters(CLOCK::FREQ, Self::FRAME_HZ) {
+            Some(parameters) => parameters,
+            None => panic!("servo frame cannot be generated at this clock speed"),
+        };
+
+    /// Start the timer with a 50 Hz frame.
+    pub fn new(timer: T::Timer) -> Self {
+        let (prescaler, top) = Self::PARAMETERS;
+
+        Self {
+            timer: T::new(timer, Pwm16Mode::Fast, prescaler, top),
+            _clock: PhantomData,
+        }
+    }
+
+    /// Drive a servo from the given pin.
+    ///
+    /// The servo is moved to the middle of its range and the pin's output is enabled.  Only the
+    /// `OCnx` pins of this timer can be used, passing any other pin is a compile-time error.
+    ///
+    /// # Panics
+    /// Panics if `settings.min_pulse_us` is larger than `settings.max_pulse_us`.
+    pub fn attach<PIN>(
+        &mut self,
+        pin: Pin<mode::Output, PIN>,
+        settings: ServoSettings,
+    ) -> Servo<T, PIN>
+    where
+        PIN: PwmPinOps<T, Duty = u16>,
+    {
+        let mut servo = Servo {
+            pin: pin.into_pwm(&self.timer),
+            ticks_per_milli: CLOCK::FREQ / self.timer.prescaler().divider() / 1000,
+            settings: ServoSettings::default(),
+            pulse_us: 0,
+        };
+
+        servo.set_settings(settings);
+        servo.set_pulse_width(settings.min_pulse_us / 2 + settings.max_pulse_us / 2);
+        servo.enable();
+        servo
+    }
+
+    /// Access the underlying PWM timer.
+    pub fn timer(&self) -> &T {
+        &self.timer
+    }
+
+    /// Return the underlying PWM timer.
+    pub fn release(self) -> T {
+        self.timer
+    }
+}
+
+/// A servo driven by one `OCnx` pin of a [`ServoTimer`].
+pub struct Servo<T, PIN> {
+    pin: Pin<mode::PwmOutput<T>, PIN>,
+    ticks_per_milli: u32,
+    settings: ServoSettings,
+    pulse_us: u16,
+}
+
+impl<T, PIN> Servo<T, PIN>
+where
+    PIN: PwmPinOps<T, Duty = u16>,
+{
+    /// Largest angle in degrees, corresponding to [`ServoSettings::max_pulse_us`].
+    pub const MAX_ANGLE: u8 = 180;
+
+    /// Set the pulse width in microseconds.
+    ///
+    /// The value is clamped to the limits from the [`ServoSettings`].
+    pub fn set_pulse_width(&mut self, pulse_us: u16) {
+        let pulse_us = pulse_us.clamp(self.settings.min_pulse_us, self.settings.max_pulse_us);
+        let ticks = pulse_us as u32 * self.ticks_per_milli / 1000;
+
+        self.pin.set_duty(ticks as u16);
+        self.pulse_us = pulse_us;
+    }
+
+    /// The current pulse width in microseconds.
+    pub fn pulse_width(&self) -> u16 {
+        self.pulse_us
+    }
+
+    /// Move the servo to the given angle in degrees.
+    ///
+    /// The angle is mapped linearly between the pulse width limits from the [`ServoSettings`] and
+    /// clamped to [`Servo::MAX_ANGLE`].
+    pub fn set_angle(&mut self, degrees: u8) {
+        let degrees = degrees.min(Self::MAX_ANGLE) as u32;
+        let range = (self.settings.max_pulse_us - self.settings.min_pulse_us) as u32;
+        let pulse_us = self.settings.min_pulse_us as u32 + range * degrees / Self::MAX_ANGLE as u32;
+
+        self.set_pulse_width(pulse_us as u16);
+    }
+
+    /// The current angle in degrees, derived from the pulse width.
+    pub fn angle(&self) -> u8 {
+        let range = (self.settings.max_pulse_us - self.settings.min_pulse_us) as u32;
+        if range == 0 {
+            return 0;
+        }
+        let offset = (self.pulse_us - self.settings.min_pulse_us) as u32;
+
+        ((offset * Self::MAX_ANGLE as u32 + range / 2) / range) as u8
+    }
+
+    /// The pulse width limits of this servo.
+    pub fn settings(&self) -> ServoSettings {
+        self.settings
+    }
+
+    /// Change the pulse width limits of this servo.
+    ///
+    /// The current pulse width is clamped to the new limits.
+    ///
+    /// # Panics
+    /// Panics if `min_pulse_us` is larger than `max_pulse_us`.
+    pub fn set_settings(&mut self, settings: ServoSettings) {
+        assert!(settings.min_pulse_us <= settings.max_pulse_us);
+        self.settings = settings;
+        self.set_pulse_width(self.pulse_us);
+    }
+
+    /// Start sending pulses to the servo.
+    pub fn enable(&mut self) {
+        self.pin.enable();
+    }
+
+    /// Stop sending pulses, which lets most servos move freely.
+    pub fn disable(&mut self) {
+        self.pin.disable();
+    }
+
+    /// Stop sending pulses and return the PWM pin.
+    pub fn release(mut self) -> Pin<mode::PwmOutput<T>, PIN> {
+        self.disable();
+        self.pin
+    }
+}
@@ -7,35 +7,7 @@ use embedded_hal::pwm::{ErrorKind, ErrorType, SetDutyCycle};
 use crate::port::mode;
 use crate::port::Pin;
 
-/// Clock prescaler for PWM
-///
-/// The prescaler dictates the PWM frequency, together with the IO clock.  The formula is as
-/// follows:
-///
-/// ```text
-/// F_pwm = CLK_io / (Prescaler * 256);
-/// ```
-///
-/// | Prescaler | 16 MHz Clock | 8 MHz Clock |
-/// | --- | --- | ---|
-/// | `Direct` | 62.5 kHz | 31.3 kHz |
-/// | `Prescale8` | 7.81 kHz | 3.91 kHz |
-/// | `Prescale64` | 977 Hz | 488 Hz |
-/// | `Prescale256` | 244 Hz | 122 Hz |
-/// | `Prescale1024` | 61.0 Hz | 30.5 Hz |
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
-pub enum Prescaler {
-    /// No prescaling, the IO clock drives the timer directly.
-    Direct,
-    /// Divide the IO clock by 8.
-    Prescale8,
-    /// Divide the IO clock by 64.
-    Prescale64,
-    /// Divide the IO clock by 256.
-    Prescale256,
-    /// Divide the IO clock by 1024.
-    Prescale1024,
-}
+pub use crate::timer::Prescaler;
 
 /// Implement traits and types for PWM timers
 pub trait PwmPinOps<TC> {
@@ -46,7 +18,7 @@ pub trait PwmPinOps<TC> {
     fn get_duty(&self) -> Self::Duty;
     fn get_max_duty(&self) -> Self::Duty;
 
-    fn set_duty(&mut self, value: u8);
+    fn set_duty(&mut self, value: Self::Duty);
 }
 
 pub trait IntoPwmPin<TC, PIN> {
@@ -79,16 +51,19 @@ impl<TC, PIN: PwmPinOps<TC>> Pin<mode::PwmOutput<TC>, PIN> {
         self.pin.get_max_duty()
     }
 
-    pub fn set_duty(&mut self, duty: u8) {
+    pub fn set_duty(&mut self, duty: <PIN as PwmPinOps<TC>>::Duty) {
         self.pin.set_duty(duty);
     }
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum PwmError {
-    /// `embedded-hal` supports duty cycles up to `u16`, however `avr` devices only support up to `u8`.
-    /// Passing a duty cycle larger than [`u8::MAX`] will result in this error.
+    /// `embedded-hal` supports duty cycles up to `u16`, however the PWM timers only support duty
+    /// cycles up to their TOP value ([`u8::MAX`] for 8-bit PWM).  Passing a duty cycle larger than
+    /// [`SetDutyCycle::max_duty_cycle()`] will result in this error.
     DutyCycleTooLarge,
+    /// The requested PWM frequency cannot be generated from the CPU clock with any prescaler.
+    FrequencyOutOfRange,
 }
 
 impl pwm::Error for PwmError {
@@ -101,20 +76,127 @@ impl<TC, PIN: PwmPinOps<TC>> ErrorType for Pin<mode::PwmOutput<TC>, PIN> {
     type Error = PwmError;
 }
 
-impl<TC, PIN: PwmPinOps<TC, Duty = u8>> SetDutyCycle for Pin<mode::PwmOutput<TC>, PIN> {
+impl<TC, PIN> SetDutyCycle for Pin<mode::PwmOutput<TC>, PIN>
+where
+    PIN: PwmPinOps<TC>,
+    <PIN as PwmPinOps<TC>>::Duty: Into<u16> + TryFrom<u16>,
+{
     fn max_duty_cycle(&self) -> u16 {
-        self.get_max_duty() as u16
+        self.get_max_duty().into()
     }
 
     fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Self::Error> {
-        if duty > u8::MAX as u16 {
+        if duty > self.max_duty_cycle() {
             return Err(PwmError::DutyCycleTooLarge);
         }
-        self.set_duty(duty as u8);
+        let duty = duty.try_into().map_err(|_| PwmError::DutyCycleTooLarge)?;
+        self.set_duty(duty);
         Ok(())
     }
 }
 
+/// Waveform generation modes of the 16-bit timers which use `ICRn` as TOP.
+///
+/// In all modes, the timer counts from zero up to TOP, so TOP sets both the PWM frequency and the
+/// resolution of the duty cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pwm16Mode {
+    /// Fast PWM: The counter restarts at zero after reaching TOP.
+    ///
+    /// `f_pwm = f_clk / (N * (TOP + 1))`
+    Fast,
+    /// Phase correct PWM: The counter counts down again after reaching TOP.
+    ///
+    /// This produces symmetric pulses at half the frequency of fast PWM.  The duty cycle is updated
+    /// at TOP.
+    ///
+    /// `f_pwm = f_clk / (2 * N * TOP)`
+    PhaseCorrect,
+    /// Phase and frequency correct PWM: Like phase correct PWM, but the duty cycle is updated at
+    /// BOTTOM.
+    ///
+    /// This keeps the pulses symmetric even while TOP is changed, so prefer this mode if the
+    /// frequency is adjusted at runtime.
+    ///
+    /// `f_pwm = f_clk / (2 * N * TOP)`
+    PhaseFrequencyCorrect,
+}
+
+impl Pwm16Mode {
+    /// The smallest TOP value supported by the hardware.
+    pub const MIN_TOP: u16 = 3;
+
+    /// Value of the four `WGMn` bits for this mode.
+    pub const fn wgm(self) -> u8 {
+        match self {
+            Pwm16Mode::Fast => 0b1110,
+            Pwm16Mode::PhaseCorrect => 0b1010,
+            Pwm16Mode::PhaseFrequencyCorrect => 0b1000,
+        }
+    }
+
+    /// The PWM frequency in Hz for a timer clocked at `clock_hz` with the given settings.
+    pub const fn frequency(self, clock_hz: u32, prescaler: Prescaler, top: u16) -> u32 {
+        let tick_hz = clock_hz / prescaler.divider();
+        match self {
+            Pwm16Mode::Fast => tick_hz / (top as u32 + 1),
+            Pwm16Mode::PhaseCorrect | Pwm16Mode::PhaseFrequencyCorrect => {
+                // Guard against a division by zero if TOP was set below the minimum.
+                tick_hz / if top == 0 { 1 } else { 2 * top as u32 }
+            }
+        }
+    }
+
+    /// Find the prescaler and TOP value which generate `frequency` (in Hz) most accurately.
+    ///
+    /// The smallest possible prescaler is chosen to get the best duty cycle resolution.  Returns
+    /// `None` if the frequency cannot be generated from a clock of `clock_hz`.
+    pub const fn parameters(self, clock_hz: u32, frequency: u32) -> Option<(Prescaler, u16)> {
+        if frequency == 0 {
+            return None;
+        }
+
+        let divisor = match self {
+            Pwm16Mode::Fast => frequency,
+            Pwm16Mode::PhaseCorrect | Pwm16Mode::PhaseFrequencyCorrect => {
+                frequency.saturating_mul(2)
+            }
+        };
+
+        let prescalers = [
+            Prescaler::Direct,
+            Prescaler::Prescale8,
+            Prescaler::Prescale64,
+            Prescaler::Prescale256,
+            Prescaler::Prescale1024,
+        ];
+
+        // `for` loops are not allowed in a `const fn`.
+        let mut i = 0;
+        while i < prescalers.len() {
+            let prescaler = prescalers[i];
+            i += 1;
+
+            let tick_hz = clock_hz / prescaler.divider();
+            let ticks = (tick_hz + divisor / 2) / divisor;
+            let top = match self {
+                Pwm16Mode::Fast => ticks.saturating_sub(1),
+                Pwm16Mode::PhaseCorrect | Pwm16Mode::PhaseFrequencyCorrect => ticks,
+            };
+
+            if top < Self::MIN_TOP as u32 {
+                // Larger prescalers only make TOP smaller.
+                return None;
+            }
+            if top <= u16::MAX as u32 {
+                return Some((prescaler, top as u16));
+            }
+        }
+
+        None
+    }
+}
+
 #[macro_export]
 macro_rules! impl_simple_pwm {
     (
@@ -191,3 +273,209 @@ macro_rules! impl_simple_pwm {
         )+
     }
 }
+
+/// Common interface of the 16-bit PWM timers created with [`impl_simple_pwm_16!`].
+///
+/// This allows drivers like [`ServoTimer`][crate::servo::ServoTimer] to be generic over the timer
+/// they use.
+pub trait Pwm16Timer: Sized {
+    /// The timer peripheral.
+    type Timer;
+
+    /// Start the timer in the given mode, counting up to `top`.
+    fn new(timer: Self::Timer, mode: Pwm16Mode, prescaler: Prescaler, top: u16) -> Self;
+
+    /// The current TOP value, which is also the maximum duty cycle of all pins.
+    fn top(&self) -> u16;
+
+    /// The waveform generation mode of the timer.
+    fn mode(&self) -> Pwm16Mode;
+
+    /// The prescaler of the timer.
+    fn prescaler(&self) -> Prescaler;
+}
+
+/// Implement 16-bit PWM with a configurable TOP for a 16-bit timer.
+///
+/// The `init` block must configure the waveform generation mode from the four `WGMn` bits in `wgm`
+/// and the clock source from `prescaler`.  It is called again whenever the frequency changes.
+#[macro_export]
+macro_rules! impl_simple_pwm_16 {
+    (
+        $(#[$timer_pwm_attr:meta])*
+        pub struct $TimerPwm:ident {
+            timer: $TIMER:ty,
+            icr: $icr:ident,
+            init: |$init_timer:ident, $wgm:ident, $prescaler:ident| $init_block:block,
+            pins: {$(
+                $PXi:ident: {
+                    ocr: $ocr:ident,
+                    $into_pwm:ident: |$pin_timer:ident| if enable
+                        $pin_enable_block:block else $pin_disable_block:block,
+                },
+            )+},
+        }
+    ) => {
+        $(#[$timer_pwm_attr])*
+        pub struct $TimerPwm {
+            timer: $TIMER,
+            mode: $crate::simple_pwm::Pwm16Mode,
+            prescaler: $crate::simple_pwm::Prescaler,
+        }
+
+        impl $TimerPwm {
+            /// Start the timer in the given mode, counting up to `top`.
+            ///
+            /// `top` must be at least `Pwm16Mode::MIN_TOP`.
+            pub fn new(
+                timer: $TIMER,
+                mode: $crate::simple_pwm::Pwm16Mode,
+                prescaler: $crate::simple_pwm::Prescaler,
+                top: u16,
+            ) -> $TimerPwm {
+                let mut t = $TimerPwm {
+                    timer,
+                    mode,
+                    prescaler,
+                };
+                t.configure(top);
+                t
+            }
+
+            /// Start the timer in the given mode, running at the given PWM frequency (in Hz).
+            ///
+            /// The prescaler and TOP are chosen to give the best duty cycle resolution.  Returns
+            /// [`PwmError::FrequencyOutOfRange`][$crate::simple_pwm::PwmError::FrequencyOutOfRange]
+            /// if the frequency cannot be generated.
+            pub fn with_frequency<CLOCK: $crate::clock::Clock>(
+                timer: $TIMER,
+                mode: $crate::simple_pwm::Pwm16Mode,
+                frequency: u32,
+            ) -> Result<$TimerPwm, $crate::simple_pwm::PwmError> {
+                let (prescaler, top) = mode
+                    .parameters(CLOCK::FREQ, frequency)
+                    .ok_or($crate::simple_pwm::PwmError::FrequencyOutOfRange)?;
+                Ok(Self::new(timer, mode, prescaler, top))
+            }
+
+            /// Change the PWM frequency (in Hz), keeping the current mode.
+            ///
+            /// The duty cycles of all pins are **not** rescaled, so they need to be set again
+            /// relative to the new [`top()`][Self::top].
+            pub fn set_frequency<CLOCK: $crate::clock::Clock>(
+                &mut self,
+                frequency: u32,
+            ) -> Result<(), $crate::simple_pwm::PwmError> {
+                let (prescaler, top) = self
+                    .mode
+                    .parameters(CLOCK::FREQ, frequency)
+                    .ok_or($crate::simple_pwm::PwmError::FrequencyOutOfRange)?;
+                self.prescaler = prescaler;
+                self.configure(top);
+                Ok(())
+            }
+
+            /// The current PWM frequency in Hz.
+            pub fn frequency<CLOCK: $crate::clock::Clock>(&self) -> u32 {
+                self.mode.frequency(CLOCK::FREQ, self.prescaler, self.top())
+            }
+
+            /// Change the TOP value, which is also the maximum duty cycle of all pins.
+            ///
+            /// In fast PWM mode, setting TOP below the current counter value makes the counter
+            /// run up to `0xffff` once before wrapping around.
+            pub fn set_top(&mut self, top: u16) {
+                self.timer.$icr.write(|w| w.bits(top));
+            }
+
+            /// The current TOP value, which is also the maximum duty cycle of all pins.
+            pub fn top(&self) -> u16 {
+                self.timer.$icr.read().bits()
+            }
+
+            /// The waveform generation mode of the timer.
+            pub fn mode(&self) -> $crate::simple_pwm::Pwm16Mode {
+                self.mode
+            }
+
+            /// The prescaler of the timer.
+            pub fn prescaler(&self) -> $crate::simple_pwm::Prescaler {
+                self.prescaler
+            }
+
+            fn configure(&mut self, top: u16) {
+                self.set_top(top);
+
+                let $wgm = self.mode.wgm();
+                let $prescaler = self.prescaler;
+                let $init_timer = &mut self.timer;
+                $init_block
+            }
+        }
+
+        impl $crate::simple_pwm::Pwm16Timer for $TimerPwm {
+            type Timer = $TIMER;
+
+            fn new(
+                timer: $TIMER,
+                mode: $crate::simple_pwm::Pwm16Mode,
+                prescaler: $crate::simple_pwm::Prescaler,
+                top: u16,
+            ) -> Self {
+                $TimerPwm::new(timer, mode, prescaler, top)
+            }
+
+            fn top(&self) -> u16 {
+                $TimerPwm::top(self)
+            }
+
+            fn mode(&self) -> $crate::simple_pwm::Pwm16Mode {
+                $TimerPwm::mode(self)
+            }
+
+            fn prescaler(&self) -> $crate::simple_pwm::Prescaler {
+                $TimerPwm::prescaler(self)
+            }
+        }
+
+        $(
+            impl avr_hal_generic::simple_pwm::PwmPinOps<$TimerPwm> for $PXi {
+                type Duty = u16;
+
+                fn enable(&mut self) {
+                    // SAFETY: This block will usually result in a read-modify-write sequence which
+                    // is not concurrency safe.  Thus, it is wrapped in a critical section which
+                    // ensures we will never hit a race-condition here.
+                    $crate::avr_device::interrupt::free(|_| {
+                        let $pin_timer = unsafe { &*<$TIMER>::ptr() };
+                        $pin_enable_block
+                    });
+                }
+
+                fn disable(&mut self) {
+                    // SAFETY: This block will usually result in a read-modify-write sequence which
+                    // is not concurrency safe.  Thus, it is wrapped in a critical section which
+                    // ensures we will never hit a race-condition here.
+                    $crate::avr_device::interrupt::free(|_| {
+                        let $pin_timer = unsafe { &*<$TIMER>::ptr() };
+                        $pin_disable_block
+                    });
+                }
+
+                fn get_duty(&self) -> Self::Duty {
+                    unsafe { (&*<$TIMER>::ptr()) }.$ocr.read().bits()
+                }
+
+                fn get_max_duty(&self) -> Self::Duty {
+                    unsafe { (&*<$TIMER>::ptr()) }.$icr.read().bits()
+                }
+
+                fn set_duty(&mut self, duty: Self::Duty) {
+                    // SAFETY: This register is exclusively used here so there are no concurrency
+                    // issues.
+                    unsafe { (&*<$TIMER>::ptr()).$ocr.write(|w| w.bits(duty)); };
+                }
+            }
+        )+
+    }
+}
@@ -1,7 +1,10 @@
 //! SPI Implementation
 use crate::port;
+use core::cell::RefCell;
 use core::marker::PhantomData;
+use embedded_hal::digital::OutputPin;
 use embedded_hal::spi::{self, SpiBus};
+use unwrap_infallible::UnwrapInfallible;
 
 /// Oscillator Clock Frequency division options.
 ///
@@ -93,6 +96,25 @@ pub trait SpiOps<H, SCLK, MOSI, MISO, CS> {
     fn raw_transaction(&mut self, byte: u8) -> u8;
 }
 
+/// Internal trait for low-level SPI peripherals which can operate in peripheral (slave) mode
+///
+/// The data register methods of [`SpiOps`] are shared with controller mode.  It is used as an
+/// intermediate abstraction ontop of which the [`SpiPeripheral`] API is built.  **Prefer using the
+/// [`SpiPeripheral`] API instead of this trait.**
+pub trait SpiPeripheralOps<H, SCLK, MOSI, MISO, CS>: SpiOps<H, SCLK, MOSI, MISO, CS> {
+    /// Enable the peripheral in slave mode, with the data order and mode from `settings`.
+    ///
+    /// The clock rate is ignored as the controller drives SCLK.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_setup_peripheral(&mut self, settings: &Settings);
+
+    /// Enable/Disable the transfer complete interrupt (`SPI_STC`).
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_interrupt(&mut self, state: bool);
+}
+
 /// Wrapper for the CS pin
 ///
 /// Used to contain the chip-select pin during operation to prevent its mode from being
@@ -195,6 +217,7 @@ pub struct Spi<H, SPI, SCLKPIN, MOSIPIN, MISOPIN, CSPIN> {
     sclk: port::Pin<port::mode::Output, SCLKPIN>,
     mosi: port::Pin<port::mode::Output, MOSIPIN>,
     miso: port::Pin<port::mode::Input, MISOPIN>,
+    settings: Settings,
     write_in_progress: bool,
     _cs: PhantomData<CSPIN>,
     _h: PhantomData<H>,
@@ -228,11 +251,12 @@ where
             sclk,
             mosi,
             miso: miso.forget_imode(),
+            settings,
             write_in_progress: false,
             _cs: PhantomData,
             _h: PhantomData,
         };
-        spi.p.raw_setup(&settings);
+        spi.p.raw_setup(&spi.settings);
         (spi, ChipSelectPin(cs))
     }
 
@@ -255,11 +279,12 @@ where
             sclk,
             mosi,
             miso: miso.forget_imode(),
+            settings,
             write_in_progress: false,
             _cs: PhantomData,
             _h: PhantomData,
         };
-        spi.p.raw_setup(&settings);
+        spi.p.raw_setup(&spi.settings);
         (spi, ChipSelectPin(cs))
     }
 
@@ -268,9 +293,15 @@ where
         // wait for any in-flight writes to complete
         self.flush()?;
         self.p.raw_setup(&settings);
+        self.settings = settings;
         Ok(())
     }
 
+    /// The settings the SPI peripheral is currently configured with
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
     /// Disable the SPI device and release ownership of the peripheral
     /// and pins.  Instance can no-longer be used after this is
     /// invoked.
@@ -444,6 +475,616 @@ where
 {
 }
 
+/// Bit-banged SPI controller on arbitrary pins
+///
+/// This drives the bus in software, for MCUs without an SPI peripheral or when its pins are needed
+/// elsewhere.  All four [`spi::Mode`]s and both [`DataOrder`]s are supported.  Like [`Spi`], it
+/// implements [`SpiBus`], so it can be used in place of the hardware peripheral.  There is no
+/// chip-select handling, use any output pin for that.
+///
+/// By default, the bus is clocked as fast as the pins can be toggled, which is in the order of
+/// 1 MHz at a 16 MHz CPU clock.  [`SoftSpi::set_max_frequency()`] slows it down using
+/// [`Delay`][crate::delay::Delay].  As the delay has a resolution of one microsecond, the
+/// resulting clock is at most 500 kHz then.
+///
+/// # Example
+/// (for Arduino Uno)
+/// ```
+/// let dp = arduino_hal::Peripherals::take().unwrap();
+/// let pins = arduino_hal::pins!(dp);
+///
+/// let mut spi = arduino_hal::spi::SoftSpi::new(
+///     pins.d2.into_output(),
+///     pins.d3.into_output(),
+///     pins.d4.into_pull_up_input(),
+///     embedded_hal::spi::MODE_0,
+///     spi::DataOrder::MostSignificantFirst,
+/// );
+/// let mut cs = pins.d5.into_output_high();
+///
+/// cs.set_low();
+/// spi.write(&[0x9f]).unwrap();
+/// cs.set_high();
+/// ```
+pub struct SoftSpi<SCKPIN, MOSIPIN, MISOPIN, CLOCK> {
+    sck: port::Pin<port::mode::Output, SCKPIN>,
+    mosi: port::Pin<port::mode::Output, MOSIPIN>,
+    miso: port::Pin<port::mode::Input, MISOPIN>,
+    mode: spi::Mode,
+    data_order: DataOrder,
+    half_period_ns: Option<u32>,
+    _clock: PhantomData<CLOCK>,
+}
+
+impl<SCKPIN, MOSIPIN, MISOPIN, CLOCK> SoftSpi<SCKPIN, MOSIPIN, MISOPIN, CLOCK>
+where
+    SCKPIN: port::PinOps,
+    MOSIPIN: port::PinOps,
+    MISOPIN: port::PinOps,
+    crate::delay::Delay<CLOCK>: embedded_hal::delay::DelayNs,
+{
+    /// Initialize a software SPI bus on the given pins.
+    ///
+    /// SCK is driven to the idle level of `mode` right away.
+    pub fn new<MISOMODE: port::mode::InputMode>(
+        sck: port::Pin<port::mode::Output, SCKPIN>,
+        mosi: port::Pin<port::mode::Output, MOSIPIN>,
+        miso: port::Pin<port::mode::Input<MISOMODE>, MISOPIN>,
+        mode: spi::Mode,
+        data_order: DataOrder,
+    ) -> Self {
+        let mut spi = Self {
+            sck,
+            mosi,
+            miso: miso.forget_imode(),
+            mode,
+            data_order,
+            half_period_ns: None,
+            _clock: PhantomData,
+        };
+        spi.reconfigure(mode, data_order);
+        spi
+    }
+
+    /// Change the mode and data order for the following transfers.
+    pub fn reconfigure(&mut self, mode: spi::Mode, data_order: DataOrder) {
+        self.mode = mode;
+        self.data_order = data_order;
+        match mode.polarity {
+            spi::Polarity::IdleLow => self.sck.set_low(),
+            spi::Polarity::IdleHigh => self.sck.set_high(),
+        }
+    }
+
+    /// Limit the bus clock to `frequency` (in Hz).
+    ///
+    /// `None` removes the limit, which is the default.
+    pub fn set_max_frequency(&mut self, frequency: Option<u32>) {
+        self.half_period_ns = frequency.map(|f| 500_000_000 / f.max(1));
+    }
+
+    /// Release the pins.
+    pub fn release(
+        self,
+    ) -> (
+        port::Pin<port::mode::Output, SCKPIN>,
+        port::Pin<port::mode::Output, MOSIPIN>,
+        port::Pin<port::mode::Input, MISOPIN>,
+    ) {
+        (self.sck, self.mosi, self.miso)
+    }
+
+    fn delay(&self) {
+        use embedded_hal::delay::DelayNs;
+        if let Some(ns) = self.half_period_ns {
+            crate::delay::Delay::<CLOCK>::new().delay_ns(ns);
+        }
+    }
+
+    fn write_mosi(&mut self, bit: bool) {
+        if bit {
+            self.mosi.set_high();
+        } else {
+            self.mosi.set_low();
+        }
+    }
+
+    /// Shift out `byte` while shifting in the reply.
+    fn transfer_byte(&mut self, byte: u8) -> u8 {
+        // The loop below always shifts MSB first, so reverse the bits for the other order.
+        let mut out = match self.data_order {
+            DataOrder::MostSignificantFirst => byte,
+            DataOrder::LeastSignificantFirst => byte.reverse_bits(),
+        };
+        let mut input = 0u8;
+
+        for _ in 0..8 {
+            let bit = out & 0x80 != 0;
+            out <<= 1;
+
+            // The first toggle of SCK moves it away from the idle level, the second one back.
+            let sampled = match self.mode.phase {
+                spi::Phase::CaptureOnFirstTransition => {
+                    self.write_mosi(bit);
+                    self.delay();
+                    self.sck.toggle();
+                    let sampled = self.miso.is_high();
+                    self.delay();
+                    self.sck.toggle();
+                    sampled
+                }
+                spi::Phase::CaptureOnSecondTransition => {
+                    self.sck.toggle();
+                    self.write_mosi(bit);
+                    self.delay();
+                    self.sck.toggle();
+                    let sampled = self.miso.is_high();
+                    self.delay();
+                    sampled
+                }
+            };
+            input = (input << 1) | sampled as u8;
+        }
+
+        match self.data_order {
+            DataOrder::MostSignificantFirst => input,
+            DataOrder::LeastSignificantFirst => input.reverse_bits(),
+        }
+    }
+}
+
+impl<SCKPIN, MOSIPIN, MISOPIN, CLOCK> spi::ErrorType
+    for SoftSpi<SCKPIN, MOSIPIN, MISOPIN, CLOCK>
+{
+    type Error = core::convert::Infallible;
+}
+
+impl<SCKPIN, MOSIPIN, MISOPIN, CLOCK> SpiBus for SoftSpi<SCKPIN, MOSIPIN, MISOPIN, CLOCK>
+where
+    SCKPIN: port::PinOps,
+    MOSIPIN: port::PinOps,
+    MISOPIN: port::PinOps,
+    crate::delay::Delay<CLOCK>: embedded_hal::delay::DelayNs,
+{
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        // Every transfer is complete when the method returns.
+        Ok(())
+    }
+
+    fn read(&mut self, read: &mut [u8]) -> Result<(), Self::Error> {
+        for b in read.iter_mut() {
+            *b = self.transfer_byte(0x00);
+        }
+        Ok(())
+    }
+
+    fn write(&mut self, write: &[u8]) -> Result<(), Self::Error> {
+        for b in write.iter() {
+            self.transfer_byte(*b);
+        }
+        Ok(())
+    }
+
+    fn transfer(&mut self, read: &mut [u8], write: &[u8]) -> Result<(), Self::Error> {
+        let longest = read.len().max(write.len());
+        for i in 0..longest {
+            let r = self.transfer_byte(*write.get(i).unwrap_or(&0x00));
+            if i < read.len() {
+                read[i] = r;
+            }
+        }
+        Ok(())
+    }
+
+    fn transfer_in_place(&mut self, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        for b in buffer.iter_mut() {
+            *b = self.transfer_byte(*b);
+        }
+        Ok(())
+    }
+}
+
+/// Run `operations` on `bus` for one device of a shared bus.
+///
+/// The bus is reconfigured first if the previous transaction used different settings.
+fn device_transaction<H, SPI, SCLKPIN, MOSIPIN, MISOPIN, CSPIN, CS, CLOCK>(
+    bus: &mut Spi<H, SPI, SCLKPIN, MOSIPIN, MISOPIN, CSPIN>,
+    cs: &mut CS,
+    settings: &Settings,
+    operations: &mut [spi::Operation<'_, u8>],
+) -> Result<(), core::convert::Infallible>
+where
+    SPI: SpiOps<H, SCLKPIN, MOSIPIN, MISOPIN, CSPIN>,
+    SCLKPIN: port::PinOps,
+    MOSIPIN: port::PinOps,
+    MISOPIN: port::PinOps,
+    CSPIN: port::PinOps,
+    CS: OutputPin<Error = core::convert::Infallible>,
+    crate::delay::Delay<CLOCK>: embedded_hal::delay::DelayNs,
+{
+    use embedded_hal::delay::DelayNs;
+
+    if bus.settings != *settings {
+        nb::block!(bus.reconfigure(settings.clone()))?;
+    }
+
+    cs.set_low()?;
+    for operation in operations.iter_mut() {
+        match operation {
+            spi::Operation::Read(buffer) => SpiBus::read(bus, buffer)?,
+            spi::Operation::Write(bytes) => SpiBus::write(bus, bytes)?,
+            spi::Operation::Transfer(read, write) => SpiBus::transfer(bus, read, write)?,
+            spi::Operation::TransferInPlace(buffer) => SpiBus::transfer_in_place(bus, buffer)?,
+            spi::Operation::DelayNs(ns) => {
+                SpiBus::flush(bus)?;
+                crate::delay::Delay::<CLOCK>::new().delay_ns(*ns);
+            }
+        }
+    }
+    SpiBus::flush(bus)?;
+    cs.set_high()?;
+
+    Ok(())
+}
+
+/// Device on an SPI bus which is shared through a `RefCell`
+///
+/// Each device has its own chip-select pin and its own [`Settings`].  The bus is reconfigured
+/// whenever a device uses different settings than the one before it.  This implements the
+/// embedded-hal 1.0 [`SpiDevice`][embedded_hal::spi::SpiDevice] trait, so each device can be
+/// handed to its own driver.
+///
+/// Only use this when all devices are accessed from the same context.  Use
+/// [`CriticalSectionDevice`] if the bus is also used from interrupt handlers.
+///
+/// # Example
+/// (for Arduino Uno)
+/// ```
+/// let dp = arduino_hal::Peripherals::take().unwrap();
+/// let pins = arduino_hal::pins!(dp);
+///
+/// let (spi, _) = arduino_hal::Spi::new(
+///     dp.SPI,
+///     pins.d13.into_output(),
+///     pins.d11.into_output(),
+///     pins.d12.into_pull_up_input(),
+///     pins.d10.into_output(),
+///     spi::Settings::default(),
+/// );
+/// let bus = core::cell::RefCell::new(spi);
+///
+/// let mut sd_card = arduino_hal::spi::RefCellDevice::new(
+///     &bus,
+///     pins.d4.into_output(),
+///     spi::Settings {
+///         clock: spi::SerialClockRate::OscfOver128,
+///         mode: embedded_hal::spi::MODE_0,
+///         ..Default::default()
+///     },
+/// );
+/// let mut display = arduino_hal::spi::RefCellDevice::new(
+///     &bus,
+///     pins.d7.into_output(),
+///     spi::Settings::default(),
+/// );
+/// ```
+pub struct RefCellDevice<'a, BUS, CS, CLOCK> {
+    bus: &'a RefCell<BUS>,
+    cs: CS,
+    settings: Settings,
+    _clock: PhantomData<CLOCK>,
+}
+
+impl<'a, BUS, CS, CLOCK> RefCellDevice<'a, BUS, CS, CLOCK>
+where
+    CS: OutputPin<Error = core::convert::Infallible>,
+{
+    /// Create a device on the shared `bus`, selected by `cs`.
+    ///
+    /// The chip-select pin is driven high, so the device is deselected until it is used.
+    pub fn new(bus: &'a RefCell<BUS>, mut cs: CS, settings: Settings) -> Self {
+        cs.set_high().unwrap_infallible();
+        Self {
+            bus,
+            cs,
+            settings,
+            _clock: PhantomData,
+        }
+    }
+
+    /// Release the chip-select pin.
+    pub fn release(self) -> CS {
+        self.cs
+    }
+}
+
+impl<'a, BUS, CS, CLOCK> spi::ErrorType for RefCellDevice<'a, BUS, CS, CLOCK> {
+    type Error = core::convert::Infallible;
+}
+
+impl<'a, H, SPI, SCLKPIN, MOSIPIN, MISOPIN, CSPIN, CS, CLOCK> spi::SpiDevice
+    for RefCellDevice<'a, Spi<H, SPI, SCLKPIN, MOSIPIN, MISOPIN, CSPIN>, CS, CLOCK>
+where
+    SPI: SpiOps<H, SCLKPIN, MOSIPIN, MISOPIN, CSPIN>,
+    SCLKPIN: port::PinOps,
+    MOSIPIN: port::PinOps,
+    MISOPIN: port::PinOps,
+    CSPIN: port::PinOps,
+    CS: OutputPin<Error = core::convert::Infallible>,
+    crate::delay::Delay<CLOCK>: embedded_hal::delay::DelayNs,
+{
+    fn transaction(
+        &mut self,
+        operations: &mut [spi::Operation<'_, u8>],
+    ) -> Result<(), Self::Error> {
+        let bus = &mut *self.bus.borrow_mut();
+        device_transaction::<_, _, _, _, _, _, _, CLOCK>(
+            bus,
+            &mut self.cs,
+            &self.settings,
+            operations,
+        )
+    }
+}
+
+/// Device on an SPI bus which is shared through a `Mutex`
+///
+/// This works like [`RefCellDevice`], but each transaction runs with interrupts disabled.  Thus
+/// a device can also be used from an interrupt handler while others are used from the main
+/// program, as long as the bus lives long enough (e.g. in a `static` initialized at startup).
+///
+/// # Example
+/// (for Arduino Uno)
+/// ```
+/// let bus = avr_device::interrupt::Mutex::new(core::cell::RefCell::new(spi));
+///
+/// let mut radio = arduino_hal::spi::CriticalSectionDevice::new(
+///     &bus,
+///     pins.d8.into_output(),
+///     spi::Settings::default(),
+/// );
+/// ```
+pub struct CriticalSectionDevice<'a, BUS, CS, CLOCK> {
+    bus: &'a avr_device::interrupt::Mutex<RefCell<BUS>>,
+    cs: CS,
+    settings: Settings,
+    _clock: PhantomData<CLOCK>,
+}
+
+impl<'a, BUS, CS, CLOCK> CriticalSectionDevice<'a, BUS, CS, CLOCK>
+where
+    CS: OutputPin<Error = core::convert::Infallible>,
+{
+    /// Create a device on the shared `bus`, selected by `cs`.
+    ///
+    /// The chip-select pin is driven high, so the device is deselected until it is used.
+    pub fn new(
+        bus: &'a avr_device::interrupt::Mutex<RefCell<BUS>>,
+        mut cs: CS,
+        settings: Settings,
+    ) -> Self {
+        cs.set_high().unwrap_infallible();
+        Self {
+            bus,
+            cs,
+            settings,
+            _clock: PhantomData,
+        }
+    }
+
+    /// Release the chip-select pin.
+    pub fn release(self) -> CS {
+        self.cs
+    }
+}
+
+impl<'a, BUS, CS, CLOCK> spi::ErrorType for CriticalSectionDevice<'a, BUS, CS, CLOCK> {
+    type Error = core::convert::Infallible;
+}
+
+impl<'a, H, SPI, SCLKPIN, MOSIPIN, MISOPIN, CSPIN, CS, CLOCK> spi::SpiDevice
+    for CriticalSectionDevice<'a, Spi<H, SPI, SCLKPIN, MOSIPIN, MISOPIN, CSPIN>, CS, CLOCK>
+where
+    SPI: SpiOps<H, SCLKPIN, MOSIPIN, MISOPIN, CSPIN>,
+    SCLKPIN: port::PinOps,
+    MOSIPIN: port::PinOps,
+    MISOPIN: port::PinOps,
+    CSPIN: port::PinOps,
+    CS: OutputPin<Error = core::convert::Infallible>,
+    crate::delay::Delay<CLOCK>: embedded_hal::delay::DelayNs,
+{
+    fn transaction(
+        &mut self,
+        operations: &mut [spi::Operation<'_, u8>],
+    ) -> Result<(), Self::Error> {
+        avr_device::interrupt::free(|cs| {
+            let bus = &mut *self.bus.borrow(cs).borrow_mut();
+            device_transaction::<_, _, _, _, _, _, _, CLOCK>(
+                bus,
+                &mut self.cs,
+                &self.settings,
+                operations,
+            )
+        })
+    }
+}
+
+/// Edge of the SS line, which frames a transaction of the SPI controller
+#[derive(ufmt::derive::uDebug, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Frame {
+    /// SS went low, this device was selected.
+    Start,
+    /// SS went high, the transaction has ended.
+    End,
+}
+
+/// SPI interface in peripheral (slave) mode
+///
+/// The SPI controller on the other end drives SCLK and selects this device by pulling SS low.
+/// Each byte it clocks in is exchanged with the byte which was preloaded into the data register
+/// using [`SpiPeripheral::preload()`].
+///
+/// For interrupt-driven operation, call [`SpiPeripheral::listen()`] and
+/// [`SpiPeripheral::on_transfer_complete()`] from the `SPI_STC` interrupt handler.  To get notified
+/// about the start and end of transactions, enable a pin-change interrupt for SS and call
+/// [`SpiPeripheral::on_select_change()`] from its handler.
+///
+/// MISO is driven all the time until [`SpiPeripheral::on_select_change()`] is called for the
+/// first time.  From then on, it is only driven while the device is selected, so several
+/// peripherals can share the bus.
+///
+/// # Example
+/// (for Arduino Uno)
+/// ```
+/// let dp = arduino_hal::Peripherals::take().unwrap();
+/// let pins = arduino_hal::pins!(dp);
+///
+/// let mut spi = arduino_hal::spi::SpiPeripheral::new(
+///     dp.SPI,
+///     pins.d13.into_floating_input(),
+///     pins.d11.into_floating_input(),
+///     pins.d12.into_output(),
+///     pins.d10.into_pull_up_input(),
+///     spi::Settings::default(),
+/// );
+///
+/// // Answer every byte with the one received before it.  Tracking SS releases MISO while other
+/// // peripherals are selected.
+/// loop {
+///     spi.on_select_change(|_| ());
+///     if let Ok(byte) = spi.read() {
+///         spi.preload(byte);
+///     }
+/// }
+/// ```
+pub struct SpiPeripheral<H, SPI, SCLKPIN, MOSIPIN, MISOPIN, CSPIN> {
+    p: SPI,
+    sclk: port::Pin<port::mode::Input, SCLKPIN>,
+    mosi: port::Pin<port::mode::Input, MOSIPIN>,
+    miso: port::Pin<port::mode::Output, MISOPIN>,
+    cs: port::Pin<port::mode::Input, CSPIN>,
+    /// Last seen state of SS, or `None` while SS is not tracked.
+    selected: Option<bool>,
+    _h: PhantomData<H>,
+}
+
+impl<H, SPI, SCLKPIN, MOSIPIN, MISOPIN, CSPIN>
+    SpiPeripheral<H, SPI, SCLKPIN, MOSIPIN, MISOPIN, CSPIN>
+where
+    SPI: SpiPeripheralOps<H, SCLKPIN, MOSIPIN, MISOPIN, CSPIN>,
+    SCLKPIN: port::PinOps,
+    MOSIPIN: port::PinOps,
+    MISOPIN: port::PinOps,
+    CSPIN: port::PinOps,
+{
+    /// Instantiate an SPI peripheral with the registers, SCLK/MOSI/MISO/SS pins, and settings.
+    ///
+    /// Unlike for [`Spi`], SS is an input here.  A pull-up keeps the device deselected while no
+    /// controller is connected.  The `clock` of the `settings` is ignored.
+    pub fn new<SCLKMODE, MOSIMODE, CSMODE>(
+        p: SPI,
+        sclk: port::Pin<port::mode::Input<SCLKMODE>, SCLKPIN>,
+        mosi: port::Pin<port::mode::Input<MOSIMODE>, MOSIPIN>,
+        miso: port::Pin<port::mode::Output, MISOPIN>,
+        cs: port::Pin<port::mode::Input<CSMODE>, CSPIN>,
+        settings: Settings,
+    ) -> Self {
+        let mut spi = Self {
+            p,
+            sclk: sclk.forget_imode(),
+            mosi: mosi.forget_imode(),
+            miso,
+            cs: cs.forget_imode(),
+            selected: None,
+            _h: PhantomData,
+        };
+        spi.p.raw_setup_peripheral(&settings);
+        spi
+    }
+
+    /// Write the byte which is sent to the controller during the next transfer.
+    ///
+    /// This must happen before the controller starts clocking the byte, otherwise the write is
+    /// ignored.
+    pub fn preload(&mut self, byte: u8) {
+        self.p.raw_write(byte);
+    }
+
+    /// Return the byte received in the last transfer.
+    ///
+    /// Returns [`nb::Error::WouldBlock`] while no transfer has completed.
+    pub fn read(&mut self) -> nb::Result<u8, core::convert::Infallible> {
+        if self.p.raw_check_iflag() {
+            Ok(self.p.raw_read())
+        } else {
+            Err(nb::Error::WouldBlock)
+        }
+    }
+
+    /// Whether the controller currently selects this device (SS is low).
+    pub fn is_selected(&self) -> bool {
+        self.cs.is_low()
+    }
+
+    /// Enable the `SPI_STC` interrupt, which fires after each transfer.
+    pub fn listen(&mut self) {
+        self.p.raw_interrupt(true);
+    }
+
+    /// Disable the `SPI_STC` interrupt.
+    pub fn unlisten(&mut self) {
+        self.p.raw_interrupt(false);
+    }
+
+    /// Exchange a byte with the controller.
+    ///
+    /// `reply` is called with the received byte and returns the byte for the next transfer.  Call
+    /// this from the `SPI_STC` interrupt handler.
+    pub fn on_transfer_complete(&mut self, reply: impl FnOnce(u8) -> u8) {
+        let byte = self.p.raw_read();
+        self.p.raw_write(reply(byte));
+    }
+
+    /// Track the SS line and call `frame` on each edge.
+    ///
+    /// From the first call on, MISO is driven while this device is selected and released
+    /// otherwise.  Call this from the pin-change interrupt handler of the SS pin, or regularly
+    /// when polling.  If the device is not selected on the first call, MISO is released without
+    /// reporting a [`Frame::End`].
+    pub fn on_select_change(&mut self, frame: impl FnOnce(Frame)) {
+        let selected = self.is_selected();
+        let previous = self.selected.replace(selected);
+        if previous == Some(selected) {
+            return;
+        }
+
+        if selected {
+            unsafe { self.miso.pin.make_output() };
+            frame(Frame::Start);
+        } else {
+            unsafe { self.miso.pin.make_input(false) };
+            if previous.is_some() {
+                frame(Frame::End);
+            }
+        }
+    }
+
+    /// Disable the SPI device and release ownership of the peripheral and pins.
+    pub fn release(
+        mut self,
+    ) -> (
+        SPI,
+        port::Pin<port::mode::Input, SCLKPIN>,
+        port::Pin<port::mode::Input, MOSIPIN>,
+        port::Pin<port::mode::Output, MISOPIN>,
+        port::Pin<port::mode::Input, CSPIN>,
+    ) {
+        self.p.raw_release();
+        unsafe { self.miso.pin.make_output() };
+        (self.p, self.sclk, self.mosi, self.miso, self.cs)
+    }
+}
+
 /// Implement traits for a SPI interface
 #[macro_export]
 macro_rules! impl_spi {
@@ -525,5 +1166,33 @@ macro_rules! impl_spi {
                 self.raw_read()
             }
         }
+
+        impl $crate::spi::SpiPeripheralOps<$HAL, $sclkpin, $mosipin, $misopin, $cspin> for $SPI {
+            fn raw_setup_peripheral(&mut self, settings: &Settings) {
+                use $crate::hal::spi;
+
+                self.spcr.write(|w| {
+                    // enable SPI in secondary mode
+                    w.spe().set_bit();
+                    w.mstr().clear_bit();
+                    match settings.data_order {
+                        DataOrder::MostSignificantFirst => w.dord().clear_bit(),
+                        DataOrder::LeastSignificantFirst => w.dord().set_bit(),
+                    };
+                    match settings.mode.polarity {
+                        spi::Polarity::IdleHigh => w.cpol().set_bit(),
+                        spi::Polarity::IdleLow => w.cpol().clear_bit(),
+                    };
+                    match settings.mode.phase {
+                        spi::Phase::CaptureOnFirstTransition => w.cpha().clear_bit(),
+                        spi::Phase::CaptureOnSecondTransition => w.cpha().set_bit(),
+                    }
+                });
+            }
+
+            fn raw_interrupt(&mut self, state: bool) {
+                self.spcr.modify(|_, w| w.spie().bit(state));
+            }
+        }
     };
 }
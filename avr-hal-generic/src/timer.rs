@@ -0,0 +1,447 @@
+//! Timer/Counter Implementation
+//!
+//! Check the documentation of [`Timer`] for details.
+
+use core::marker::PhantomData;
+
+/// Clock prescaler for timers
+///
+/// The prescaler divides the IO clock to produce the rate at which the timer counts:
+///
+/// ```text
+/// F_tick = CLK_io / Prescaler
+/// ```
+///
+/// For PWM, the prescaler dictates the PWM frequency, together with the IO clock.  The formula is
+/// as follows:
+///
+/// ```text
+/// F_pwm = CLK_io / (Prescaler * 256);
+/// ```
+///
+/// | Prescaler | 16 MHz Clock | 8 MHz Clock |
+/// | --- | --- | ---|
+/// | `Direct` | 62.5 kHz | 31.3 kHz |
+/// | `Prescale8` | 7.81 kHz | 3.91 kHz |
+/// | `Prescale64` | 977 Hz | 488 Hz |
+/// | `Prescale256` | 244 Hz | 122 Hz |
+/// | `Prescale1024` | 61.0 Hz | 30.5 Hz |
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Prescaler {
+    /// No prescaling, the IO clock drives the timer directly.
+    Direct,
+    /// Divide the IO clock by 8.
+    Prescale8,
+    /// Divide the IO clock by 64.
+    Prescale64,
+    /// Divide the IO clock by 256.
+    Prescale256,
+    /// Divide the IO clock by 1024.
+    Prescale1024,
+}
+
+impl Prescaler {
+    /// The factor by which this prescaler divides the IO clock.
+    pub const fn divider(self) -> u32 {
+        match self {
+            Prescaler::Direct => 1,
+            Prescaler::Prescale8 => 8,
+            Prescaler::Prescale64 => 64,
+            Prescaler::Prescale256 => 256,
+            Prescaler::Prescale1024 => 1024,
+        }
+    }
+}
+
+/// Waveform generation mode of a timer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    /// Count up to the maximum value of the counter and wrap around to zero.
+    ///
+    /// The [`Event::Overflow`] interrupt fires on every wraparound.
+    Normal,
+    /// Clear Timer on Compare match: count up to the value of compare register A, then restart at
+    /// zero.
+    ///
+    /// The [`Event::CompareMatchA`] interrupt fires whenever the counter is reset.
+    Ctc,
+    /// Fast PWM, counting from zero up to 255 (also for 16-bit timers).
+    FastPwm,
+    /// Phase-correct PWM, counting from zero up to 255 and back down to zero (also for 16-bit
+    /// timers).
+    PhaseCorrectPwm,
+}
+
+/// Events/Interrupts for timer peripherals
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Event {
+    /// The counter overflowed.
+    ///
+    /// Corresponds to the `TIMER#_OVF` interrupt.  Please refer to the datasheet for your MCU for
+    /// details.
+    Overflow,
+
+    /// The counter matched the value of compare register A.
+    ///
+    /// Corresponds to the `TIMER#_COMPA` interrupt.  Please refer to the datasheet for your MCU for
+    /// details.
+    CompareMatchA,
+
+    /// The counter matched the value of compare register B.
+    ///
+    /// Corresponds to the `TIMER#_COMPB` interrupt.  Please refer to the datasheet for your MCU for
+    /// details.
+    CompareMatchB,
+}
+
+/// Internal trait for low-level timer/counter peripherals.
+///
+/// This trait defines the common interface for all timer peripheral variants.  It is used as an
+/// intermediate abstraction ontop of which the [`Timer`] API is built.  **Prefer using the
+/// [`Timer`] API instead of this trait.**
+pub trait TimerOps<H> {
+    /// Type of the counter register (`u8` for 8-bit timers, `u16` for 16-bit timers).
+    type Counter: Copy + Default;
+
+    /// Configure the waveform generation mode of this timer.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_set_mode(&mut self, mode: Mode);
+
+    /// Start the timer by connecting it to the prescaled IO clock.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_start(&mut self, prescaler: Prescaler);
+
+    /// Stop the timer by disconnecting its clock source.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_stop(&mut self);
+
+    /// Read the current value of the counter register.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_read_counter(&self) -> Self::Counter;
+
+    /// Overwrite the current value of the counter register.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_write_counter(&mut self, value: Self::Counter);
+
+    /// Read compare register A.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_read_compare_a(&self) -> Self::Counter;
+
+    /// Write compare register A.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_write_compare_a(&mut self, value: Self::Counter);
+
+    /// Read compare register B.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_read_compare_b(&self) -> Self::Counter;
+
+    /// Write compare register B.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_write_compare_b(&mut self, value: Self::Counter);
+
+    /// Enable/Disable a certain interrupt.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_interrupt(&mut self, event: Event, state: bool);
+
+    /// Check whether the interrupt flag for a certain event is set.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_is_pending(&self, event: Event) -> bool;
+
+    /// Clear the interrupt flag for a certain event.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_clear_pending(&mut self, event: Event);
+}
+
+/// Timer/Counter driver
+///
+/// # Example
+/// (for Arduino Uno)
+/// ```
+/// let dp = arduino_hal::Peripherals::take().unwrap();
+///
+/// // Count at 16 MHz / 256 = 62.5 kHz and reset every 15625 ticks (4 Hz).
+/// let mut timer = arduino_hal::timer::Timer::new(dp.TC1, Mode::Ctc, Prescaler::Prescale256);
+/// timer.set_compare_a(15624);
+/// timer.listen(Event::CompareMatchA);
+///
+/// loop {
+///     let ticks = timer.counter();
+///     // ...
+/// }
+/// ```
+pub struct Timer<H, TC, CLOCK> {
+    p: TC,
+    prescaler: Option<Prescaler>,
+    _clock: PhantomData<CLOCK>,
+    _h: PhantomData<H>,
+}
+
+impl<H, TC, CLOCK> Timer<H, TC, CLOCK>
+where
+    TC: TimerOps<H>,
+    CLOCK: crate::clock::Clock,
+{
+    /// Initialize a timer in the given mode and start it with the given prescaler.
+    ///
+    /// The counter is reset to zero.
+    pub fn new(p: TC, mode: Mode, prescaler: Prescaler) -> Self {
+        let mut timer = Self {
+            p,
+            prescaler: None,
+            _clock: PhantomData,
+            _h: PhantomData,
+        };
+        timer.configure(mode, prescaler);
+        timer
+    }
+
+    /// Reconfigure mode and prescaler of this timer.
+    ///
+    /// The timer is stopped while reconfiguring and the counter is reset to zero.  Enabled
+    /// interrupts are left untouched.
+    pub fn configure(&mut self, mode: Mode, prescaler: Prescaler) {
+        self.p.raw_stop();
+        self.p.raw_set_mode(mode);
+        self.p.raw_write_counter(TC::Counter::default());
+        self.start(prescaler);
+    }
+
+    /// (Re)start the timer with the given prescaler.
+    ///
+    /// The counter keeps its current value.
+    pub fn start(&mut self, prescaler: Prescaler) {
+        self.prescaler = Some(prescaler);
+        self.p.raw_start(prescaler);
+    }
+
+    /// Stop the timer.
+    ///
+    /// The counter keeps its current value until the timer is started again.
+    pub fn stop(&mut self) {
+        self.prescaler = None;
+        self.p.raw_stop();
+    }
+
+    /// Whether the timer is currently counting.
+    pub fn is_running(&self) -> bool {
+        self.prescaler.is_some()
+    }
+
+    /// The prescaler the timer is running with, or `None` if it is stopped.
+    pub fn prescaler(&self) -> Option<Prescaler> {
+        self.prescaler
+    }
+
+    /// Frequency in Hz at which the counter is incremented, or `0` if the timer is stopped.
+    pub fn tick_hz(&self) -> u32 {
+        match self.prescaler {
+            Some(prescaler) => CLOCK::FREQ / prescaler.divider(),
+            None => 0,
+        }
+    }
+
+    /// Read the current counter value.
+    pub fn counter(&self) -> TC::Counter {
+        self.p.raw_read_counter()
+    }
+
+    /// Overwrite the current counter value.
+    pub fn set_counter(&mut self, value: TC::Counter) {
+        self.p.raw_write_counter(value);
+    }
+
+    /// Read compare register A.
+    pub fn compare_a(&self) -> TC::Counter {
+        self.p.raw_read_compare_a()
+    }
+
+    /// Write compare register A.
+    ///
+    /// In [`Mode::Ctc`], this is the value after which the counter restarts at zero.
+    pub fn set_compare_a(&mut self, value: TC::Counter) {
+        self.p.raw_write_compare_a(value);
+    }
+
+    /// Read compare register B.
+    pub fn compare_b(&self) -> TC::Counter {
+        self.p.raw_read_compare_b()
+    }
+
+    /// Write compare register B.
+    pub fn set_compare_b(&mut self, value: TC::Counter) {
+        self.p.raw_write_compare_b(value);
+    }
+
+    /// Enable the interrupt for [`Event`].
+    pub fn listen(&mut self, event: Event) {
+        self.p.raw_interrupt(event, true);
+    }
+
+    /// Disable the interrupt for [`Event`].
+    pub fn unlisten(&mut self, event: Event) {
+        self.p.raw_interrupt(event, false);
+    }
+
+    /// Check whether the flag for [`Event`] is set.
+    ///
+    /// The hardware clears the flag automatically when the corresponding interrupt handler is
+    /// executed.  When polling, use [`Timer::clear_pending`] to reset it.
+    pub fn is_pending(&self, event: Event) -> bool {
+        self.p.raw_is_pending(event)
+    }
+
+    /// Clear the flag for [`Event`].
+    pub fn clear_pending(&mut self, event: Event) {
+        self.p.raw_clear_pending(event);
+    }
+
+    /// Stop the timer and release the peripheral.
+    pub fn release(mut self) -> TC {
+        self.p.raw_interrupt(Event::Overflow, false);
+        self.p.raw_interrupt(Event::CompareMatchA, false);
+        self.p.raw_interrupt(Event::CompareMatchB, false);
+        self.p.raw_stop();
+        self.p
+    }
+}
+
+/// Implement [`TimerOps`] for a timer/counter peripheral.
+///
+/// Timers with a single compare register leave out `ocrb` and `compare_b`.  Their compare
+/// register is used as compare register A, while writes to compare register B are ignored, reads
+/// return zero and [`Event::CompareMatchB`] never occurs.
+#[macro_export]
+macro_rules! impl_timer {
+    (@read_compare_b $self:ident) => { Default::default() };
+    (@read_compare_b $self:ident $ocrb:ident) => { $self.$ocrb.read().bits() };
+    (
+        hal: $HAL:ty,
+        peripheral: $TC:ty,
+        counter: $Counter:ty,
+        tcnt: $tcnt:ident,
+        ocra: $ocra:ident,
+        $(ocrb: $ocrb:ident,)?
+        timsk: $timsk:ident {
+            overflow: $toie:ident,
+            compare_a: $ociea:ident
+            $(, compare_b: $ocieb:ident)? $(,)?
+        },
+        tifr: $tifr:ident {
+            overflow: $tov:ident,
+            compare_a: $ocfa:ident
+            $(, compare_b: $ocfb:ident)? $(,)?
+        },
+        set_mode: |$mode_timer:ident, $mode:ident| $mode_block:block,
+        start: |$start_timer:ident, $prescaler:ident| $start_block:block,
+        stop: |$stop_timer:ident| $stop_block:block,
+    ) => {
+        impl $crate::timer::TimerOps<$HAL> for $TC {
+            type Counter = $Counter;
+
+            #[inline]
+            fn raw_set_mode(&mut self, mode: $crate::timer::Mode) {
+                let $mode_timer = &*self;
+                let $mode = mode;
+                $mode_block
+            }
+
+            #[inline]
+            fn raw_start(&mut self, prescaler: $crate::timer::Prescaler) {
+                let $start_timer = &*self;
+                let $prescaler = prescaler;
+                $start_block
+            }
+
+            #[inline]
+            fn raw_stop(&mut self) {
+                let $stop_timer = &*self;
+                $stop_block
+            }
+
+            #[inline]
+            fn raw_read_counter(&self) -> Self::Counter {
+                self.$tcnt.read().bits()
+            }
+
+            #[inline]
+            fn raw_write_counter(&mut self, value: Self::Counter) {
+                self.$tcnt.write(|w| w.bits(value));
+            }
+
+            #[inline]
+            fn raw_read_compare_a(&self) -> Self::Counter {
+                self.$ocra.read().bits()
+            }
+
+            #[inline]
+            fn raw_write_compare_a(&mut self, value: Self::Counter) {
+                self.$ocra.write(|w| w.bits(value));
+            }
+
+            #[inline]
+            fn raw_read_compare_b(&self) -> Self::Counter {
+                $crate::impl_timer!(@read_compare_b self $($ocrb)?)
+            }
+
+            #[inline]
+            fn raw_write_compare_b(&mut self, _value: Self::Counter) {
+                $(self.$ocrb.write(|w| w.bits(_value));)?
+            }
+
+            #[inline]
+            fn raw_interrupt(&mut self, event: $crate::timer::Event, state: bool) {
+                // SAFETY: The interrupt mask register is shared between multiple timers on some
+                // MCUs.  The read-modify-write sequence is thus wrapped in a critical section.
+                $crate::avr_device::interrupt::free(|_| match event {
+                    $crate::timer::Event::Overflow => {
+                        self.$timsk.modify(|_, w| w.$toie().bit(state))
+                    }
+                    $crate::timer::Event::CompareMatchA => {
+                        self.$timsk.modify(|_, w| w.$ociea().bit(state))
+                    }
+                    $crate::timer::Event::CompareMatchB => {
+                        $(self.$timsk.modify(|_, w| w.$ocieb().bit(state));)?
+                    }
+                });
+            }
+
+            #[inline]
+            fn raw_is_pending(&self, event: $crate::timer::Event) -> bool {
+                let r = self.$tifr.read();
+                match event {
+                    $crate::timer::Event::Overflow => r.$tov().bit_is_set(),
+                    $crate::timer::Event::CompareMatchA => r.$ocfa().bit_is_set(),
+                    $crate::timer::Event::CompareMatchB => false $(|| r.$ocfb().bit_is_set())?,
+                }
+            }
+
+            #[inline]
+            fn raw_clear_pending(&mut self, event: $crate::timer::Event) {
+                // Flags are cleared by writing a logical one.  Writing zeros to the other flags
+                // leaves them untouched, so no read-modify-write is necessary.
+                match event {
+                    $crate::timer::Event::Overflow => self.$tifr.write(|w| w.$tov().set_bit()),
+                    $crate::timer::Event::CompareMatchA => {
+                        self.$tifr.write(|w| w.$ocfa().set_bit())
+                    }
+                    $crate::timer::Event::CompareMatchB => {
+                        $(self.$tifr.write(|w| w.$ocfb().set_bit());)?
+                    }
+                }
+            }
+        }
+    };
+}
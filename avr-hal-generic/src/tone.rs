@@ -0,0 +1,360 @@
+//! Tone Generator Implementation
+//!
+//! Check the documentation of [`Tone`] and [`SoftTone`] for details.
+
+use crate::port;
+use crate::timer::{Event, Mode, Prescaler, TimerOps};
+use core::marker::PhantomData;
+
+/// Error returned when a tone cannot be generated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToneError {
+    /// The requested frequency is zero or cannot be generated with any prescaler.
+    FrequencyOutOfRange,
+}
+
+/// Internal trait for timers which can toggle their `OCnA` pin in hardware.
+///
+/// This trait is implemented for each timer peripheral together with the pin that is connected to
+/// its compare output A.  It is used as an intermediate abstraction ontop of which the [`Tone`]
+/// API is built.  **Prefer using the [`Tone`] API instead of this trait.**
+pub trait ToneOps<H, PIN>: TimerOps<H> {
+    /// Enable/Disable toggling of the `OCnA` pin on every compare match A.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_toggle_output(&mut self, state: bool);
+}
+
+/// Timer state shared by [`Tone`] and [`SoftTone`].
+struct ToneTimer<H, TC, CLOCK> {
+    p: TC,
+    prescaler: Option<Prescaler>,
+    remaining: Option<u32>,
+    _clock: PhantomData<CLOCK>,
+    _h: PhantomData<H>,
+}
+
+impl<H, TC, CLOCK> ToneTimer<H, TC, CLOCK>
+where
+    TC: TimerOps<H>,
+    TC::Counter: Into<u32> + TryFrom<u32>,
+    CLOCK: crate::clock::Clock,
+{
+    fn new(mut p: TC) -> Self {
+        p.raw_stop();
+        p.raw_interrupt(Event::CompareMatchA, false);
+        p.raw_set_mode(Mode::Ctc);
+
+        Self {
+            p,
+            prescaler: None,
+            remaining: None,
+            _clock: PhantomData,
+            _h: PhantomData,
+        }
+    }
+
+    /// Find the smallest prescaler for which the half-period of `frequency` fits the counter.
+    fn parameters(frequency: u32) -> Option<(Prescaler, TC::Counter)> {
+        if frequency == 0 {
+            return None;
+        }
+
+        for prescaler in [
+            Prescaler::Direct,
+            Prescaler::Prescale8,
+            Prescaler::Prescale64,
+            Prescaler::Prescale256,
+            Prescaler::Prescale1024,
+        ] {
+            // The output is toggled twice per period.
+            let divisor = frequency.saturating_mul(2);
+            let tick_hz = CLOCK::FREQ / prescaler.divider();
+            let ticks = (tick_hz + divisor / 2) / divisor;
+
+            if ticks == 0 {
+                // Larger prescalers only make the half-period shorter.
+                return None;
+            }
+            if let Ok(compare) = TC::Counter::try_from(ticks - 1) {
+                return Some((prescaler, compare));
+            }
+        }
+
+        None
+    }
+
+    fn start(
+        &mut self,
+        frequency: u32,
+        duration_ms: Option<u32>,
+        listen: bool,
+    ) -> Result<(), ToneError> {
+        let (prescaler, compare) =
+            Self::parameters(frequency).ok_or(ToneError::FrequencyOutOfRange)?;
+
+        self.p.raw_stop();
+        self.p.raw_write_counter(TC::Counter::default());
+        self.p.raw_write_compare_a(compare);
+
+        self.remaining = duration_ms.map(|ms| {
+            let toggles = frequency as u64 * ms as u64 / 500;
+            toggles.clamp(1, u32::MAX as u64) as u32
+        });
+
+        self.p.raw_clear_pending(Event::CompareMatchA);
+        self.p.raw_interrupt(Event::CompareMatchA, listen || self.remaining.is_some());
+
+        self.prescaler = Some(prescaler);
+        self.p.raw_start(prescaler);
+        Ok(())
+    }
+
+    fn stop(&mut self) {
+        self.p.raw_stop();
+        self.p.raw_interrupt(Event::CompareMatchA, false);
+        self.p.raw_clear_pending(Event::CompareMatchA);
+        self.prescaler = None;
+        self.remaining = None;
+    }
+
+    /// Count down the remaining toggles.  Returns `false` once the tone has ended.
+    fn on_compare_match(&mut self) -> bool {
+        match self.remaining {
+            Some(0) | Some(1) => {
+                self.stop();
+                false
+            }
+            Some(remaining) => {
+                self.remaining = Some(remaining - 1);
+                true
+            }
+            None => self.prescaler.is_some(),
+        }
+    }
+
+    fn frequency(&self) -> Option<u32> {
+        let prescaler = self.prescaler?;
+        let ticks = self.p.raw_read_compare_a().into() + 1;
+        Some(CLOCK::FREQ / prescaler.divider() / (2 * ticks))
+    }
+}
+
+/// Square-wave generator which toggles the `OCnA` pin of a timer in hardware
+///
+/// The timer runs in CTC mode and toggles its compare output A on every compare match, so a
+/// running tone needs no CPU time at all.  The prescaler is chosen from the requested frequency
+/// and the core clock `CLOCK`.  Only the `OCnA` pin of the timer can be used, passing any other
+/// pin is a compile-time error.  Use [`SoftTone`] for other pins.
+///
+/// Tones with a duration ([`Tone::play_for()`]) are stopped by counting compare matches, so
+/// [`Tone::on_compare_match()`] must be called from the `TIMERn_COMPA` interrupt handler.
+///
+/// # Example
+/// (for Arduino Uno)
+/// ```
+/// let dp = arduino_hal::Peripherals::take().unwrap();
+/// let pins = arduino_hal::pins!(dp);
+///
+/// // D11 is OC2A
+/// let mut tone = Tone::new(dp.TC2, pins.d11.into_output());
+///
+/// // Concert pitch A
+/// tone.play(440).unwrap();
+/// arduino_hal::delay_ms(500);
+/// tone.stop();
+/// ```
+pub struct Tone<H, TC, PIN, CLOCK> {
+    timer: ToneTimer<H, TC, CLOCK>,
+    pin: port::Pin<port::mode::Output, PIN>,
+}
+
+impl<H, TC, PIN, CLOCK> Tone<H, TC, PIN, CLOCK>
+where
+    TC: ToneOps<H, PIN>,
+    TC::Counter: Into<u32> + TryFrom<u32>,
+    PIN: port::PinOps,
+    CLOCK: crate::clock::Clock,
+{
+    /// Initialize the timer for generating tones on the given pin.
+    ///
+    /// The timer is stopped until a tone is played.
+    pub fn new(p: TC, pin: port::Pin<port::mode::Output, PIN>) -> Self {
+        Self {
+            timer: ToneTimer::new(p),
+            pin,
+        }
+    }
+
+    /// Play a tone with the given frequency (in Hz) until it is stopped.
+    pub fn play(&mut self, frequency: u32) -> Result<(), ToneError> {
+        self.timer.start(frequency, None, false)?;
+        self.timer.p.raw_toggle_output(true);
+        Ok(())
+    }
+
+    /// Play a tone with the given frequency (in Hz) for `duration_ms` milliseconds.
+    ///
+    /// This enables the `TIMERn_COMPA` interrupt, whose handler must call
+    /// [`Tone::on_compare_match()`].
+    pub fn play_for(&mut self, frequency: u32, duration_ms: u32) -> Result<(), ToneError> {
+        self.timer.start(frequency, Some(duration_ms), false)?;
+        self.timer.p.raw_toggle_output(true);
+        Ok(())
+    }
+
+    /// Stop the tone and drive the pin low.
+    pub fn stop(&mut self) {
+        self.timer.stop();
+        self.timer.p.raw_toggle_output(false);
+        self.pin.set_low();
+    }
+
+    /// Whether a tone is currently playing.
+    pub fn is_playing(&self) -> bool {
+        self.timer.prescaler.is_some()
+    }
+
+    /// The frequency which is actually generated, or `None` if no tone is playing.
+    ///
+    /// This can deviate slightly from the requested frequency due to rounding.
+    pub fn frequency(&self) -> Option<u32> {
+        self.timer.frequency()
+    }
+
+    /// Stop the tone once its duration has passed.
+    ///
+    /// Call this from the `TIMERn_COMPA` interrupt handler.
+    pub fn on_compare_match(&mut self) {
+        if !self.timer.on_compare_match() {
+            self.timer.p.raw_toggle_output(false);
+            self.pin.set_low();
+        }
+    }
+
+    /// Stop the tone and release the timer and pin.
+    pub fn release(mut self) -> (TC, port::Pin<port::mode::Output, PIN>) {
+        self.stop();
+        (self.timer.p, self.pin)
+    }
+}
+
+/// Square-wave generator which toggles an arbitrary pin from an interrupt handler
+///
+/// This works like [`Tone`], but the pin is toggled in software, so
+/// [`SoftTone::on_compare_match()`] must be called from the `TIMERn_COMPA` interrupt handler for
+/// every tone.  The interrupt latency makes the waveform less precise, and high frequencies cost a
+/// lot of CPU time.
+///
+/// # Example
+/// (for Arduino Uno)
+/// ```
+/// let dp = arduino_hal::Peripherals::take().unwrap();
+/// let pins = arduino_hal::pins!(dp);
+///
+/// let mut tone = SoftTone::new(dp.TC2, pins.d4.into_output());
+///
+/// // Usually moved into a `static` which the TIMER2_COMPA handler can access.
+/// tone.play_for(1000, 200).unwrap();
+/// ```
+pub struct SoftTone<H, TC, PIN, CLOCK> {
+    timer: ToneTimer<H, TC, CLOCK>,
+    pin: port::Pin<port::mode::Output, PIN>,
+}
+
+impl<H, TC, PIN, CLOCK> SoftTone<H, TC, PIN, CLOCK>
+where
+    TC: TimerOps<H>,
+    TC::Counter: Into<u32> + TryFrom<u32>,
+    PIN: port::PinOps,
+    CLOCK: crate::clock::Clock,
+{
+    /// Initialize the timer for generating tones on the given pin.
+    ///
+    /// The timer is stopped until a tone is played.
+    pub fn new(p: TC, pin: port::Pin<port::mode::Output, PIN>) -> Self {
+        Self {
+            timer: ToneTimer::new(p),
+            pin,
+        }
+    }
+
+    /// Play a tone with the given frequency (in Hz) until it is stopped.
+    pub fn play(&mut self, frequency: u32) -> Result<(), ToneError> {
+        self.timer.start(frequency, None, true)
+    }
+
+    /// Play a tone with the given frequency (in Hz) for `duration_ms` milliseconds.
+    pub fn play_for(&mut self, frequency: u32, duration_ms: u32) -> Result<(), ToneError> {
+        self.timer.start(frequency, Some(duration_ms), true)
+    }
+
+    /// Stop the tone and drive the pin low.
+    pub fn stop(&mut self) {
+        self.timer.stop();
+        self.pin.set_low();
+    }
+
+    /// Whether a tone is currently playing.
+    pub fn is_playing(&self) -> bool {
+        self.timer.prescaler.is_some()
+    }
+
+    /// The frequency which is actually generated, or `None` if no tone is playing.
+    ///
+    /// This can deviate slightly from the requested frequency due to rounding.
+    pub fn frequency(&self) -> Option<u32> {
+        self.timer.frequency()
+    }
+
+    /// Toggle the pin and stop the tone once its duration has passed.
+    ///
+    /// Call this from the `TIMERn_COMPA` interrupt handler.
+    pub fn on_compare_match(&mut self) {
+        if self.timer.on_compare_match() {
+            self.pin.toggle();
+        } else {
+            self.pin.set_low();
+        }
+    }
+
+    /// Stop the tone and release the timer and pin.
+    pub fn release(mut self) -> (TC, port::Pin<port::mode::Output, PIN>) {
+        self.stop();
+        (self.timer.p, self.pin)
+    }
+}
+
+/// Implement [`ToneOps`] for the timers of an MCU.
+///
+/// For each timer, the pin connected to its compare output A and the control register together
+/// with the bit offset of the two `COMnA` bits are given.
+#[macro_export]
+macro_rules! impl_tone {
+    (
+        hal: $HAL:ty,
+        timers: {
+            $(
+                $TC:ty: {
+                    pin: $pin:ty,
+                    com: $tccr:ident[$shift:literal],
+                },
+            )+
+        },
+    ) => {
+        $(
+            impl $crate::tone::ToneOps<$HAL, $pin> for $TC {
+                #[inline]
+                fn raw_toggle_output(&mut self, state: bool) {
+                    // SAFETY: Only the two compare output mode bits of output A are changed.
+                    // `0b01` toggles the pin on compare match, `0b00` disconnects it.
+                    $crate::avr_device::interrupt::free(|_| {
+                        self.$tccr.modify(|r, w| unsafe {
+                            w.bits(r.bits() & !(0b11 << $shift) | (state as u8) << $shift)
+                        })
+                    });
+                }
+            }
+        )+
+    };
+}
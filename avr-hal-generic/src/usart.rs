@@ -3,8 +3,10 @@
 //! Check the documentation of [`Usart`] for details.
 
 use crate::prelude::*;
+use core::cell::RefCell;
 use core::cmp::Ordering;
 use core::marker;
+use core::task::{Poll, Waker};
 
 use crate::port;
 
@@ -50,26 +52,61 @@ impl<CLOCK: crate::clock::Clock> From<u32> for Baudrate<CLOCK> {
 
 impl<CLOCK: crate::clock::Clock> Baudrate<CLOCK> {
     /// Calculate parameters for a certain baudrate at a certain `CLOCK` speed.
-    pub fn new(baud: u32) -> Baudrate<CLOCK> {
-        let mut ubrr = (CLOCK::FREQ / 4 / baud - 1) / 2;
-        let mut u2x = true;
-        debug_assert!(ubrr <= u16::MAX as u32);
-        if ubrr > 4095 {
-            u2x = false;
-            ubrr = (CLOCK::FREQ / 8 / baud - 1) / 2;
+    ///
+    /// Both normal and double speed (`U2X#`) operation are considered and the one which gets
+    /// closer to the requested baudrate is chosen.  If both are equally close, normal speed is
+    /// preferred as its receiver is more tolerant of noise and clock deviations.
+    ///
+    /// The baudrate is not guaranteed to be achievable at all: Use [`Baudrate::error_ppm()`] to
+    /// check the result or [`Baudrate::new_checked()`] to reject inaccurate baudrates.
+    pub const fn new(baud: u32) -> Baudrate<CLOCK> {
+        let normal = Self::with_u2x(baud, false);
+        let double = Self::with_u2x(baud, true);
+
+        if double.error_ppm(baud).unsigned_abs() < normal.error_ppm(baud).unsigned_abs() {
+            double
+        } else {
+            normal
         }
+    }
 
-        Baudrate {
-            ubrr: ubrr as u16,
-            u2x,
-            _clock: marker::PhantomData,
+    /// Calculate parameters for a certain baudrate, allowing at most the given error.
+    ///
+    /// Returns `None` if the baudrate which can be generated at this `CLOCK` speed deviates more
+    /// than `max_error_ppm` (in parts per million) from the requested one.
+    pub const fn try_new(baud: u32, max_error_ppm: u32) -> Option<Baudrate<CLOCK>> {
+        let br = Self::new(baud);
+        if br.error_ppm(baud).unsigned_abs() <= max_error_ppm {
+            Some(br)
+        } else {
+            None
+        }
+    }
+
+    /// Calculate parameters for a certain baudrate, allowing at most the given error.
+    ///
+    /// This is meant to be evaluated at compile time, where an inaccurate baudrate becomes a build
+    /// error:
+    ///
+    /// ```
+    /// // Fails to compile at 8 MHz, where 115200 baud are off by -3.5%.
+    /// const BAUDRATE: Baudrate<MHz8> = Baudrate::new_checked(115200, 20_000);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if the baudrate deviates more than `max_error_ppm` (in parts per million) from the
+    /// requested one.
+    pub const fn new_checked(baud: u32, max_error_ppm: u32) -> Baudrate<CLOCK> {
+        match Self::try_new(baud, max_error_ppm) {
+            Some(br) => br,
+            None => panic!("baudrate error exceeds the allowed maximum"),
         }
     }
 
     /// Construct a `Baudrate` from given `UBRR#` and `U2X#` values.
     ///
     /// This provides exact control over the resulting clock speed.
-    pub fn with_exact(u2x: bool, ubrr: u16) -> Baudrate<CLOCK> {
+    pub const fn with_exact(u2x: bool, ubrr: u16) -> Baudrate<CLOCK> {
         Baudrate {
             ubrr,
             u2x,
@@ -77,7 +114,46 @@ impl<CLOCK: crate::clock::Clock> Baudrate<CLOCK> {
         }
     }
 
-    fn compare_value(&self) -> u32 {
+    /// Find the closest `UBRR#` value for the given `U2X#` setting.
+    const fn with_u2x(baud: u32, u2x: bool) -> Baudrate<CLOCK> {
+        let divisor = if u2x { 8 } else { 16 } * baud as u64;
+        let ubrr = (CLOCK::FREQ as u64 + divisor / 2) / divisor;
+        // `UBRR#` is a 12-bit register.
+        let ubrr = if ubrr == 0 {
+            0
+        } else if ubrr > 4096 {
+            4095
+        } else {
+            ubrr - 1
+        };
+
+        Self::with_exact(u2x, ubrr as u16)
+    }
+
+    /// The baudrate which is actually generated with these parameters.
+    pub const fn actual(&self) -> u32 {
+        let divisor = self.compare_value();
+        (CLOCK::FREQ + divisor / 2) / divisor
+    }
+
+    /// Deviation of the generated baudrate from `baud`, in parts per million.
+    ///
+    /// A positive value means that the generated baudrate is too fast.  Depending on the frame
+    /// format, the receiver tolerates a deviation of about ±2% (±20000 ppm) in total, which is
+    /// shared between the two sides of a connection.
+    pub const fn error_ppm(&self, baud: u32) -> i32 {
+        let divisor = self.compare_value() as i64;
+        let baud = baud as i64;
+        let error = (CLOCK::FREQ as i64 - baud * divisor) * 1_000_000 / (baud * divisor);
+
+        if error > i32::MAX as i64 {
+            i32::MAX
+        } else {
+            error as i32
+        }
+    }
+
+    const fn compare_value(&self) -> u32 {
         if self.u2x {
             8 * (self.ubrr as u32 + 1)
         } else {
@@ -133,14 +209,203 @@ impl BaudrateArduinoExt for u32 {
         //
         // https://github.com/arduino/ArduinoCore-avr/blob/3055c1efa3c6980c864f661e6c8cc5d5ac773af4/cores/arduino/HardwareSerial.cpp#L123-L132
         if CLOCK::FREQ == 16_000_000 && br.ubrr == 34 && br.u2x {
-            // (CLOCK::FREQ / 8 / 57600 - 1) / 2 == 16
-            Baudrate::with_exact(false, 16)
+            // Same as the original firmware: UBRR = 16 without U2X
+            Baudrate::with_u2x(self, false)
         } else {
             br
         }
     }
 }
 
+/// Number of data bits in a USART frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataBits {
+    Five,
+    Six,
+    Seven,
+    Eight,
+    /// 9-bit frames, transmitted using [`Usart::write_9bit()`] and received using
+    /// [`Usart::read_9bit()`].
+    ///
+    /// The ninth bit is also used to mark address frames in multi-processor communication mode,
+    /// see [`Usart::set_multiprocessor_mode()`].
+    Nine,
+}
+
+/// Parity bit of a USART frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Parity {
+    None,
+    Even,
+    Odd,
+}
+
+/// Number of stop bits of a USART frame
+///
+/// The receiver only checks the first stop bit, so this only affects transmitted frames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopBits {
+    One,
+    Two,
+}
+
+/// Format of a USART frame
+///
+/// The default is 8 data bits, no parity and 1 stop bit (8N1).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameFormat {
+    /// Number of data bits
+    pub data_bits: DataBits,
+    /// Parity bit, if any
+    pub parity: Parity,
+    /// Number of stop bits
+    pub stop_bits: StopBits,
+}
+
+impl FrameFormat {
+    /// Create a frame format from its parts.
+    pub const fn new(data_bits: DataBits, parity: Parity, stop_bits: StopBits) -> Self {
+        Self {
+            data_bits,
+            parity,
+            stop_bits,
+        }
+    }
+}
+
+impl Default for FrameFormat {
+    fn default() -> Self {
+        Self::new(DataBits::Eight, Parity::None, StopBits::One)
+    }
+}
+
+/// Configuration of a USART peripheral
+///
+/// A plain [`Baudrate`] converts into settings with the default 8N1 [`FrameFormat`], so it can be
+/// passed wherever `UsartSettings` are expected.
+///
+/// # Example
+/// ```
+/// let settings = UsartSettings::new(
+///     9600.into_baudrate(),
+///     FrameFormat::new(DataBits::Seven, Parity::Even, StopBits::One),
+/// );
+/// let mut serial = Usart::new(dp.USART0, pins.d0, pins.d1.into_output(), settings);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct UsartSettings<CLOCK> {
+    /// Baudrate of the peripheral
+    pub baudrate: Baudrate<CLOCK>,
+    /// Format of the transmitted and received frames
+    pub format: FrameFormat,
+}
+
+impl<CLOCK> UsartSettings<CLOCK> {
+    /// Combine a baudrate and a frame format.
+    pub fn new(baudrate: Baudrate<CLOCK>, format: FrameFormat) -> Self {
+        Self { baudrate, format }
+    }
+}
+
+impl<CLOCK> From<Baudrate<CLOCK>> for UsartSettings<CLOCK> {
+    fn from(baudrate: Baudrate<CLOCK>) -> Self {
+        Self::new(baudrate, FrameFormat::default())
+    }
+}
+
+/// Errors which can occur while receiving a frame
+///
+/// The frame which caused a [`Error::FrameFormat`] or [`Error::Parity`] error is discarded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The stop bit of the received frame was not set (`FE#` flag).
+    ///
+    /// This usually means that the baudrate or frame format of the sender does not match.
+    FrameFormat,
+    /// The parity bit of the received frame did not match its data (`UPE#` flag).
+    Parity,
+    /// One or more frames were lost because the receive buffer was full (`DOR#` flag).
+    ///
+    /// The byte which carried the flag is still valid, so it is returned first and the overrun
+    /// is reported by the following read.
+    Overrun,
+}
+
+/// Overrun which is reported by the read after the byte that carried the `DOR#` flag.
+///
+/// Used by the [`UsartOps::raw_read()`] implementations, which have no other place to keep it.
+#[doc(hidden)]
+pub struct PendingOverrun(core::sync::atomic::AtomicBool);
+
+impl PendingOverrun {
+    pub const fn new() -> Self {
+        Self(core::sync::atomic::AtomicBool::new(false))
+    }
+
+    pub fn set(&self) {
+        self.0.store(true, core::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Check whether an overrun is pending and clear it.
+    ///
+    /// This is not atomic, which is fine as `raw_read()` has exclusive access to its USART.
+    pub fn take(&self) -> bool {
+        let pending = self.0.load(core::sync::atomic::Ordering::Relaxed);
+        self.0.store(false, core::sync::atomic::Ordering::Relaxed);
+        pending
+    }
+}
+
+impl Default for PendingOverrun {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl embedded_hal_nb::serial::Error for Error {
+    fn kind(&self) -> embedded_hal_nb::serial::ErrorKind {
+        match self {
+            Error::FrameFormat => embedded_hal_nb::serial::ErrorKind::FrameFormat,
+            Error::Parity => embedded_hal_nb::serial::ErrorKind::Parity,
+            Error::Overrun => embedded_hal_nb::serial::ErrorKind::Overrun,
+        }
+    }
+}
+
+impl embedded_io::Error for Error {
+    fn kind(&self) -> embedded_io::ErrorKind {
+        embedded_io::ErrorKind::InvalidData
+    }
+}
+
+/// Errors which can occur during [`Usart::autobaud()`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AutobaudError {
+    /// The edges on the RX line were not evenly spaced, so the received character was not the
+    /// `0x55` sync character.
+    InvalidSyncCharacter,
+    /// The measured baudrate cannot be generated at this `CLOCK` speed.
+    RateOutOfRange,
+    /// The sync character was not received completely within the timeout.
+    Timeout,
+}
+
+/// Result of [`Usart::autobaud()`]
+#[derive(Debug, Clone, Copy)]
+pub struct DetectedBaudrate<CLOCK> {
+    /// The baudrate which the peripheral was configured for.
+    ///
+    /// This is the closest baudrate to the measured one which can be generated at this `CLOCK`
+    /// speed.
+    pub baudrate: Baudrate<CLOCK>,
+    /// Deviation of `baudrate` from the measured baudrate of the sender, in hundredths of a
+    /// percent.
+    ///
+    /// A positive value means that the peripheral is faster than the sender.  Values beyond about
+    /// ±200 (±2%) will lead to unreliable communication.
+    pub error_percent_x100: i16,
+}
+
 /// Events/Interrupts for USART peripherals
 #[repr(u8)]
 pub enum Event {
@@ -169,10 +434,10 @@ pub enum Event {
 /// intermediate abstraction ontop of which the [`Usart`] API is built.  **Prefer using the
 /// [`Usart`] API instead of this trait.**
 pub trait UsartOps<H, RX, TX> {
-    /// Enable & initialize this USART peripheral to the given baudrate.
+    /// Enable & initialize this USART peripheral to the given baudrate and frame format.
     ///
     /// **Warning**: This is a low-level method and should not be called directly from user code.
-    fn raw_init<CLOCK>(&mut self, baudrate: Baudrate<CLOCK>);
+    fn raw_init<CLOCK>(&mut self, settings: UsartSettings<CLOCK>);
     /// Disable this USART peripheral such that the pins can be used for other purposes again.
     ///
     /// **Warning**: This is a low-level method and should not be called directly from user code.
@@ -195,10 +460,47 @@ pub trait UsartOps<H, RX, TX> {
     /// Read a byte from the RX buffer.
     ///
     /// This operation must be non-blocking and return [`nb::Error::WouldBlock`] if no incoming
-    /// byte is available.
+    /// byte is available.  If the frame was received with a frame format or parity error, the
+    /// byte is discarded and the error is returned instead.  An overrun does not affect the byte
+    /// which carries it, so the byte is returned and [`Error::Overrun`] is reported by the next
+    /// call.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_read(&mut self) -> nb::Result<u8, Error>;
+    /// Check whether a received byte is waiting in the RX buffer, without reading it.
     ///
     /// **Warning**: This is a low-level method and should not be called directly from user code.
-    fn raw_read(&mut self) -> nb::Result<u8, core::convert::Infallible>;
+    fn raw_read_ready(&mut self) -> bool;
+
+    /// Write a 9-bit frame to the TX buffer.
+    ///
+    /// This behaves like [`UsartOps::raw_write()`] but also sets the ninth data bit.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_write_9bit(&mut self, data: u16) -> nb::Result<(), core::convert::Infallible>;
+    /// Read a 9-bit frame from the RX buffer.
+    ///
+    /// This behaves like [`UsartOps::raw_read()`] but also returns the ninth data bit.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_read_9bit(&mut self) -> nb::Result<u16, Error>;
+
+    /// Enable/Disable multi-processor communication mode.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_multiprocessor_mode(&mut self, state: bool);
+
+    /// Check whether the transmit complete flag (`TXC#`) is set.
+    ///
+    /// The flag is set once the last frame has been shifted out entirely, including its stop bits,
+    /// and no more data is waiting in the data register.
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_is_tx_complete(&mut self) -> bool;
+    /// Clear the transmit complete flag (`TXC#`).
+    ///
+    /// **Warning**: This is a low-level method and should not be called directly from user code.
+    fn raw_clear_tx_complete(&mut self);
 
     /// Enable/Disable a certain interrupt.
     ///
@@ -223,7 +525,7 @@ pub trait UsartOps<H, RX, TX> {
 /// ufmt::uwriteln!(&mut serial, "Hello from Arduino!\r").unwrap_infallible();
 ///
 /// loop {
-///     let b = nb::block!(serial.read()).unwrap_infallible();
+///     let b = nb::block!(serial.read()).unwrap();
 ///     ufmt::uwriteln!(&mut serial, "Got {}!\r", b).unwrap_infallible();
 /// }
 /// ```
@@ -231,6 +533,8 @@ pub struct Usart<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> {
     p: USART,
     rx: RX,
     tx: TX,
+    /// Error which `embedded_io::Read` reports on its next call.
+    io_error: Option<Error>,
     _clock: marker::PhantomData<CLOCK>,
     _h: marker::PhantomData<H>,
 }
@@ -252,22 +556,226 @@ where
     ///
     /// Note that the RX and TX pins are hardwired for each USART peripheral and you *must* pass
     /// the correct ones.  This is enforced at compile time.
+    ///
+    /// `settings` is either a [`Baudrate`], which selects the 8N1 frame format, or
+    /// [`UsartSettings`] with a different [`FrameFormat`].
     pub fn new<IMODE: port::mode::InputMode>(
         p: USART,
         rx: port::Pin<port::mode::Input<IMODE>, RXPIN>,
         tx: port::Pin<port::mode::Output, TXPIN>,
-        baudrate: Baudrate<CLOCK>,
+        settings: impl Into<UsartSettings<CLOCK>>,
     ) -> Self {
         let mut usart = Self {
             p,
             rx: rx.forget_imode(),
             tx,
+            io_error: None,
             _clock: marker::PhantomData,
             _h: marker::PhantomData,
         };
-        usart.p.raw_init(baudrate);
+        usart.p.raw_init(settings.into());
         usart
     }
+
+    /// Detect the baudrate of the sender and reconfigure the peripheral for it.
+    ///
+    /// This blocks until the sender transmits the sync character `0x55`.  Its falling edges on the
+    /// RX line are timed with the given timer, which runs without prescaler during the
+    /// measurement and is stopped afterwards.  From the time between the first and the last
+    /// falling edge (8 bit times), the closest `UBRR#`/`U2X#` setting is chosen and the peripheral
+    /// is reinitialized with it and the given frame format.  The sync character itself is
+    /// discarded.
+    ///
+    /// The timer's overflow interrupt is disabled, so the timer must not be used for anything
+    /// else in the meantime.
+    ///
+    /// With a `timeout` (in CPU cycles, measured with the timer), [`AutobaudError::Timeout`] is
+    /// returned if the sync character was not received completely in time.  Without one, this
+    /// waits forever, e.g. on a floating RX line.
+    ///
+    /// # Example
+    /// (for Arduino Uno)
+    /// ```
+    /// let mut serial = arduino_hal::default_serial!(dp, pins, 57600);
+    ///
+    /// // Give up after one second.
+    /// let detected = serial
+    ///     .autobaud(&mut dp.TC1, FrameFormat::default(), Some(16_000_000))
+    ///     .unwrap();
+    /// ufmt::uwriteln!(&mut serial, "UBRR: {}\r", detected.baudrate.ubrr).unwrap_infallible();
+    /// ```
+    pub fn autobaud<TC>(
+        &mut self,
+        timer: &mut TC,
+        format: FrameFormat,
+        timeout: Option<u32>,
+    ) -> Result<DetectedBaudrate<CLOCK>, AutobaudError>
+    where
+        TC: crate::timer::TimerOps<H>,
+        TC::Counter: Into<u32> + TryFrom<u32>,
+        CLOCK: crate::clock::Clock,
+    {
+        let mut stopwatch = Stopwatch::start(timer);
+        let edges = self.sync_edges(&mut stopwatch, timeout);
+        stopwatch.stop();
+        let edges = edges?;
+
+        // Each of the four intervals must be within ±25% of a quarter of the total.
+        let ticks = edges[4].wrapping_sub(edges[0]);
+        let tolerance = ticks as u64 * 3 / 4..=ticks as u64 * 5 / 4;
+        for pair in edges.windows(2) {
+            let interval = pair[1].wrapping_sub(pair[0]) as u64 * 4;
+            if !tolerance.contains(&interval) {
+                return Err(AutobaudError::InvalidSyncCharacter);
+            }
+        }
+
+        // Clock cycles per bit are `ticks / 8`, the peripheral divides by `8 * (UBRR# + 1)` with
+        // `U2X#` and by `16 * (UBRR# + 1)` without.  Both candidates are compared and normal speed
+        // is preferred as its receiver samples each bit more often.
+        let candidate = |u2x: bool| {
+            let cycles = if u2x { 8 } else { 16 };
+            let divisor = 8 * cycles;
+            let n = (ticks + divisor / 2) / divisor;
+            if n == 0 || n > 4096 {
+                return None;
+            }
+
+            let error = (ticks as u64 * 10000 + divisor as u64 * n as u64 / 2)
+                / (divisor as u64 * n as u64);
+            Some((Baudrate::with_exact(u2x, (n - 1) as u16), error as i32 - 10000))
+        };
+        let (baudrate, error) = match (candidate(false), candidate(true)) {
+            (Some(normal), Some(double)) if double.1.abs() < normal.1.abs() => double,
+            (Some(normal), _) => normal,
+            (None, Some(double)) => double,
+            (None, None) => return Err(AutobaudError::RateOutOfRange),
+        };
+
+        // `Baudrate` is only `Copy` if `CLOCK` is.
+        self.reconfigure(UsartSettings::new(
+            Baudrate::with_exact(baudrate.u2x, baudrate.ubrr),
+            format,
+        ));
+        while self.p.raw_read_ready() {
+            let _ = self.p.raw_read();
+        }
+
+        Ok(DetectedBaudrate {
+            baudrate,
+            error_percent_x100: error as i16,
+        })
+    }
+
+    /// Timestamps of the five falling edges of the sync character, for [`Usart::autobaud()`].
+    fn sync_edges<TC>(
+        &mut self,
+        stopwatch: &mut Stopwatch<'_, H, TC>,
+        timeout: Option<u32>,
+    ) -> Result<[u32; 5], AutobaudError>
+    where
+        TC: crate::timer::TimerOps<H>,
+        TC::Counter: Into<u32> + TryFrom<u32>,
+    {
+        let rx = &self.rx;
+
+        // Wait for the line to be idle before looking for the start bit.
+        stopwatch.wait_while(timeout, || rx.is_low())?;
+
+        // 0x55 is sent LSB first, so the start bit and every second data bit after it are low.
+        // This gives five falling edges which are two bit times apart each.
+        let mut edges = [0u32; 5];
+        for (i, edge) in edges.iter_mut().enumerate() {
+            if i > 0 {
+                stopwatch.wait_while(timeout, || rx.is_low())?;
+            }
+            *edge = stopwatch.wait_while(timeout, || rx.is_high())?;
+        }
+
+        // Let the rest of the frame pass before the receiver is reconfigured.
+        stopwatch.wait_while(timeout, || rx.is_low())?;
+
+        Ok(edges)
+    }
+}
+
+/// Free-running timestamps from a timer, extended beyond the counter width by polling the
+/// overflow flag.
+///
+/// [`Stopwatch::now()`] must be called at least once per counter period.
+struct Stopwatch<'a, H, TC: crate::timer::TimerOps<H>> {
+    timer: &'a mut TC,
+    period: u32,
+    overflows: u32,
+    _h: marker::PhantomData<H>,
+}
+
+impl<'a, H, TC> Stopwatch<'a, H, TC>
+where
+    TC: crate::timer::TimerOps<H>,
+    TC::Counter: Into<u32> + TryFrom<u32>,
+{
+    fn start(timer: &'a mut TC) -> Self {
+        use crate::timer::{Event, Mode, Prescaler};
+
+        timer.raw_stop();
+        timer.raw_interrupt(Event::Overflow, false);
+        timer.raw_set_mode(Mode::Normal);
+        timer.raw_write_counter(TC::Counter::default());
+        timer.raw_clear_pending(Event::Overflow);
+        timer.raw_start(Prescaler::Direct);
+
+        let period = if TC::Counter::try_from(0xffff).is_ok() {
+            0x1_0000
+        } else {
+            0x100
+        };
+
+        Self {
+            timer,
+            period,
+            overflows: 0,
+            _h: marker::PhantomData,
+        }
+    }
+
+    fn now(&mut self) -> u32 {
+        use crate::timer::Event;
+
+        let mut counter = self.timer.raw_read_counter();
+        if self.timer.raw_is_pending(Event::Overflow) {
+            // The counter may have overflowed just after it was read, so read it again.
+            counter = self.timer.raw_read_counter();
+            self.timer.raw_clear_pending(Event::Overflow);
+            self.overflows = self.overflows.wrapping_add(1);
+        }
+        self.overflows
+            .wrapping_mul(self.period)
+            .wrapping_add(counter.into())
+    }
+
+    /// Poll `busy` until it returns `false` and return the timestamp from just before that.
+    ///
+    /// Gives up once `deadline` ticks have passed since the stopwatch was started.
+    fn wait_while(
+        &mut self,
+        deadline: Option<u32>,
+        mut busy: impl FnMut() -> bool,
+    ) -> Result<u32, AutobaudError> {
+        loop {
+            let now = self.now();
+            if !busy() {
+                return Ok(now);
+            }
+            if deadline.is_some_and(|deadline| now >= deadline) {
+                return Err(AutobaudError::Timeout);
+            }
+        }
+    }
+
+    fn stop(self) {
+        self.timer.raw_stop();
+    }
 }
 
 impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> Usart<H, USART, RX, TX, CLOCK> {
@@ -292,9 +800,45 @@ impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> Usart<H, USART, RX, TX, CLOCK
 
     /// Receive a byte.
     ///
-    /// This method will block until a byte could be received.
-    pub fn read_byte(&mut self) -> u8 {
-        nb::block!(self.p.raw_read()).unwrap_infallible()
+    /// This method will block until a byte could be received.  Frames which were received with
+    /// an error are reported as [`Error`].
+    pub fn read_byte(&mut self) -> Result<u8, Error> {
+        nb::block!(self.p.raw_read())
+    }
+
+    /// Transmit a 9-bit frame.
+    ///
+    /// Only the lower 9 bits of `data` are sent.  The peripheral must be configured for
+    /// [`DataBits::Nine`].  Like [`Usart::write_byte()`], this blocks until the frame has been
+    /// enqueued for transmission.
+    pub fn write_9bit(&mut self, data: u16) {
+        nb::block!(self.p.raw_write_9bit(data)).unwrap_infallible()
+    }
+
+    /// Receive a 9-bit frame.
+    ///
+    /// The peripheral must be configured for [`DataBits::Nine`].  This method will block until a
+    /// frame could be received.
+    pub fn read_9bit(&mut self) -> Result<u16, Error> {
+        nb::block!(self.p.raw_read_9bit())
+    }
+
+    /// Enable/Disable multi-processor communication mode.
+    ///
+    /// While enabled, the receiver ignores all frames whose ninth bit is cleared, so only address
+    /// frames (ninth bit set) are received.  A node disables this mode after it was addressed to
+    /// receive the following data frames, and enables it again afterwards.
+    pub fn set_multiprocessor_mode(&mut self, state: bool) {
+        self.p.raw_multiprocessor_mode(state);
+    }
+
+    /// Change the baudrate and frame format of this peripheral.
+    ///
+    /// Any ongoing transmission is finished first.  All interrupts are disabled by this call and
+    /// need to be enabled again using [`Usart::listen()`].
+    pub fn reconfigure(&mut self, settings: impl Into<UsartSettings<CLOCK>>) {
+        self.flush();
+        self.p.raw_init(settings.into());
     }
 
     /// Enable the interrupt for [`Event`].
@@ -320,6 +864,7 @@ impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> Usart<H, USART, RX, TX, CLOCK
             UsartReader {
                 p: unsafe { core::ptr::read(&self.p) },
                 rx: self.rx,
+                io_error: self.io_error,
                 _tx: marker::PhantomData,
                 _clock: marker::PhantomData,
                 _h: marker::PhantomData,
@@ -363,13 +908,39 @@ impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_hal_v0::serial::Writ
 impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_hal_v0::serial::Read<u8>
     for Usart<H, USART, RX, TX, CLOCK>
 {
-    type Error = core::convert::Infallible;
+    type Error = Error;
+
+    fn read(&mut self) -> nb::Result<u8, Self::Error> {
+        self.p.raw_read()
+    }
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_hal_nb::serial::ErrorType
+    for Usart<H, USART, RX, TX, CLOCK>
+{
+    type Error = Error;
+}
 
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_hal_nb::serial::Read<u8>
+    for Usart<H, USART, RX, TX, CLOCK>
+{
     fn read(&mut self) -> nb::Result<u8, Self::Error> {
         self.p.raw_read()
     }
 }
 
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_hal_nb::serial::Write<u8>
+    for Usart<H, USART, RX, TX, CLOCK>
+{
+    fn write(&mut self, byte: u8) -> nb::Result<(), Self::Error> {
+        self.p.raw_write(byte).map_err(|e| e.map(|e| match e {}))
+    }
+
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        self.p.raw_flush().map_err(|e| e.map(|e| match e {}))
+    }
+}
+
 /// Writer half of a [`Usart`] peripheral.
 ///
 /// Created by calling [`Usart::split`].  Splitting a peripheral into reader and writer allows
@@ -394,6 +965,7 @@ pub struct UsartWriter<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> {
 pub struct UsartReader<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> {
     p: USART,
     rx: RX,
+    io_error: Option<Error>,
     _tx: marker::PhantomData<TX>,
     _clock: marker::PhantomData<CLOCK>,
     _h: marker::PhantomData<H>,
@@ -409,6 +981,7 @@ impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> UsartWriter<H, USART, RX, TX,
             p: self.p,
             rx: other.rx,
             tx: self.tx,
+            io_error: other.io_error,
             _clock: marker::PhantomData,
             _h: marker::PhantomData,
         }
@@ -425,6 +998,7 @@ impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> UsartReader<H, USART, RX, TX,
             p: self.p,
             rx: self.rx,
             tx: other.tx,
+            io_error: self.io_error,
             _clock: marker::PhantomData,
             _h: marker::PhantomData,
         }
@@ -461,60 +1035,1469 @@ impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_hal_v0::serial::Writ
 impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_hal_v0::serial::Read<u8>
     for UsartReader<H, USART, RX, TX, CLOCK>
 {
-    type Error = core::convert::Infallible;
+    type Error = Error;
 
     fn read(&mut self) -> nb::Result<u8, Self::Error> {
         self.p.raw_read()
     }
 }
 
-#[macro_export]
-macro_rules! impl_usart_traditional {
-    (
-        hal: $HAL:ty,
-        peripheral: $USART:ty,
-        register_suffix: $n:expr,
-        rx: $rxpin:ty,
-        tx: $txpin:ty,
-    ) => {
-        $crate::paste::paste! {
-            impl $crate::usart::UsartOps<
-                $HAL,
-                $crate::port::Pin<$crate::port::mode::Input, $rxpin>,
-                $crate::port::Pin<$crate::port::mode::Output, $txpin>,
-            > for $USART {
-                fn raw_init<CLOCK>(&mut self, baudrate: $crate::usart::Baudrate<CLOCK>) {
-                    self.[<ubrr $n>].write(|w| unsafe { w.bits(baudrate.ubrr) });
-                    self.[<ucsr $n a>].write(|w| w.[<u2x $n>]().bit(baudrate.u2x));
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_hal_nb::serial::ErrorType
+    for UsartReader<H, USART, RX, TX, CLOCK>
+{
+    type Error = Error;
+}
 
-                    // Enable receiver and transmitter but leave interrupts disabled.
-                    self.[<ucsr $n b>].write(|w| w
-                        .[<txen $n>]().set_bit()
-                        .[<rxen $n>]().set_bit()
-                    );
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_hal_nb::serial::Read<u8>
+    for UsartReader<H, USART, RX, TX, CLOCK>
+{
+    fn read(&mut self) -> nb::Result<u8, Self::Error> {
+        self.p.raw_read()
+    }
+}
 
-                    // Set frame format to 8n1 for now.  At some point, this should be made
-                    // configurable, similar to what is done in other HALs.
-                    self.[<ucsr $n c>].write(|w| w
-                        .[<umsel $n>]().usart_async()
-                        .[<ucsz $n>]().chr8()
-                        .[<usbs $n>]().stop1()
-                        .[<upm $n>]().disabled()
-                    );
-                }
+/// Read as many bytes as are available, blocking only until the first one was received.
+///
+/// An error is only returned if no byte was read yet.  Otherwise, reading stops at the error,
+/// which is kept in `pending` and reported by the next call.
+fn io_read<H, USART: UsartOps<H, RX, TX>, RX, TX>(
+    p: &mut USART,
+    pending: &mut Option<Error>,
+    buf: &mut [u8],
+) -> Result<usize, Error> {
+    if let Some(e) = pending.take() {
+        return Err(e);
+    }
 
-                fn raw_deinit(&mut self) {
-                    // Wait for any ongoing transfer to finish.
-                    $crate::nb::block!(self.raw_flush()).ok();
-                    self.[<ucsr $n b>].reset();
-                }
+    let mut count = 0;
+    for b in buf.iter_mut() {
+        let result = if count == 0 {
+            nb::block!(p.raw_read())
+        } else {
+            match p.raw_read() {
+                Err(nb::Error::WouldBlock) => break,
+                Err(nb::Error::Other(e)) => Err(e),
+                Ok(byte) => Ok(byte),
+            }
+        };
+        match result {
+            Ok(byte) => *b = byte,
+            Err(e) if count == 0 => return Err(e),
+            Err(e) => {
+                *pending = Some(e);
+                break;
+            }
+        }
+        count += 1;
+    }
+    Ok(count)
+}
 
-                fn raw_flush(&mut self) -> $crate::nb::Result<(), core::convert::Infallible> {
-                    if self.[<ucsr $n a>].read().[<udre $n>]().bit_is_clear() {
-                        Err($crate::nb::Error::WouldBlock)
-                    } else {
-                        Ok(())
-                    }
+/// Write as many bytes as fit, blocking only until the first one was enqueued.
+fn io_write<H, USART: UsartOps<H, RX, TX>, RX, TX>(p: &mut USART, buf: &[u8]) -> usize {
+    let mut count = 0;
+    for b in buf.iter() {
+        if count == 0 {
+            nb::block!(p.raw_write(*b)).unwrap_infallible();
+        } else if p.raw_write(*b).is_err() {
+            break;
+        }
+        count += 1;
+    }
+    count
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_io::ErrorType
+    for Usart<H, USART, RX, TX, CLOCK>
+{
+    type Error = Error;
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_io::Read
+    for Usart<H, USART, RX, TX, CLOCK>
+{
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        io_read(&mut self.p, &mut self.io_error, buf)
+    }
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_io::ReadReady
+    for Usart<H, USART, RX, TX, CLOCK>
+{
+    fn read_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.io_error.is_some() || self.p.raw_read_ready())
+    }
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_io::Write
+    for Usart<H, USART, RX, TX, CLOCK>
+{
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        Ok(io_write(&mut self.p, buf))
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Usart::flush(self);
+        Ok(())
+    }
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_io::WriteReady
+    for Usart<H, USART, RX, TX, CLOCK>
+{
+    fn write_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.p.raw_flush().is_ok())
+    }
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_io::ErrorType
+    for UsartReader<H, USART, RX, TX, CLOCK>
+{
+    type Error = Error;
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_io::Read
+    for UsartReader<H, USART, RX, TX, CLOCK>
+{
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        io_read(&mut self.p, &mut self.io_error, buf)
+    }
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_io::ReadReady
+    for UsartReader<H, USART, RX, TX, CLOCK>
+{
+    fn read_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.io_error.is_some() || self.p.raw_read_ready())
+    }
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_io::ErrorType
+    for UsartWriter<H, USART, RX, TX, CLOCK>
+{
+    type Error = core::convert::Infallible;
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_io::Write
+    for UsartWriter<H, USART, RX, TX, CLOCK>
+{
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        Ok(io_write(&mut self.p, buf))
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        nb::block!(self.p.raw_flush())
+    }
+}
+
+impl<H, USART: UsartOps<H, RX, TX>, RX, TX, CLOCK> embedded_io::WriteReady
+    for UsartWriter<H, USART, RX, TX, CLOCK>
+{
+    fn write_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.p.raw_flush().is_ok())
+    }
+}
+
+/// RS-485 half-duplex driver with automatic driver-enable pin
+///
+/// Wraps a [`Usart`] connected to an RS-485 transceiver (e.g. MAX485) and controls its
+/// driver-enable (`DE`, often tied to `/RE`) pin.  The pin is asserted before a byte is
+/// transmitted and only released once the transmit complete (`TXC#`) condition is reached.  At
+/// this point the last stop bit has left the shift register, so the bus is never released early.
+///
+/// In the default blocking mode, the pin is released by [`Rs485::flush()`] and before reading.  To
+/// release it as soon as possible without polling, create an [`Rs485DriverEnable`] and its
+/// `USART#_TX` interrupt handler with the `impl_rs485!` macro of your MCU HAL and pass it to
+/// [`Rs485::listen_tx_complete()`].
+///
+/// # Example
+/// (for Arduino Uno)
+/// ```
+/// let dp = arduino_hal::Peripherals::take().unwrap();
+/// let pins = arduino_hal::pins!(dp);
+/// let serial = arduino_hal::default_serial!(dp, pins, 9600);
+///
+/// let mut bus = Rs485::new(serial, pins.d2.into_output());
+///
+/// ufmt::uwrite!(&mut bus, "ping").unwrap_infallible();
+/// // Waits for the last stop bit and releases the bus before receiving the answer.
+/// let answer = bus.read_byte().unwrap();
+/// ```
+pub struct Rs485<H, USART: UsartOps<H, RX, TX> + 'static, RX, TX, CLOCK, DE: 'static> {
+    usart: Usart<H, USART, RX, TX, CLOCK>,
+    de: DriverEnable<USART, DE>,
+}
+
+/// Driver-enable pin of an [`Rs485`], either owned or handed over to the interrupt handler.
+enum DriverEnable<USART: 'static, DE: 'static> {
+    Owned(port::Pin<port::mode::Output, DE>),
+    Shared(&'static Rs485DriverEnable<USART, DE>),
+}
+
+impl<USART, DE: port::PinOps> DriverEnable<USART, DE> {
+    fn set_high(&mut self) {
+        match self {
+            Self::Owned(pin) => pin.set_high(),
+            Self::Shared(shared) => shared.with(|pin| {
+                if let Some(pin) = pin {
+                    pin.set_high();
+                }
+            }),
+        }
+    }
+
+    fn set_low(&mut self) {
+        match self {
+            Self::Owned(pin) => pin.set_low(),
+            Self::Shared(shared) => shared.on_tx_complete(),
+        }
+    }
+
+    fn is_set_high(&self) -> bool {
+        match self {
+            Self::Owned(pin) => pin.is_set_high(),
+            Self::Shared(shared) => {
+                shared.with(|pin| pin.as_ref().is_some_and(|pin| pin.is_set_high()))
+            }
+        }
+    }
+
+    fn is_shared(&self) -> bool {
+        matches!(self, Self::Shared(_))
+    }
+}
+
+impl<H, USART, RX, TX, CLOCK, DE: port::PinOps> Rs485<H, USART, RX, TX, CLOCK, DE>
+where
+    USART: UsartOps<H, RX, TX> + 'static,
+{
+    /// Wrap a USART and the driver-enable pin of its transceiver.
+    ///
+    /// The pin is driven low, so the bus is released initially.
+    pub fn new(
+        usart: Usart<H, USART, RX, TX, CLOCK>,
+        mut de: port::Pin<port::mode::Output, DE>,
+    ) -> Self {
+        de.set_low();
+        Self {
+            usart,
+            de: DriverEnable::Owned(de),
+        }
+    }
+
+    /// Release the driver-enable pin from the `USART#_TX` interrupt instead of by polling.
+    ///
+    /// Any transmission is finished first.  The pin is then handed over to `driver_enable`, whose
+    /// interrupt handler releases it, and the [`Event::TxComplete`] interrupt is enabled.
+    pub fn listen_tx_complete(&mut self, driver_enable: &'static Rs485DriverEnable<USART, DE>) {
+        self.flush();
+        if let DriverEnable::Owned(pin) =
+            core::mem::replace(&mut self.de, DriverEnable::Shared(driver_enable))
+        {
+            driver_enable.with(|shared| *shared = Some(pin));
+        }
+        self.usart.p.raw_interrupt(Event::TxComplete, true);
+    }
+
+    /// Go back to releasing the driver-enable pin by polling.
+    ///
+    /// Waits for any transmission to finish and takes the pin back from the interrupt handler.
+    pub fn unlisten_tx_complete(&mut self) {
+        if let DriverEnable::Shared(shared) = self.de {
+            nb::block!(self.usart.p.raw_flush()).unwrap_infallible();
+            while self.de.is_set_high() {}
+            self.usart.p.raw_interrupt(Event::TxComplete, false);
+            if let Some(pin) = shared.with(Option::take) {
+                self.de = DriverEnable::Owned(pin);
+            }
+        }
+    }
+
+    /// Whether the driver-enable pin is currently asserted.
+    pub fn is_transmitting(&self) -> bool {
+        self.de.is_set_high()
+    }
+
+    /// Queue a byte for transmission, asserting the driver-enable pin first.
+    ///
+    /// Returns [`nb::Error::WouldBlock`] if the data register is not empty yet.
+    pub fn write(&mut self, byte: u8) -> nb::Result<(), core::convert::Infallible> {
+        self.usart.p.raw_flush()?;
+
+        // Clear the flag left over from the previous frame so it only signals the completion of
+        // this one.  A stale flag would otherwise release the pin while this byte is sent, either
+        // here or, if it was set while interrupts were disabled, from the interrupt handler.  The
+        // handler must not run between these steps.
+        avr_device::interrupt::free(|_| {
+            self.usart.p.raw_clear_tx_complete();
+            self.de.set_high();
+            self.usart.p.raw_write(byte)
+        })
+    }
+
+    /// Transmit a byte.
+    ///
+    /// This method will block until the byte has been enqueued for transmission but **not** until
+    /// it was entirely sent.
+    pub fn write_byte(&mut self, byte: u8) {
+        nb::block!(self.write(byte)).unwrap_infallible()
+    }
+
+    /// Block until all data has been transmitted and release the driver-enable pin.
+    ///
+    /// When listening for [`Event::TxComplete`], this only waits for the data register to drain
+    /// and the pin is released by the interrupt handler.
+    pub fn flush(&mut self) {
+        if self.de.is_shared() {
+            nb::block!(self.usart.p.raw_flush()).unwrap_infallible();
+        } else if self.de.is_set_high() {
+            while !self.usart.p.raw_is_tx_complete() {}
+            self.de.set_low();
+        }
+    }
+
+    /// Release the driver-enable pin if the transmission is complete.
+    ///
+    /// Returns whether the pin is released.
+    fn poll_tx_complete(&mut self) -> bool {
+        if self.de.is_set_high() {
+            if self.de.is_shared() || !self.usart.p.raw_is_tx_complete() {
+                return false;
+            }
+            self.de.set_low();
+        }
+        true
+    }
+
+    /// Receive a byte.
+    ///
+    /// Returns [`nb::Error::WouldBlock`] while a transmission is still in progress or no byte was
+    /// received yet.
+    pub fn read(&mut self) -> nb::Result<u8, Error> {
+        if !self.poll_tx_complete() {
+            return Err(nb::Error::WouldBlock);
+        }
+        self.usart.p.raw_read()
+    }
+
+    /// Finish any transmission and block until a byte could be received.
+    pub fn read_byte(&mut self) -> Result<u8, Error> {
+        nb::block!(self.read())
+    }
+
+    /// Finish any transmission and return the USART and the driver-enable pin.
+    pub fn release(
+        mut self,
+    ) -> (
+        Usart<H, USART, RX, TX, CLOCK>,
+        port::Pin<port::mode::Output, DE>,
+    ) {
+        self.unlisten_tx_complete();
+        self.flush();
+        match self.de {
+            DriverEnable::Owned(pin) => (self.usart, pin),
+            // `unlisten_tx_complete()` took the pin back from the interrupt handler.
+            DriverEnable::Shared(_) => unreachable!(),
+        }
+    }
+}
+
+impl<H, USART, RX, TX, CLOCK, DE: port::PinOps> ufmt::uWrite
+    for Rs485<H, USART, RX, TX, CLOCK, DE>
+where
+    USART: UsartOps<H, RX, TX> + 'static,
+{
+    type Error = core::convert::Infallible;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        for b in s.as_bytes().iter() {
+            self.write_byte(*b);
+        }
+        Ok(())
+    }
+}
+
+impl<H, USART, RX, TX, CLOCK, DE: port::PinOps> embedded_hal_v0::serial::Write<u8>
+    for Rs485<H, USART, RX, TX, CLOCK, DE>
+where
+    USART: UsartOps<H, RX, TX> + 'static,
+{
+    type Error = core::convert::Infallible;
+
+    fn write(&mut self, byte: u8) -> nb::Result<(), Self::Error> {
+        Rs485::write(self, byte)
+    }
+
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        if !self.poll_tx_complete() {
+            return Err(nb::Error::WouldBlock);
+        }
+        Ok(())
+    }
+}
+
+impl<H, USART, RX, TX, CLOCK, DE: port::PinOps> embedded_hal_v0::serial::Read<u8>
+    for Rs485<H, USART, RX, TX, CLOCK, DE>
+where
+    USART: UsartOps<H, RX, TX> + 'static,
+{
+    type Error = Error;
+
+    fn read(&mut self) -> nb::Result<u8, Self::Error> {
+        Rs485::read(self)
+    }
+}
+
+/// Driver-enable pin of an [`Rs485`] shared with its `USART#_TX` interrupt handler.
+///
+/// The pin must live in a `static` so the interrupt handler can access it.  Use the `impl_rs485!`
+/// macro of your MCU HAL to create it together with the handler.
+pub struct Rs485DriverEnable<USART, DE> {
+    pin: avr_device::interrupt::Mutex<RefCell<Option<port::Pin<port::mode::Output, DE>>>>,
+    _usart: marker::PhantomData<fn() -> USART>,
+}
+
+impl<USART, DE: port::PinOps> Rs485DriverEnable<USART, DE> {
+    /// Create the shared state without a pin.
+    pub const fn new() -> Self {
+        Self {
+            pin: avr_device::interrupt::Mutex::new(RefCell::new(None)),
+            _usart: marker::PhantomData,
+        }
+    }
+
+    fn with<R>(&self, f: impl FnOnce(&mut Option<port::Pin<port::mode::Output, DE>>) -> R) -> R {
+        avr_device::interrupt::free(|cs| f(&mut self.pin.borrow(cs).borrow_mut()))
+    }
+
+    /// Release the driver-enable pin.
+    ///
+    /// **Warning**: This is a low-level method which is called from the `USART#_TX` interrupt
+    /// handler and should not be called directly from user code.  The hardware clears the `TXC#`
+    /// flag when the handler is executed.
+    pub fn on_tx_complete(&self) {
+        self.with(|pin| {
+            if let Some(pin) = pin {
+                pin.set_low();
+            }
+        });
+    }
+}
+
+/// Fixed-capacity FIFO of bytes.
+struct RingBuffer<const N: usize> {
+    data: [u8; N],
+    head: usize,
+    len: usize,
+}
+
+impl<const N: usize> RingBuffer<N> {
+    const fn new() -> Self {
+        Self {
+            data: [0; N],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    fn push(&mut self, byte: u8) -> bool {
+        if self.len == N {
+            return false;
+        }
+        let tail = (self.head + self.len) % N;
+        self.data[tail] = byte;
+        self.len += 1;
+        true
+    }
+
+    fn pop(&mut self) -> Option<u8> {
+        if self.len == 0 {
+            return None;
+        }
+        let byte = self.data[self.head];
+        self.head = (self.head + 1) % N;
+        self.len -= 1;
+        Some(byte)
+    }
+
+    fn clear(&mut self) {
+        self.head = 0;
+        self.len = 0;
+    }
+}
+
+struct BufferState<const RX: usize, const TX: usize> {
+    rx: RingBuffer<RX>,
+    tx: RingBuffer<TX>,
+    overflow: bool,
+    /// Task waiting for received bytes.
+    rx_waker: Option<Waker>,
+    /// Task waiting for free space in the TX buffer or for the transmission to finish.
+    tx_waker: Option<Waker>,
+}
+
+/// RX and TX buffers shared between a [`BufferedUsart`] and its interrupt handlers.
+///
+/// The buffers must live in a `static` so the interrupt handlers can access them.  Use the
+/// `impl_buffered_usart!` macro of your MCU HAL to create them together with the handlers.
+///
+/// The buffers are tied to the `USART` peripheral whose interrupt handlers fill and drain them,
+/// so a [`BufferedUsart`] can only be created with the buffers of its own USART.
+pub struct UsartBuffers<USART, const RX: usize, const TX: usize> {
+    state: avr_device::interrupt::Mutex<RefCell<BufferState<RX, TX>>>,
+    _usart: marker::PhantomData<fn() -> USART>,
+}
+
+impl<USART, const RX: usize, const TX: usize> UsartBuffers<USART, RX, TX> {
+    /// Create empty buffers.
+    pub const fn new() -> Self {
+        Self {
+            _usart: marker::PhantomData,
+            state: avr_device::interrupt::Mutex::new(RefCell::new(BufferState {
+                rx: RingBuffer::new(),
+                tx: RingBuffer::new(),
+                overflow: false,
+                rx_waker: None,
+                tx_waker: None,
+            })),
+        }
+    }
+
+    fn with<R>(&self, f: impl FnOnce(&mut BufferState<RX, TX>) -> R) -> R {
+        avr_device::interrupt::free(|cs| f(&mut self.state.borrow(cs).borrow_mut()))
+    }
+
+    /// Move a received byte into the RX buffer.
+    ///
+    /// **Warning**: This is a low-level method which is called from the `USART#_RX` interrupt
+    /// handler and should not be called directly from user code.
+    pub fn on_rx_complete<H, RXPIN, TXPIN>(&self, p: &mut USART)
+    where
+        USART: UsartOps<H, RXPIN, TXPIN>,
+    {
+        match p.raw_read() {
+            Ok(byte) => self.with(|state| {
+                if !state.rx.push(byte) {
+                    state.overflow = true;
+                }
+                if let Some(waker) = state.rx_waker.take() {
+                    waker.wake();
+                }
+            }),
+            // The hardware buffer overflowed, so bytes were lost just like with a full RX buffer.
+            Err(nb::Error::Other(Error::Overrun)) => self.with(|state| state.overflow = true),
+            // Frames with other errors are dropped.
+            Err(_) => (),
+        }
+    }
+
+    /// Move the next byte from the TX buffer into the data register.
+    ///
+    /// **Warning**: This is a low-level method which is called from the `USART#_UDRE` interrupt
+    /// handler and should not be called directly from user code.
+    pub fn on_data_register_empty<H, RXPIN, TXPIN>(&self, p: &mut USART)
+    where
+        USART: UsartOps<H, RXPIN, TXPIN>,
+    {
+        self.with(|state| {
+            match state.tx.pop() {
+                // The data register is known to be empty here, so this never blocks.
+                Some(byte) => nb::block!(p.raw_write(byte)).unwrap_infallible(),
+                // Nothing left to send, the interrupt is enabled again by the next write.
+                None => p.raw_interrupt(Event::DataRegisterEmpty, false),
+            }
+            if let Some(waker) = state.tx_waker.take() {
+                waker.wake();
+            }
+        });
+    }
+}
+
+/// Interrupt-driven USART with RX and TX ring buffers
+///
+/// Incoming bytes are moved into the RX buffer by the `USART#_RX` interrupt, so they are not lost
+/// while the application is busy.  Outgoing bytes are queued in the TX buffer and sent from the
+/// `USART#_UDRE` interrupt.  The buffers and both interrupt handlers are created by the
+/// `impl_buffered_usart!` macro of your MCU HAL.
+///
+/// If the RX buffer is full, further incoming bytes are dropped and
+/// [`BufferedUsart::take_overflow()`] reports the loss.
+///
+/// Besides the blocking API, `BufferedUsart` implements the `embedded-io-async` traits so it can be
+/// used from an async executor.  Tasks waiting for data are woken from the interrupt handlers.
+///
+/// # Example
+/// (for Arduino Uno)
+/// ```
+/// arduino_hal::hal::impl_buffered_usart! {
+///     static SERIAL_BUFFERS: USART0<64, 32>;
+/// }
+///
+/// let dp = arduino_hal::Peripherals::take().unwrap();
+/// let pins = arduino_hal::pins!(dp);
+/// let serial = arduino_hal::default_serial!(dp, pins, 57600);
+///
+/// let mut serial = BufferedUsart::new(serial, &SERIAL_BUFFERS);
+/// unsafe { avr_device::interrupt::enable() };
+///
+/// loop {
+///     if let Ok(b) = serial.read() {
+///         serial.write_byte(b);
+///     }
+/// }
+/// ```
+pub struct BufferedUsart<
+    H,
+    USART: UsartOps<H, RX, TX> + 'static,
+    RX,
+    TX,
+    CLOCK,
+    const RXN: usize,
+    const TXN: usize,
+> {
+    usart: Usart<H, USART, RX, TX, CLOCK>,
+    buffers: &'static UsartBuffers<USART, RXN, TXN>,
+}
+
+impl<H, USART, RX, TX, CLOCK, const RXN: usize, const TXN: usize>
+    BufferedUsart<H, USART, RX, TX, CLOCK, RXN, TXN>
+where
+    USART: UsartOps<H, RX, TX> + 'static,
+{
+    /// Start buffering on the given USART.
+    ///
+    /// The buffers are cleared and the RX interrupt is enabled.  Interrupts must be enabled
+    /// globally for any data to be transferred.
+    pub fn new(
+        mut usart: Usart<H, USART, RX, TX, CLOCK>,
+        buffers: &'static UsartBuffers<USART, RXN, TXN>,
+    ) -> Self {
+        buffers.with(|state| {
+            state.rx.clear();
+            state.tx.clear();
+            state.overflow = false;
+            state.rx_waker = None;
+            state.tx_waker = None;
+        });
+        usart.listen(Event::RxComplete);
+
+        Self { usart, buffers }
+    }
+
+    /// Take a received byte from the RX buffer.
+    ///
+    /// Returns [`nb::Error::WouldBlock`] if the buffer is empty.
+    pub fn read(&mut self) -> nb::Result<u8, core::convert::Infallible> {
+        self.buffers.with(|state| state.rx.pop()).ok_or(nb::Error::WouldBlock)
+    }
+
+    /// Receive a byte.
+    ///
+    /// This method will block until a byte could be received.
+    pub fn read_byte(&mut self) -> u8 {
+        nb::block!(self.read()).unwrap_infallible()
+    }
+
+    /// Queue a byte for transmission.
+    ///
+    /// Returns [`nb::Error::WouldBlock`] if the TX buffer is full.
+    pub fn write(&mut self, byte: u8) -> nb::Result<(), core::convert::Infallible> {
+        let p = &mut self.usart.p;
+        self.buffers.with(|state| {
+            if state.tx.push(byte) {
+                p.raw_interrupt(Event::DataRegisterEmpty, true);
+                Ok(())
+            } else {
+                Err(nb::Error::WouldBlock)
+            }
+        })
+    }
+
+    /// Transmit a byte.
+    ///
+    /// This method will block until the byte could be queued in the TX buffer.
+    pub fn write_byte(&mut self, byte: u8) {
+        nb::block!(self.write(byte)).unwrap_infallible()
+    }
+
+    /// Block until all queued data has been transmitted.
+    pub fn flush(&mut self) {
+        while self.buffers.with(|state| state.tx.len) != 0 {}
+        nb::block!(self.usart.p.raw_flush()).unwrap_infallible()
+    }
+
+    /// Number of received bytes waiting in the RX buffer.
+    pub fn available(&self) -> usize {
+        self.buffers.with(|state| state.rx.len)
+    }
+
+    /// Whether received bytes were dropped because the RX buffer was full.
+    ///
+    /// The overflow flag is cleared by this call.
+    pub fn take_overflow(&mut self) -> bool {
+        self.buffers.with(|state| core::mem::replace(&mut state.overflow, false))
+    }
+
+    /// Send all queued data, stop buffering and return the underlying USART.
+    pub fn release(mut self) -> Usart<H, USART, RX, TX, CLOCK> {
+        self.flush();
+        self.usart.unlisten(Event::RxComplete);
+        self.usart.unlisten(Event::DataRegisterEmpty);
+        self.usart
+    }
+}
+
+impl<H, USART, RX, TX, CLOCK, const RXN: usize, const TXN: usize> ufmt::uWrite
+    for BufferedUsart<H, USART, RX, TX, CLOCK, RXN, TXN>
+where
+    USART: UsartOps<H, RX, TX> + 'static,
+{
+    type Error = core::convert::Infallible;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        for b in s.as_bytes().iter() {
+            self.write_byte(*b);
+        }
+        Ok(())
+    }
+}
+
+impl<H, USART, RX, TX, CLOCK, const RXN: usize, const TXN: usize>
+    embedded_hal_v0::serial::Write<u8> for BufferedUsart<H, USART, RX, TX, CLOCK, RXN, TXN>
+where
+    USART: UsartOps<H, RX, TX> + 'static,
+{
+    type Error = core::convert::Infallible;
+
+    fn write(&mut self, byte: u8) -> nb::Result<(), Self::Error> {
+        BufferedUsart::write(self, byte)
+    }
+
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        if self.buffers.with(|state| state.tx.len) != 0 {
+            return Err(nb::Error::WouldBlock);
+        }
+        self.usart.p.raw_flush()
+    }
+}
+
+impl<H, USART, RX, TX, CLOCK, const RXN: usize, const TXN: usize>
+    embedded_hal_v0::serial::Read<u8> for BufferedUsart<H, USART, RX, TX, CLOCK, RXN, TXN>
+where
+    USART: UsartOps<H, RX, TX> + 'static,
+{
+    type Error = core::convert::Infallible;
+
+    fn read(&mut self) -> nb::Result<u8, Self::Error> {
+        BufferedUsart::read(self)
+    }
+}
+
+impl<H, USART, RX, TX, CLOCK, const RXN: usize, const TXN: usize>
+    BufferedUsart<H, USART, RX, TX, CLOCK, RXN, TXN>
+where
+    USART: UsartOps<H, RX, TX> + 'static,
+{
+    /// Move as many bytes as available from the RX buffer into `buf`.
+    ///
+    /// Returns `None` if the RX buffer is empty, after registering `waker` (if any) to be woken
+    /// when a byte arrives.
+    fn pop_into(&mut self, buf: &mut [u8], waker: Option<&Waker>) -> Option<usize> {
+        self.buffers.with(|state| {
+            if state.rx.len == 0 {
+                state.rx_waker = waker.cloned();
+                return None;
+            }
+
+            let mut count = 0;
+            for b in buf.iter_mut() {
+                match state.rx.pop() {
+                    Some(byte) => *b = byte,
+                    None => break,
+                }
+                count += 1;
+            }
+            Some(count)
+        })
+    }
+
+    /// Queue as many bytes from `buf` as fit into the TX buffer.
+    ///
+    /// Returns `None` if the TX buffer is full, after registering `waker` (if any) to be woken
+    /// when space becomes available.
+    fn push_from(&mut self, buf: &[u8], waker: Option<&Waker>) -> Option<usize> {
+        let p = &mut self.usart.p;
+        self.buffers.with(|state| {
+            let count = buf.iter().take_while(|b| state.tx.push(**b)).count();
+            if count == 0 {
+                state.tx_waker = waker.cloned();
+                return None;
+            }
+
+            p.raw_interrupt(Event::DataRegisterEmpty, true);
+            Some(count)
+        })
+    }
+
+    /// Check whether all queued data has been transmitted.
+    ///
+    /// If not, `waker` (if any) is registered to be woken when more data was sent.
+    fn is_flushed(&mut self, waker: Option<&Waker>) -> bool {
+        let p = &mut self.usart.p;
+        self.buffers.with(|state| {
+            if state.tx.len == 0 && p.raw_flush().is_ok() {
+                return true;
+            }
+            state.tx_waker = waker.cloned();
+            false
+        })
+    }
+}
+
+impl<H, USART, RX, TX, CLOCK, const RXN: usize, const TXN: usize>
+    embedded_io::ErrorType for BufferedUsart<H, USART, RX, TX, CLOCK, RXN, TXN>
+where
+    USART: UsartOps<H, RX, TX> + 'static,
+{
+    type Error = core::convert::Infallible;
+}
+
+impl<H, USART, RX, TX, CLOCK, const RXN: usize, const TXN: usize>
+    embedded_io::Read for BufferedUsart<H, USART, RX, TX, CLOCK, RXN, TXN>
+where
+    USART: UsartOps<H, RX, TX> + 'static,
+{
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        loop {
+            if let Some(count) = self.pop_into(buf, None) {
+                return Ok(count);
+            }
+        }
+    }
+}
+
+impl<H, USART, RX, TX, CLOCK, const RXN: usize, const TXN: usize>
+    embedded_io::ReadReady for BufferedUsart<H, USART, RX, TX, CLOCK, RXN, TXN>
+where
+    USART: UsartOps<H, RX, TX> + 'static,
+{
+    fn read_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.available() != 0)
+    }
+}
+
+impl<H, USART, RX, TX, CLOCK, const RXN: usize, const TXN: usize>
+    embedded_io::Write for BufferedUsart<H, USART, RX, TX, CLOCK, RXN, TXN>
+where
+    USART: UsartOps<H, RX, TX> + 'static,
+{
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        loop {
+            if let Some(count) = self.push_from(buf, None) {
+                return Ok(count);
+            }
+        }
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        BufferedUsart::flush(self);
+        Ok(())
+    }
+}
+
+impl<H, USART, RX, TX, CLOCK, const RXN: usize, const TXN: usize>
+    embedded_io::WriteReady for BufferedUsart<H, USART, RX, TX, CLOCK, RXN, TXN>
+where
+    USART: UsartOps<H, RX, TX> + 'static,
+{
+    fn write_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.buffers.with(|state| state.tx.len) < TXN)
+    }
+}
+
+impl<H, USART, RX, TX, CLOCK, const RXN: usize, const TXN: usize>
+    embedded_io_async::Read for BufferedUsart<H, USART, RX, TX, CLOCK, RXN, TXN>
+where
+    USART: UsartOps<H, RX, TX> + 'static,
+{
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        core::future::poll_fn(|cx| match self.pop_into(buf, Some(cx.waker())) {
+            Some(count) => Poll::Ready(Ok(count)),
+            None => Poll::Pending,
+        })
+        .await
+    }
+}
+
+impl<H, USART, RX, TX, CLOCK, const RXN: usize, const TXN: usize>
+    embedded_io_async::Write for BufferedUsart<H, USART, RX, TX, CLOCK, RXN, TXN>
+where
+    USART: UsartOps<H, RX, TX> + 'static,
+{
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        core::future::poll_fn(|cx| match self.push_from(buf, Some(cx.waker())) {
+            Some(count) => Poll::Ready(Ok(count)),
+            None => Poll::Pending,
+        })
+        .await
+    }
+
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        core::future::poll_fn(|cx| match self.is_flushed(Some(cx.waker())) {
+            true => Poll::Ready(Ok(())),
+            false => Poll::Pending,
+        })
+        .await
+    }
+}
+
+/// Cycles spent per bit of a [`SoftSerial`] frame outside of the delay loop (approximate).
+const SOFT_SERIAL_BIT_OVERHEAD: u16 = 12;
+
+/// Bit-banged serial port on arbitrary pins
+///
+/// This is meant for MCUs without a USART, like most ATtiny, where it is mostly useful for debug
+/// output.  Frames are always 8N1 (8 data bits, no parity, one stop bit).
+///
+/// Transmitting is cycle-accurate using the [`delay`][crate::delay] busy loop.  Interrupts are
+/// disabled while a frame is sent, so other interrupt handlers are delayed by up to one frame
+/// time.  The achievable baudrate depends on the CPU clock; at 8 MHz, up to 57600 baud work
+/// reliably.
+///
+/// Receiving works in one of two ways:
+///
+/// - By default, [`SoftSerial::read()`] polls the RX pin and receives a frame in place when it
+///   sees a start bit.  This only works if it is called in a tight loop (e.g. by `nb::block!()`)
+///   while the other side is sending.
+/// - After [`SoftSerial::listen()`], reception is interrupt-driven: the pin-change interrupt of
+///   the RX pin (see [`PcintBank`][crate::pcint::PcintBank]) detects the start bit and calls
+///   [`SoftSerial::on_start_bit()`].  The following bits are sampled from the compare match
+///   interrupt of a timer, which calls [`SoftSerial::on_timer()`].  The last received byte is
+///   buffered until it is read.
+///
+/// As there is just one CPU to do both, the port is half-duplex: bytes arriving while a frame is
+/// being sent are corrupted.
+///
+/// # Example
+/// (for Trinket)
+/// ```
+/// let dp = arduino_hal::Peripherals::take().unwrap();
+/// let pins = arduino_hal::pins!(dp);
+///
+/// let mut serial = arduino_hal::usart::SoftSerial::new(
+///     pins.d2.into_pull_up_input(),
+///     pins.d0.into_output(),
+///     9600,
+/// );
+///
+/// ufmt::uwriteln!(&mut serial, "Hello from ATtiny!\r").unwrap_infallible();
+///
+/// loop {
+///     let b = nb::block!(serial.read()).unwrap();
+///     serial.write_byte(b);
+/// }
+/// ```
+pub struct SoftSerial<RX, TX, CLOCK> {
+    rx: port::Pin<port::mode::Input, RX>,
+    tx: port::Pin<port::mode::Output, TX>,
+    bit_cycles: u16,
+    bit_delay: u16,
+    /// Timer ticks per bit, if reception is interrupt-driven.
+    timer_ticks: Option<u8>,
+    /// Number of sampled bits of the frame currently being received, including the start bit.
+    rx_step: Option<u8>,
+    rx_shift: u8,
+    received: Option<Result<u8, Error>>,
+    overrun: bool,
+    _clock: marker::PhantomData<CLOCK>,
+}
+
+impl<RX, TX, CLOCK> SoftSerial<RX, TX, CLOCK>
+where
+    RX: port::PinOps,
+    TX: port::PinOps,
+    CLOCK: crate::clock::Clock,
+{
+    /// Initialize a software serial port on the given pins.
+    ///
+    /// TX is driven high, which is the idle level of the line.
+    pub fn new<IMODE: port::mode::InputMode>(
+        rx: port::Pin<port::mode::Input<IMODE>, RX>,
+        mut tx: port::Pin<port::mode::Output, TX>,
+        baudrate: u32,
+    ) -> Self {
+        tx.set_high();
+
+        let bit_cycles = (CLOCK::FREQ / baudrate.max(1)).min(u16::MAX as u32) as u16;
+        Self {
+            rx: rx.forget_imode(),
+            tx,
+            bit_cycles,
+            // Round to the 4-cycle steps of the delay loop.
+            bit_delay: bit_cycles.saturating_sub(SOFT_SERIAL_BIT_OVERHEAD).saturating_add(2),
+            timer_ticks: None,
+            rx_step: None,
+            rx_shift: 0,
+            received: None,
+            overrun: false,
+            _clock: marker::PhantomData,
+        }
+    }
+
+    /// Release the pins.
+    ///
+    /// If reception was interrupt-driven, call [`SoftSerial::unlisten()`] first.
+    pub fn release(
+        self,
+    ) -> (
+        port::Pin<port::mode::Input, RX>,
+        port::Pin<port::mode::Output, TX>,
+    ) {
+        (self.rx, self.tx)
+    }
+
+    /// Transmit a byte.
+    ///
+    /// This blocks until the whole frame was sent.
+    pub fn write_byte(&mut self, byte: u8) {
+        let bit_delay = self.bit_delay;
+        let tx = &mut self.tx;
+        avr_device::interrupt::free(|_| {
+            // Start bit
+            tx.set_low();
+            crate::delay::delay_cycles(bit_delay);
+
+            let mut data = byte;
+            for _ in 0..8 {
+                if data & 0x01 != 0 {
+                    tx.set_high();
+                } else {
+                    tx.set_low();
+                }
+                data >>= 1;
+                crate::delay::delay_cycles(bit_delay);
+            }
+
+            // Stop bit
+            tx.set_high();
+            crate::delay::delay_cycles(bit_delay);
+        });
+    }
+
+    /// Block until a byte was received.
+    pub fn read_byte(&mut self) -> Result<u8, Error> {
+        nb::block!(self.read())
+    }
+
+    /// Receive a byte, if one is available.
+    ///
+    /// When reception is interrupt-driven, this returns the buffered byte.  Otherwise, a frame is
+    /// received in place if the RX line currently carries a start bit.
+    ///
+    /// [`Error::Overrun`] is returned once if a byte was lost because the previous one was not
+    /// read in time.
+    pub fn read(&mut self) -> nb::Result<u8, Error> {
+        if self.overrun {
+            self.overrun = false;
+            return Err(nb::Error::Other(Error::Overrun));
+        }
+        if let Some(received) = self.received.take() {
+            return received.map_err(nb::Error::Other);
+        }
+        if self.timer_ticks.is_none() && self.rx.is_low() {
+            return self.receive_in_place();
+        }
+        Err(nb::Error::WouldBlock)
+    }
+
+    /// Receive the frame whose start bit was just seen, sampling each bit in its middle.
+    fn receive_in_place(&mut self) -> nb::Result<u8, Error> {
+        let bit_delay = self.bit_delay;
+        let half_delay = (self.bit_cycles / 2).saturating_sub(SOFT_SERIAL_BIT_OVERHEAD);
+        let rx = &self.rx;
+        avr_device::interrupt::free(|_| {
+            crate::delay::delay_cycles(half_delay);
+            if rx.is_high() {
+                // Just a glitch on the line.
+                return Err(nb::Error::WouldBlock);
+            }
+
+            let mut data = 0u8;
+            for _ in 0..8 {
+                crate::delay::delay_cycles(bit_delay);
+                data = (data >> 1) | ((rx.is_high() as u8) << 7);
+            }
+
+            crate::delay::delay_cycles(bit_delay);
+            if rx.is_low() {
+                return Err(nb::Error::Other(Error::FrameFormat));
+            }
+            Ok(data)
+        })
+    }
+
+    /// Switch to interrupt-driven reception, sampling the bits with `timer`.
+    ///
+    /// The timer is reconfigured to CTC mode with a period of one bit, using the smallest
+    /// prescaler that allows this.  It must not be used for anything else in the meantime.  Its
+    /// compare match A interrupt handler must call [`SoftSerial::on_timer()`], and the pin-change
+    /// interrupt handler of the RX pin must call [`SoftSerial::on_start_bit()`].
+    pub fn listen<H, TC>(&mut self, timer: &mut crate::timer::Timer<H, TC, CLOCK>)
+    where
+        TC: crate::timer::TimerOps<H>,
+        TC::Counter: From<u8>,
+    {
+        use crate::timer::{Mode, Prescaler};
+
+        let (prescaler, ticks) = [
+            Prescaler::Direct,
+            Prescaler::Prescale8,
+            Prescaler::Prescale64,
+            Prescaler::Prescale256,
+            Prescaler::Prescale1024,
+        ]
+        .into_iter()
+        .map(|p| (p, self.bit_cycles as u32 / p.divider()))
+        .find(|(_, ticks)| *ticks <= u8::MAX as u32)
+        .unwrap_or((Prescaler::Prescale1024, u8::MAX as u32));
+        let ticks = (ticks as u8).max(2);
+
+        timer.configure(Mode::Ctc, prescaler);
+        timer.set_compare_a(TC::Counter::from(ticks - 1));
+        self.timer_ticks = Some(ticks);
+        self.rx_step = None;
+    }
+
+    /// Return to polled reception.
+    pub fn unlisten<H, TC>(&mut self, timer: &mut crate::timer::Timer<H, TC, CLOCK>)
+    where
+        TC: crate::timer::TimerOps<H>,
+    {
+        timer.unlisten(crate::timer::Event::CompareMatchA);
+        self.timer_ticks = None;
+        self.rx_step = None;
+    }
+
+    /// Start receiving a frame.
+    ///
+    /// Call this from the pin-change interrupt handler of the RX pin.  Rising edges and edges
+    /// while a frame is being received are ignored, so the interrupt may stay enabled.
+    pub fn on_start_bit<H, TC>(&mut self, timer: &mut crate::timer::Timer<H, TC, CLOCK>)
+    where
+        TC: crate::timer::TimerOps<H>,
+        TC::Counter: From<u8>,
+    {
+        let ticks = match self.timer_ticks {
+            Some(ticks) => ticks,
+            None => return,
+        };
+        if self.rx_step.is_some() || self.rx.is_high() {
+            return;
+        }
+
+        // The first compare match is half a bit from now, in the middle of the start bit.
+        timer.set_counter(TC::Counter::from(ticks / 2));
+        timer.clear_pending(crate::timer::Event::CompareMatchA);
+        timer.listen(crate::timer::Event::CompareMatchA);
+        self.rx_step = Some(0);
+        self.rx_shift = 0;
+    }
+
+    /// Sample the next bit of the frame being received.
+    ///
+    /// Call this from the compare match A interrupt handler of the timer passed to
+    /// [`SoftSerial::listen()`].
+    pub fn on_timer<H, TC>(&mut self, timer: &mut crate::timer::Timer<H, TC, CLOCK>)
+    where
+        TC: crate::timer::TimerOps<H>,
+    {
+        let step = match self.rx_step {
+            Some(step) => step,
+            None => return,
+        };
+        let level = self.rx.is_high();
+
+        let result = match step {
+            // Start bit which went away is just a glitch.
+            0 if level => None,
+            0..=8 => {
+                if step > 0 {
+                    self.rx_shift = (self.rx_shift >> 1) | ((level as u8) << 7);
+                }
+                self.rx_step = Some(step + 1);
+                return;
+            }
+            _ if level => Some(Ok(self.rx_shift)),
+            _ => Some(Err(Error::FrameFormat)),
+        };
+
+        timer.unlisten(crate::timer::Event::CompareMatchA);
+        self.rx_step = None;
+        if let Some(result) = result {
+            if self.received.is_some() {
+                self.overrun = true;
+            } else {
+                self.received = Some(result);
+            }
+        }
+    }
+}
+
+impl<RX, TX, CLOCK> ufmt::uWrite for SoftSerial<RX, TX, CLOCK>
+where
+    RX: port::PinOps,
+    TX: port::PinOps,
+    CLOCK: crate::clock::Clock,
+{
+    type Error = core::convert::Infallible;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        for b in s.as_bytes().iter() {
+            self.write_byte(*b);
+        }
+        Ok(())
+    }
+}
+
+impl<RX, TX, CLOCK> embedded_hal_nb::serial::ErrorType for SoftSerial<RX, TX, CLOCK> {
+    type Error = Error;
+}
+
+impl<RX, TX, CLOCK> embedded_hal_nb::serial::Read<u8> for SoftSerial<RX, TX, CLOCK>
+where
+    RX: port::PinOps,
+    TX: port::PinOps,
+    CLOCK: crate::clock::Clock,
+{
+    fn read(&mut self) -> nb::Result<u8, Self::Error> {
+        SoftSerial::read(self)
+    }
+}
+
+impl<RX, TX, CLOCK> embedded_hal_nb::serial::Write<u8> for SoftSerial<RX, TX, CLOCK>
+where
+    RX: port::PinOps,
+    TX: port::PinOps,
+    CLOCK: crate::clock::Clock,
+{
+    fn write(&mut self, byte: u8) -> nb::Result<(), Self::Error> {
+        self.write_byte(byte);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> nb::Result<(), Self::Error> {
+        // Frames are sent synchronously.
+        Ok(())
+    }
+}
+
+impl<RX, TX, CLOCK> embedded_io::ErrorType for SoftSerial<RX, TX, CLOCK> {
+    type Error = Error;
+}
+
+impl<RX, TX, CLOCK> embedded_io::Read for SoftSerial<RX, TX, CLOCK>
+where
+    RX: port::PinOps,
+    TX: port::PinOps,
+    CLOCK: crate::clock::Clock,
+{
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        // At most one byte is buffered, so there is no point in waiting for more.
+        buf[0] = self.read_byte()?;
+        Ok(1)
+    }
+}
+
+impl<RX, TX, CLOCK> embedded_io::ReadReady for SoftSerial<RX, TX, CLOCK>
+where
+    RX: port::PinOps,
+    TX: port::PinOps,
+    CLOCK: crate::clock::Clock,
+{
+    fn read_ready(&mut self) -> Result<bool, Self::Error> {
+        let polled_start = self.timer_ticks.is_none() && self.rx.is_low();
+        Ok(self.overrun || self.received.is_some() || polled_start)
+    }
+}
+
+impl<RX, TX, CLOCK> embedded_io::Write for SoftSerial<RX, TX, CLOCK>
+where
+    RX: port::PinOps,
+    TX: port::PinOps,
+    CLOCK: crate::clock::Clock,
+{
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        for b in buf.iter() {
+            self.write_byte(*b);
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<RX, TX, CLOCK> embedded_io::WriteReady for SoftSerial<RX, TX, CLOCK>
+where
+    RX: port::PinOps,
+    TX: port::PinOps,
+    CLOCK: crate::clock::Clock,
+{
+    fn write_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+}
+
+/// Create the buffers of a [`BufferedUsart`] and install its interrupt handlers.
+///
+/// This is used by the `impl_buffered_usart!` macro of the MCU HALs, which looks up the interrupt
+/// vectors and pins of each USART.  The crate invoking it needs `#![feature(abi_avr_interrupt)]`.
+#[macro_export]
+macro_rules! impl_buffered_usart {
+    (
+        mcu: $mcu:ident,
+        hal: $HAL:ty,
+        peripherals: $Peripherals:ty,
+        peripheral: $USART:ident: $UsartTy:ty,
+        rx: $rxpin:ty,
+        tx: $txpin:ty,
+        vectors: { rx: $rx_vector:ident, udre: $udre_vector:ident, tx: $tx_vector:ident },
+        $vis:vis static $NAME:ident: [$rxn:expr, $txn:expr],
+    ) => {
+        $vis static $NAME: $crate::usart::UsartBuffers<$UsartTy, { $rxn }, { $txn }> =
+            $crate::usart::UsartBuffers::new();
+
+        #[$crate::avr_device::interrupt($mcu)]
+        fn $rx_vector() {
+            // SAFETY: The peripheral is owned by the `BufferedUsart`, which only touches the
+            // registers inside critical sections while the buffers are in use.
+            let mut p = unsafe { <$Peripherals>::steal() }.$USART;
+            $NAME.on_rx_complete::<
+                $HAL,
+                $crate::port::Pin<$crate::port::mode::Input, $rxpin>,
+                $crate::port::Pin<$crate::port::mode::Output, $txpin>,
+            >(&mut p);
+        }
+
+        #[$crate::avr_device::interrupt($mcu)]
+        fn $udre_vector() {
+            // SAFETY: See above.
+            let mut p = unsafe { <$Peripherals>::steal() }.$USART;
+            $NAME.on_data_register_empty::<
+                $HAL,
+                $crate::port::Pin<$crate::port::mode::Input, $rxpin>,
+                $crate::port::Pin<$crate::port::mode::Output, $txpin>,
+            >(&mut p);
+        }
+    };
+}
+
+/// Create the shared driver-enable pin of an [`Rs485`] and install its `USART#_TX` interrupt
+/// handler.
+///
+/// This is used by the `impl_rs485!` macro of the MCU HALs, which looks up the interrupt vectors of
+/// each USART.  The crate invoking it needs `#![feature(abi_avr_interrupt)]`.
+#[macro_export]
+macro_rules! impl_rs485 {
+    (
+        mcu: $mcu:ident,
+        hal: $HAL:ty,
+        peripherals: $Peripherals:ty,
+        peripheral: $USART:ident: $UsartTy:ty,
+        rx: $rxpin:ty,
+        tx: $txpin:ty,
+        vectors: { rx: $rx_vector:ident, udre: $udre_vector:ident, tx: $tx_vector:ident },
+        $vis:vis static $NAME:ident: [$de:ty],
+    ) => {
+        $vis static $NAME: $crate::usart::Rs485DriverEnable<$UsartTy, $de> =
+            $crate::usart::Rs485DriverEnable::new();
+
+        #[$crate::avr_device::interrupt($mcu)]
+        fn $tx_vector() {
+            $NAME.on_tx_complete();
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! impl_usart_traditional {
+    (
+        hal: $HAL:ty,
+        peripheral: $USART:ty,
+        register_suffix: $n:expr,
+        rx: $rxpin:ty,
+        tx: $txpin:ty,
+    ) => {
+        $crate::paste::paste! {
+            impl $crate::usart::UsartOps<
+                $HAL,
+                $crate::port::Pin<$crate::port::mode::Input, $rxpin>,
+                $crate::port::Pin<$crate::port::mode::Output, $txpin>,
+            > for $USART {
+                fn raw_init<CLOCK>(&mut self, settings: $crate::usart::UsartSettings<CLOCK>) {
+                    use $crate::usart::{DataBits, Parity, StopBits};
+
+                    let baudrate = settings.baudrate;
+                    let format = settings.format;
+
+                    self.[<ubrr $n>].write(|w| unsafe { w.bits(baudrate.ubrr) });
+                    self.[<ucsr $n a>].write(|w| w.[<u2x $n>]().bit(baudrate.u2x));
+
+                    // Enable receiver and transmitter but leave interrupts disabled.
+                    self.[<ucsr $n b>].write(|w| w
+                        .[<txen $n>]().set_bit()
+                        .[<rxen $n>]().set_bit()
+                        .[<ucsz $n 2>]().bit(format.data_bits == DataBits::Nine)
+                    );
+
+                    self.[<ucsr $n c>].write(|w| {
+                        let w = w.[<umsel $n>]().usart_async();
+                        let w = match format.data_bits {
+                            DataBits::Five => w.[<ucsz $n>]().chr5(),
+                            DataBits::Six => w.[<ucsz $n>]().chr6(),
+                            DataBits::Seven => w.[<ucsz $n>]().chr7(),
+                            DataBits::Eight | DataBits::Nine => w.[<ucsz $n>]().chr8(),
+                        };
+                        let w = match format.stop_bits {
+                            StopBits::One => w.[<usbs $n>]().stop1(),
+                            StopBits::Two => w.[<usbs $n>]().stop2(),
+                        };
+                        match format.parity {
+                            Parity::None => w.[<upm $n>]().disabled(),
+                            Parity::Even => w.[<upm $n>]().parity_even(),
+                            Parity::Odd => w.[<upm $n>]().parity_odd(),
+                        }
+                    });
+                }
+
+                fn raw_deinit(&mut self) {
+                    // Wait for any ongoing transfer to finish.
+                    $crate::nb::block!(self.raw_flush()).ok();
+                    self.[<ucsr $n b>].reset();
+                }
+
+                fn raw_flush(&mut self) -> $crate::nb::Result<(), core::convert::Infallible> {
+                    if self.[<ucsr $n a>].read().[<udre $n>]().bit_is_clear() {
+                        Err($crate::nb::Error::WouldBlock)
+                    } else {
+                        Ok(())
+                    }
                 }
 
                 fn raw_write(&mut self, byte: u8) -> $crate::nb::Result<(), core::convert::Infallible> {
@@ -525,12 +2508,73 @@ macro_rules! impl_usart_traditional {
                     Ok(())
                 }
 
-                fn raw_read(&mut self) -> $crate::nb::Result<u8, core::convert::Infallible> {
+                fn raw_read(&mut self) -> $crate::nb::Result<u8, $crate::usart::Error> {
+                    static OVERRUN: $crate::usart::PendingOverrun =
+                        $crate::usart::PendingOverrun::new();
+                    if OVERRUN.take() {
+                        return Err($crate::nb::Error::Other($crate::usart::Error::Overrun));
+                    }
+
+                    let ucsra = self.[<ucsr $n a>].read();
+                    if ucsra.[<rxc $n>]().bit_is_clear() {
+                        return Err($crate::nb::Error::WouldBlock);
+                    }
+
+                    // The error flags belong to the frame in the receive buffer, so they must be
+                    // read before the data register.  Reading it discards the frame either way.
+                    let byte = self.[<udr $n>].read().bits();
+                    if ucsra.[<dor $n>]().bit_is_set() {
+                        OVERRUN.set();
+                    }
+                    if ucsra.[<fe $n>]().bit_is_set() {
+                        Err($crate::nb::Error::Other($crate::usart::Error::FrameFormat))
+                    } else if ucsra.[<upe $n>]().bit_is_set() {
+                        Err($crate::nb::Error::Other($crate::usart::Error::Parity))
+                    } else {
+                        Ok(byte)
+                    }
+                }
+
+                fn raw_write_9bit(
+                    &mut self,
+                    data: u16,
+                ) -> $crate::nb::Result<(), core::convert::Infallible> {
+                    self.raw_flush()?;
+
+                    // The ninth bit must be written before the low byte.
+                    self.[<ucsr $n b>].modify(|_, w| w.[<txb8 $n>]().bit(data & 0x100 != 0));
+                    self.[<udr $n>].write(|w| unsafe { w.bits(data as u8) });
+                    Ok(())
+                }
+
+                fn raw_read_ready(&mut self) -> bool {
+                    self.[<ucsr $n a>].read().[<rxc $n>]().bit_is_set()
+                }
+
+                fn raw_read_9bit(&mut self) -> $crate::nb::Result<u16, $crate::usart::Error> {
                     if self.[<ucsr $n a>].read().[<rxc $n>]().bit_is_clear() {
                         return Err($crate::nb::Error::WouldBlock);
                     }
 
-                    Ok(self.[<udr $n>].read().bits())
+                    // The ninth bit must be read before the low byte.
+                    let high = self.[<ucsr $n b>].read().[<rxb8 $n>]().bit() as u16;
+                    let low = self.raw_read()?;
+                    Ok(high << 8 | low as u16)
+                }
+
+                fn raw_multiprocessor_mode(&mut self, state: bool) {
+                    // Writing back a set TXC flag would clear it, so write it as zero.
+                    self.[<ucsr $n a>]
+                        .modify(|_, w| w.[<mpcm $n>]().bit(state).[<txc $n>]().clear_bit());
+                }
+
+                fn raw_is_tx_complete(&mut self) -> bool {
+                    self.[<ucsr $n a>].read().[<txc $n>]().bit_is_set()
+                }
+
+                fn raw_clear_tx_complete(&mut self) {
+                    // The flag is cleared by writing a logical one.
+                    self.[<ucsr $n a>].modify(|_, w| w.[<txc $n>]().set_bit());
                 }
 
                 fn raw_interrupt(&mut self, event: $crate::usart::Event, state: bool) {
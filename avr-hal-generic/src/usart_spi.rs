@@ -0,0 +1,140 @@
+//! USART in Master SPI Mode (MSPIM)
+//!
+//! Most ATmega USARTs can be switched into a mode where they act as an SPI master.  The `XCK#`
+//! pin becomes SCLK, `TXD#` becomes MOSI and `RXD#` becomes MISO.  There is no hardware
+//! chip-select, so any output pin can be used for it.
+//!
+//! As the peripheral then behaves just like the dedicated SPI peripheral, this module only
+//! implements [`SpiOps`][crate::spi::SpiOps] for the USARTs.  The resulting [`UsartSpi`] is a
+//! regular [`Spi`][crate::spi::Spi] and accepts the same [`Settings`][crate::spi::Settings].
+
+/// SPI bus driven by a USART in Master SPI Mode
+///
+/// This is a [`Spi`][crate::spi::Spi], so it implements both the embedded-hal 1.0
+/// [`SpiBus`][embedded_hal::spi::SpiBus] and the embedded-hal 0.2 traits.
+///
+/// The [`SerialClockRate`][crate::spi::SerialClockRate] is derived from the USART baudrate
+/// generator, so `OscfOver2` and all other rates can be used.
+///
+/// # Example
+/// (for Arduino Uno)
+/// ```
+/// let dp = arduino_hal::Peripherals::take().unwrap();
+/// let pins = arduino_hal::pins!(dp);
+///
+/// // The serial console is not available while USART0 is used for SPI.
+/// let (mut spi, mut cs) = arduino_hal::usart_spi::Usart0Spi::new(
+///     dp.USART0,
+///     pins.d4.into_output(),
+///     pins.d1.into_output(),
+///     pins.d0.into_pull_up_input(),
+///     pins.d7.into_output(),
+///     spi::Settings::default(),
+/// );
+///
+/// cs.set_low().unwrap();
+/// spi.write(&[0x9f]).unwrap();
+/// cs.set_high().unwrap();
+/// ```
+pub type UsartSpi<H, USART, SCLKPIN, MOSIPIN, MISOPIN, CSPIN> =
+    crate::spi::Spi<H, USART, SCLKPIN, MOSIPIN, MISOPIN, CSPIN>;
+
+/// Implement [`SpiOps`][crate::spi::SpiOps] for a USART peripheral in Master SPI Mode.
+///
+/// The chip-select pin is not tied to the peripheral, so the implementation is generic over it.
+#[macro_export]
+macro_rules! impl_usart_spi {
+    (
+        hal: $HAL:ty,
+        peripheral: $USART:ty,
+        register_suffix: $n:expr,
+        sclk: $sclkpin:ty,
+        mosi: $mosipin:ty,
+        miso: $misopin:ty,
+    ) => {
+        $crate::paste::paste! {
+            impl<CSPIN> $crate::spi::SpiOps<$HAL, $sclkpin, $mosipin, $misopin, CSPIN> for $USART {
+                fn raw_setup(&mut self, settings: &$crate::spi::Settings) {
+                    use $crate::hal::spi;
+                    use $crate::spi::{DataOrder, SerialClockRate};
+
+                    // The baudrate register must be zero at the time the transmitter is enabled.
+                    self.[<ubrr $n>].write(|w| unsafe { w.bits(0) });
+
+                    // In MSPIM, the `UCSZ#1` and `UCSZ#0` bits select the data order (`UDORD#`)
+                    // and the clock phase (`UCPHA#`), so the `chr*` names are meaningless here.
+                    self.[<ucsr $n c>].write(|w| {
+                        let w = w.[<umsel $n>]().spi_master();
+                        let w = match (settings.data_order, settings.mode.phase) {
+                            (
+                                DataOrder::MostSignificantFirst,
+                                spi::Phase::CaptureOnFirstTransition,
+                            ) => w.[<ucsz $n>]().chr5(),
+                            (
+                                DataOrder::MostSignificantFirst,
+                                spi::Phase::CaptureOnSecondTransition,
+                            ) => w.[<ucsz $n>]().chr6(),
+                            (
+                                DataOrder::LeastSignificantFirst,
+                                spi::Phase::CaptureOnFirstTransition,
+                            ) => w.[<ucsz $n>]().chr7(),
+                            (
+                                DataOrder::LeastSignificantFirst,
+                                spi::Phase::CaptureOnSecondTransition,
+                            ) => w.[<ucsz $n>]().chr8(),
+                        };
+                        match settings.mode.polarity {
+                            spi::Polarity::IdleLow => w.[<ucpol $n>]().clear_bit(),
+                            spi::Polarity::IdleHigh => w.[<ucpol $n>]().set_bit(),
+                        }
+                    });
+
+                    // Enable receiver and transmitter but leave interrupts disabled.
+                    self.[<ucsr $n b>].write(|w| w
+                        .[<txen $n>]().set_bit()
+                        .[<rxen $n>]().set_bit()
+                    );
+
+                    // F_sck = CLK_io / (2 * (UBRR# + 1)).  This must be set after the transmitter
+                    // was enabled.
+                    let ubrr = match settings.clock {
+                        SerialClockRate::OscfOver2 => 0,
+                        SerialClockRate::OscfOver4 => 1,
+                        SerialClockRate::OscfOver8 => 3,
+                        SerialClockRate::OscfOver16 => 7,
+                        SerialClockRate::OscfOver32 => 15,
+                        SerialClockRate::OscfOver64 => 31,
+                        SerialClockRate::OscfOver128 => 63,
+                    };
+                    self.[<ubrr $n>].write(|w| unsafe { w.bits(ubrr) });
+                }
+
+                fn raw_release(&mut self) {
+                    self.[<ucsr $n b>].reset();
+                    self.[<ucsr $n c>].reset();
+                }
+
+                fn raw_check_iflag(&self) -> bool {
+                    // The receiver completes a byte at the same time as the transmitter.
+                    self.[<ucsr $n a>].read().[<rxc $n>]().bit_is_set()
+                }
+
+                fn raw_read(&self) -> u8 {
+                    self.[<udr $n>].read().bits()
+                }
+
+                fn raw_write(&mut self, byte: u8) {
+                    self.[<udr $n>].write(|w| unsafe { w.bits(byte) });
+                }
+
+                fn raw_transaction(&mut self, byte: u8) -> u8 {
+                    // The register accesses are spelled out as the method names are shared with
+                    // `UsartOps` and the chip-select pin cannot be inferred.
+                    self.[<udr $n>].write(|w| unsafe { w.bits(byte) });
+                    while self.[<ucsr $n a>].read().[<rxc $n>]().bit_is_clear() {}
+                    self.[<udr $n>].read().bits()
+                }
+            }
+        }
+    };
+}
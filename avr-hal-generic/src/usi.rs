@@ -0,0 +1,374 @@
+//! Universal Serial Interface (USI)
+//!
+//! Many ATtiny MCUs have no dedicated SPI or TWI peripheral, only the USI.  It consists of a
+//! shift register and a 4-bit counter, while the clock is strobed in software.  In three-wire
+//! mode, `USCK` becomes SCLK, `DO` becomes MOSI and `DI` becomes MISO.  In two-wire mode, `DI` is
+//! SDA and `USCK` is SCL.
+//!
+//! This module only implements [`SpiOps`][crate::spi::SpiOps] and
+//! [`I2cOps`][crate::i2c::I2cOps] for the USI.  The resulting types are a regular
+//! [`Spi`][crate::spi::Spi] and [`I2c`][crate::i2c::I2c], so they accept the same settings and
+//! implement the same embedded-hal traits.
+//!
+//! The USI keeps no configuration besides its mode, so the bit timing is stored in a `static`
+//! for each USI.  Only one of the two modes can be used at a time.
+
+/// Implement [`SpiOps`][crate::spi::SpiOps] for a USI in three-wire mode.
+///
+/// `port` names the port of the `USCK` pin, whose number is given as `sclk`.  The idle level of
+/// SCLK is set through the `PORT` register.  There is no hardware chip-select, so the
+/// implementation is generic over it.
+///
+/// The USI can only shift out the most significant bit first.  For
+/// [`DataOrder::LeastSignificantFirst`], the bits of each byte are reversed in software.
+///
+/// [`DataOrder::LeastSignificantFirst`]: crate::spi::DataOrder::LeastSignificantFirst
+#[macro_export]
+macro_rules! impl_usi_spi {
+    (
+        hal: $HAL:ty,
+        peripheral: $USI:ty,
+        port: $name:ident: $PORT:ty,
+        sclk: $sclkpin:ty = $sclk:literal,
+        mosi: $mosipin:ty,
+        miso: $misopin:ty,
+    ) => {
+        const _: () = {
+            use core::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+
+            // Half a period of SCLK in CPU cycles
+            static HALF_PERIOD: AtomicU8 = AtomicU8::new(1);
+            // Whether the least significant bit is sent first
+            static LSB_FIRST: AtomicBool = AtomicBool::new(false);
+
+            /// Convert between the configured data order and the MSB-first order of the USI.
+            fn order(byte: u8) -> u8 {
+                if LSB_FIRST.load(Ordering::Relaxed) {
+                    byte.reverse_bits()
+                } else {
+                    byte
+                }
+            }
+
+            fn shift(usi: &$USI, byte: u8) -> u8 {
+                let half_period = HALF_PERIOD.load(Ordering::Relaxed) as u16;
+
+                usi.usidr.write(|w| unsafe { w.bits(order(byte)) });
+                // Clear the overflow flag, the counter overflows after 16 clock edges.
+                usi.usisr.write(|w| w.usioif().set_bit().usicnt().bits(0));
+                while usi.usisr.read().usioif().bit_is_clear() {
+                    usi.usicr.modify(|_, w| w.usitc().set_bit());
+                    $crate::delay::delay_cycles(half_period);
+                }
+                order(usi.usidr.read().bits())
+            }
+
+            $crate::paste::paste! {
+                impl<CSPIN> $crate::spi::SpiOps<$HAL, $sclkpin, $mosipin, $misopin, CSPIN>
+                    for $USI
+                {
+                    fn raw_setup(&mut self, settings: &$crate::spi::Settings) {
+                        use $crate::hal::spi;
+
+                        LSB_FIRST.store(
+                            settings.data_order == $crate::spi::DataOrder::LeastSignificantFirst,
+                            Ordering::Relaxed,
+                        );
+                        HALF_PERIOD.store(settings.clock.into_divider() / 2, Ordering::Relaxed);
+
+                        // `USITC` toggles the PORT bit of USCK, so it also sets the idle level.
+                        unsafe {
+                            (*<$PORT>::ptr()).[<port $name:lower>].modify(|_, w| {
+                                w.[<p $name:lower $sclk>]()
+                                    .bit(settings.mode.polarity == spi::Polarity::IdleHigh)
+                            })
+                        };
+
+                        // With `ext_pos`, data is sampled on the rising edge of SCLK and changes
+                        // on the falling edge.  The counter is clocked by `USITC` strobes.
+                        let sample_on_rising = (settings.mode.polarity == spi::Polarity::IdleLow)
+                            == (settings.mode.phase == spi::Phase::CaptureOnFirstTransition);
+                        self.usicr.write(|w| {
+                            let w = w.usiwm().three_wire().usiclk().set_bit();
+                            if sample_on_rising {
+                                w.usics().ext_pos()
+                            } else {
+                                w.usics().ext_neg()
+                            }
+                        });
+                    }
+
+                    fn raw_release(&mut self) {
+                        self.usicr.reset();
+                    }
+
+                    fn raw_check_iflag(&self) -> bool {
+                        self.usisr.read().usioif().bit_is_set()
+                    }
+
+                    fn raw_read(&self) -> u8 {
+                        order(self.usidr.read().bits())
+                    }
+
+                    fn raw_write(&mut self, byte: u8) {
+                        // The clock is strobed in software, so the transfer is finished on return.
+                        shift(self, byte);
+                    }
+
+                    fn raw_transaction(&mut self, byte: u8) -> u8 {
+                        shift(self, byte)
+                    }
+                }
+            }
+        };
+    };
+}
+
+/// Implement [`I2cOps`][crate::i2c::I2cOps] for a USI in two-wire mode.
+///
+/// `port` names the port of the `DI`/SDA and `USCK`/SCL pins, whose numbers are given as `sda`
+/// and `scl`.  The USI only pulls the lines low while their `PORT` bits are set, so the start and
+/// stop conditions are generated through the `PORT` register.
+#[macro_export]
+macro_rules! impl_usi_i2c {
+    (
+        hal: $HAL:ty,
+        peripheral: $USI:ty,
+        port: $name:ident: $PORT:ty,
+        sda: $sdapin:ty = $sda:literal,
+        scl: $sclpin:ty = $scl:literal,
+    ) => {
+        const _: () = {
+            use core::sync::atomic::{AtomicU8, Ordering};
+            use $crate::i2c::{Direction, Error};
+
+            // Half a period of SCL in steps of 4 CPU cycles
+            static HALF_PERIOD: AtomicU8 = AtomicU8::new(0);
+
+            fn delay() {
+                $crate::delay::delay_cycles(HALF_PERIOD.load(Ordering::Relaxed) as u16 * 4);
+            }
+
+            $crate::paste::paste! {
+                fn sda_release(release: bool) {
+                    unsafe {
+                        (*<$PORT>::ptr()).[<port $name:lower>].modify(|_, w| {
+                            w.[<p $name:lower $sda>]().bit(release)
+                        })
+                    };
+                }
+
+                fn sda_output(output: bool) {
+                    unsafe {
+                        (*<$PORT>::ptr()).[<ddr $name:lower>].modify(|_, w| {
+                            w.[<p $name:lower $sda>]().bit(output)
+                        })
+                    };
+                }
+
+                fn scl_release(release: bool) {
+                    unsafe {
+                        (*<$PORT>::ptr()).[<port $name:lower>].modify(|_, w| {
+                            w.[<p $name:lower $scl>]().bit(release)
+                        })
+                    };
+                }
+
+                fn scl_output(output: bool) {
+                    unsafe {
+                        (*<$PORT>::ptr()).[<ddr $name:lower>].modify(|_, w| {
+                            w.[<p $name:lower $scl>]().bit(output)
+                        })
+                    };
+                }
+
+                fn scl_is_high() -> bool {
+                    unsafe {
+                        (*<$PORT>::ptr()).[<pin $name:lower>].read().[<p $name:lower $scl>]().bit()
+                    }
+                }
+            }
+
+            /// Clock `edges` SCL edges (two per bit) and return the shifted-in data.
+            fn transfer(usi: &$USI, edges: u8, timeout: Option<u32>) -> Result<u8, Error> {
+                // Clear all flags and preset the counter to overflow after `edges` edges.
+                usi.usisr.write(|w| {
+                    w.usisif()
+                        .set_bit()
+                        .usioif()
+                        .set_bit()
+                        .usipf()
+                        .set_bit()
+                        .usicnt()
+                        .bits(16 - edges)
+                });
+
+                loop {
+                    delay();
+                    // Rising edge, which a device can delay by stretching the clock
+                    usi.usicr.modify(|_, w| w.usitc().set_bit());
+                    $crate::i2c::wait_until(timeout, scl_is_high)?;
+                    delay();
+                    // Falling edge
+                    usi.usicr.modify(|_, w| w.usitc().set_bit());
+                    if usi.usisr.read().usioif().bit_is_set() {
+                        break;
+                    }
+                }
+                delay();
+
+                let data = usi.usidr.read().bits();
+                // Release SDA
+                usi.usidr.write(|w| unsafe { w.bits(0xff) });
+                sda_output(true);
+                Ok(data)
+            }
+
+            /// Send a byte and return whether the device acknowledged it.
+            fn write_byte(usi: &$USI, byte: u8, timeout: Option<u32>) -> Result<bool, Error> {
+                scl_release(false);
+                usi.usidr.write(|w| unsafe { w.bits(byte) });
+                transfer(usi, 16, timeout)?;
+
+                sda_output(false);
+                Ok(transfer(usi, 2, timeout)? & 0x01 == 0)
+            }
+
+            /// Receive a byte and acknowledge it if `ack` is set.
+            fn read_byte(usi: &$USI, ack: bool, timeout: Option<u32>) -> Result<u8, Error> {
+                sda_output(false);
+                let byte = transfer(usi, 16, timeout)?;
+
+                usi.usidr.write(|w| unsafe { w.bits(if ack { 0x00 } else { 0xff }) });
+                transfer(usi, 2, timeout)?;
+                Ok(byte)
+            }
+
+            fn stop(usi: &$USI, timeout: Option<u32>) -> Result<(), Error> {
+                // Stop condition: SDA rises while SCL is high
+                sda_release(false);
+                scl_release(true);
+                $crate::i2c::wait_until(timeout, scl_is_high)?;
+                delay();
+                sda_release(true);
+                delay();
+
+                if usi.usisr.read().usipf().bit_is_clear() {
+                    return Err(Error::BusError);
+                }
+                Ok(())
+            }
+
+            impl
+                $crate::i2c::I2cOps<
+                    $HAL,
+                    $crate::port::Pin<$crate::port::mode::Input, $sdapin>,
+                    $crate::port::Pin<$crate::port::mode::Input, $sclpin>,
+                > for $USI
+            {
+                #[inline]
+                fn raw_setup<CLOCK: $crate::clock::Clock>(&mut self, speed: u32) {
+                    let half_period = CLOCK::FREQ / speed.max(1) / 8;
+                    HALF_PERIOD.store(half_period.min(u8::MAX as u32) as u8, Ordering::Relaxed);
+
+                    // Both lines are outputs, which the USI only ever pulls low.
+                    sda_release(true);
+                    scl_release(true);
+                    sda_output(true);
+                    scl_output(true);
+
+                    self.usidr.write(|w| unsafe { w.bits(0xff) });
+                    // The PAC calls two-wire mode without holding SCL on counter overflow
+                    // `two_wire_slave`, but it is also the mode for controllers.
+                    self.usicr.write(|w| {
+                        w.usiwm()
+                            .two_wire_slave()
+                            .usics()
+                            .ext_pos()
+                            .usiclk()
+                            .set_bit()
+                    });
+                    self.usisr.write(|w| {
+                        w.usisif()
+                            .set_bit()
+                            .usioif()
+                            .set_bit()
+                            .usipf()
+                            .set_bit()
+                            .usicnt()
+                            .bits(0)
+                    });
+                }
+
+                #[inline]
+                fn raw_start(
+                    &mut self,
+                    address: u8,
+                    direction: Direction,
+                    timeout: Option<u32>,
+                ) -> Result<(), Error> {
+                    // Release SCL, so a repeated start condition can be generated.
+                    scl_release(true);
+                    $crate::i2c::wait_until(timeout, scl_is_high)?;
+                    delay();
+
+                    // Start condition: SDA falls while SCL is high
+                    sda_release(false);
+                    delay();
+                    scl_release(false);
+                    sda_release(true);
+
+                    if self.usisr.read().usisif().bit_is_clear() {
+                        // Another controller is using the bus.
+                        return Err(Error::ArbitrationLost);
+                    }
+
+                    if !write_byte(self, address << 1 | direction as u8, timeout)? {
+                        // Stop the transaction if it did not respond
+                        stop(self, timeout)?;
+                        return Err(Error::AddressNack);
+                    }
+                    Ok(())
+                }
+
+                #[inline]
+                fn raw_write(&mut self, bytes: &[u8], timeout: Option<u32>) -> Result<(), Error> {
+                    for byte in bytes {
+                        if !write_byte(self, *byte, timeout)? {
+                            stop(self, timeout)?;
+                            return Err(Error::DataNack);
+                        }
+                    }
+                    Ok(())
+                }
+
+                #[inline]
+                fn raw_read(
+                    &mut self,
+                    buffer: &mut [u8],
+                    timeout: Option<u32>,
+                ) -> Result<(), Error> {
+                    let last = buffer.len().saturating_sub(1);
+                    for (i, byte) in buffer.iter_mut().enumerate() {
+                        // The last byte is not acknowledged, which ends the read.
+                        *byte = read_byte(self, i != last, timeout)?;
+                    }
+                    Ok(())
+                }
+
+                #[inline]
+                fn raw_stop(&mut self, timeout: Option<u32>) -> Result<(), Error> {
+                    stop(self, timeout)
+                }
+
+                #[inline]
+                fn raw_disable(&mut self) {
+                    self.usicr.reset();
+                    // Back to inputs, keeping the PORT bits set as pull-ups.
+                    sda_output(false);
+                    scl_output(false);
+                }
+            }
+        };
+    };
+}
@@ -0,0 +1,42 @@
+/*!
+ * Echo everything received on the serial console, using interrupt-driven RX and TX buffers.
+ *
+ * Bytes arriving while the main loop is busy are kept in the RX buffer instead of being lost.  If
+ * the buffer overflows nonetheless, a message is printed.
+ */
+#![no_std]
+#![no_main]
+#![feature(abi_avr_interrupt)]
+
+use arduino_hal::usart::BufferedUsart;
+use panic_halt as _;
+
+arduino_hal::hal::impl_buffered_usart! {
+    static SERIAL_BUFFERS: USART0<64, 32>;
+}
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+    let serial = arduino_hal::default_serial!(dp, pins, 57600);
+
+    let mut serial = BufferedUsart::new(serial, &SERIAL_BUFFERS);
+
+    // Enable interrupts globally
+    unsafe { avr_device::interrupt::enable() };
+
+    ufmt::uwriteln!(&mut serial, "Hello from Arduino!\r").unwrap();
+
+    loop {
+        let b = serial.read_byte();
+        serial.write_byte(b);
+
+        // Simulate some slow processing
+        arduino_hal::delay_ms(10);
+
+        if serial.take_overflow() {
+            ufmt::uwriteln!(&mut serial, "\r\nRX buffer overflow!\r").unwrap();
+        }
+    }
+}
@@ -0,0 +1,54 @@
+/*!
+ * Act as an I2C target (slave) device with a small register file.
+ *
+ * The controller (e.g. a Raspberry Pi) first writes a register index, optionally followed by
+ * values for consecutive registers.  Reads return the registers starting at the last index:
+ *
+ *     i2cset -y 1 0x42 0x02 0xab
+ *     i2cget -y 1 0x42 0x02
+ *
+ * Connections
+ * -----------
+ *  - `A4`: I2C SDA signal
+ *  - `A5`: I2C SCL signal
+ *
+ * Note that a Raspberry Pi uses 3.3V logic levels, so the bus must not be pulled up to 5V.
+ */
+#![no_std]
+#![no_main]
+
+use arduino_hal::i2c::{I2cTarget, TargetEvent};
+use arduino_hal::prelude::*;
+use panic_halt as _;
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+    let mut serial = arduino_hal::default_serial!(dp, pins, 57600);
+
+    let mut target = I2cTarget::with_external_pullup(dp.TWI, pins.a4, pins.a5, 0x42);
+
+    let mut registers = [0u8; 16];
+    let mut index = None;
+
+    loop {
+        match nb::block!(target.poll()) {
+            Ok(TargetEvent::WriteStart { .. }) => index = None,
+            Ok(TargetEvent::WriteReceived(value)) => match index {
+                None => index = Some(value as usize % registers.len()),
+                Some(i) => {
+                    registers[i] = value;
+                    index = Some((i + 1) % registers.len());
+                }
+            },
+            Ok(TargetEvent::ReadRequest { .. }) => {
+                let i = index.unwrap_or(0);
+                target.respond(registers[i]);
+                index = Some((i + 1) % registers.len());
+            }
+            Ok(TargetEvent::Stop) => (),
+            Err(e) => ufmt::uwriteln!(&mut serial, "I2C error: {:?}\r", e).unwrap_infallible(),
+        }
+    }
+}
@@ -4,6 +4,8 @@
  * In this example we can get an interrupt when pin 2 changes
  * and use that to move a stepper motor.
  *
+ * The interrupt handler asks the pin-change bank which pins changed, so more pins of the same
+ * bank (e.g. a second encoder or a keypad) could share the PCINT2 vector.
  */
 #![no_std]
 #![no_main]
@@ -11,24 +13,35 @@
 
 use panic_halt as _;
 
-use core::sync::atomic::{AtomicBool, Ordering};
+use arduino_hal::pcint::{Pcint2, PcintBank};
+use arduino_hal::port::{mode, Pin};
+use core::cell::{Cell, RefCell};
 
-static PIN_CHANGED: AtomicBool = AtomicBool::new(false);
+struct Encoder {
+    bank: PcintBank<Pcint2>,
+    clk: Pin<mode::Input<mode::Floating>, arduino_hal::hal::port::PD2>,
+    dt: Pin<mode::Input<mode::Floating>, arduino_hal::hal::port::PD3>,
+}
+
+static ENCODER: avr_device::interrupt::Mutex<RefCell<Option<Encoder>>> =
+    avr_device::interrupt::Mutex::new(RefCell::new(None));
+
+/// Direction of the last rotation which was not handled yet.
+static ROTATION: avr_device::interrupt::Mutex<Cell<Option<bool>>> =
+    avr_device::interrupt::Mutex::new(Cell::new(None));
 
 //This function is called on change of pin 2
 #[avr_device::interrupt(atmega328p)]
 #[allow(non_snake_case)]
 fn PCINT2() {
-    PIN_CHANGED.store(true, Ordering::SeqCst);
-}
-
-fn rotate(flag: &AtomicBool) -> bool {
-    avr_device::interrupt::free(|_cs| {
-        if flag.load(Ordering::SeqCst) {
-            flag.store(false, Ordering::SeqCst);
-            true
-        } else {
-            false
+    avr_device::interrupt::free(|cs| {
+        if let Some(encoder) = ENCODER.borrow(cs).borrow_mut().as_mut() {
+            let changes = encoder.bank.changed();
+            if changes.contains(&encoder.clk) {
+                //Check which direction the rotary encoder was turned
+                let clockwise = changes.is_high(&encoder.clk) != encoder.dt.is_high();
+                ROTATION.borrow(cs).set(Some(clockwise));
+            }
         }
     })
 }
@@ -43,24 +56,23 @@ fn main() -> ! {
     let mut step_pin = pins.d5.into_output();
 
     //Rotary encoder attached on these pins
-    let rotary_pins = [
-        pins.d2.into_floating_input().downgrade(), //CLK
-        pins.d3.into_floating_input().downgrade(), //DT
-    ];
-
-    // Enable the PCINT2 pin change interrupt
-    dp.EXINT.pcicr.write(|w| unsafe { w.bits(0b100) });
+    let clk = pins.d2.into_floating_input();
+    let dt = pins.d3.into_floating_input();
 
-    // Enable pin change interrupts on PCINT18 which is pin PD2 (= d2)
-    dp.EXINT.pcmsk2.write(|w| w.bits(0b100));
+    // Enable the PCINT2 pin change interrupt for changes on CLK (d2)
+    let mut exint = dp.EXINT;
+    let mut bank = PcintBank::new(&mut exint);
+    bank.listen(&clk);
+    avr_device::interrupt::free(|cs| {
+        ENCODER.borrow(cs).replace(Some(Encoder { bank, clk, dt }));
+    });
 
     //From this point on an interrupt can happen
     unsafe { avr_device::interrupt::enable() };
 
     loop {
-        if rotate(&PIN_CHANGED) {
-            //Check which direction the rotary encoder was turned
-            if rotary_pins[0].is_high() != rotary_pins[1].is_high() {
+        if let Some(clockwise) = avr_device::interrupt::free(|cs| ROTATION.borrow(cs).take()) {
+            if clockwise {
                 dir_pin.set_high();
             } else {
                 dir_pin.set_low();
@@ -0,0 +1,87 @@
+/*!
+ * Act as an SPI peripheral (slave) device behind another SPI controller.
+ *
+ * Every received byte is answered with that byte plus one during the next transfer.  After each
+ * transaction, the number of bytes which were exchanged is printed.
+ *
+ * Connections
+ * -----------
+ *  - `D10`: SS, connected to the chip-select output of the controller
+ *  - `D11`: MOSI
+ *  - `D12`: MISO
+ *  - `D13`: SCLK
+ */
+#![no_std]
+#![no_main]
+#![feature(abi_avr_interrupt)]
+
+use arduino_hal::prelude::*;
+use arduino_hal::spi::{self, Frame, SpiPeripheral};
+use avr_device::interrupt::Mutex;
+use core::cell::{Cell, RefCell};
+use panic_halt as _;
+
+static PERIPHERAL: Mutex<RefCell<Option<SpiPeripheral>>> = Mutex::new(RefCell::new(None));
+static COUNT: Mutex<Cell<u8>> = Mutex::new(Cell::new(0));
+static FINISHED: Mutex<Cell<Option<u8>>> = Mutex::new(Cell::new(None));
+
+#[avr_device::interrupt(atmega328p)]
+fn SPI_STC() {
+    avr_device::interrupt::free(|cs| {
+        if let Some(spi) = PERIPHERAL.borrow(cs).borrow_mut().as_mut() {
+            spi.on_transfer_complete(|byte| byte.wrapping_add(1));
+            let count = COUNT.borrow(cs);
+            count.set(count.get().wrapping_add(1));
+        }
+    })
+}
+
+// Fires on changes of D10 (SS)
+#[avr_device::interrupt(atmega328p)]
+fn PCINT0() {
+    avr_device::interrupt::free(|cs| {
+        if let Some(spi) = PERIPHERAL.borrow(cs).borrow_mut().as_mut() {
+            spi.on_select_change(|frame| match frame {
+                Frame::Start => COUNT.borrow(cs).set(0),
+                Frame::End => FINISHED.borrow(cs).set(Some(COUNT.borrow(cs).get())),
+            });
+        }
+    })
+}
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+    let mut serial = arduino_hal::default_serial!(dp, pins, 57600);
+
+    let ss = pins.d10.into_pull_up_input();
+
+    // Enable the PCINT0 pin change interrupt for changes on SS
+    let mut exint = dp.EXINT;
+    let mut bank = arduino_hal::pcint::PcintBank::new(&mut exint);
+    bank.listen(&ss);
+
+    let mut spi = SpiPeripheral::new(
+        dp.SPI,
+        pins.d13.into_floating_input(),
+        pins.d11.into_floating_input(),
+        pins.d12.into_output(),
+        ss,
+        spi::Settings::default(),
+    );
+    spi.preload(0x00);
+    // Start tracking SS so MISO is released while the device is not selected.
+    spi.on_select_change(|_| ());
+    spi.listen();
+    avr_device::interrupt::free(|cs| PERIPHERAL.borrow(cs).replace(Some(spi)));
+
+    // From this point on an interrupt can happen
+    unsafe { avr_device::interrupt::enable() };
+
+    loop {
+        if let Some(count) = avr_device::interrupt::free(|cs| FINISHED.borrow(cs).take()) {
+            ufmt::uwriteln!(&mut serial, "Exchanged {} bytes\r", count).unwrap_infallible();
+        }
+    }
+}
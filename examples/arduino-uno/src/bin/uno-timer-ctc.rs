@@ -0,0 +1,65 @@
+/*!
+ * Blink the builtin LED from a timer interrupt using the generic timer driver.
+ *
+ * TC1 is put into CTC mode with a prescaler of 256 so it fires the COMPA interrupt four times per
+ * second.  Compare this with `uno-timer.rs` which does the same thing by writing the timer
+ * registers directly.
+ */
+#![no_std]
+#![no_main]
+#![feature(abi_avr_interrupt)]
+
+use arduino_hal::port::mode::Output;
+use arduino_hal::port::Pin;
+use arduino_hal::prelude::*;
+use arduino_hal::timer::{Event, Mode, Prescaler};
+use core::mem;
+use panic_halt as _;
+
+static mut LED: mem::MaybeUninit<Pin<Output>> = mem::MaybeUninit::uninit();
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+
+    let mut serial = arduino_hal::default_serial!(dp, pins, 57600);
+
+    let led = pins.d13.into_output();
+    unsafe {
+        // SAFETY: Interrupts are not enabled at this point so we can safely write the global
+        // variable here.  A memory barrier afterwards ensures the compiler won't reorder this
+        // after any operation that enables interrupts.
+        LED = mem::MaybeUninit::new(led.downgrade());
+        core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+    }
+
+    let mut timer = arduino_hal::timer::Timer::new(dp.TC1, Mode::Ctc, Prescaler::Prescale256);
+
+    // 16 MHz / 256 = 62500 ticks per second, so a compare value of 15624 gives 4 Hz.
+    let ticks = (timer.tick_hz() / 4 - 1) as u16;
+    timer.set_compare_a(ticks);
+    timer.listen(Event::CompareMatchA);
+
+    ufmt::uwriteln!(&mut serial, "Timer ticking at {} Hz, TOP = {}\r", timer.tick_hz(), ticks)
+        .unwrap_infallible();
+
+    // SAFETY: Not inside a critical section and any non-atomic operations have been completed
+    // at this point.
+    unsafe { avr_device::interrupt::enable() };
+
+    loop {
+        avr_device::asm::sleep()
+    }
+}
+
+#[avr_device::interrupt(atmega328p)]
+fn TIMER1_COMPA() {
+    let led = unsafe {
+        // SAFETY: We _know_ that interrupts will only be enabled after the LED global was
+        // initialized so this ISR will never run when LED is uninitialized.
+        &mut *LED.as_mut_ptr()
+    };
+
+    led.toggle();
+}
@@ -0,0 +1,36 @@
+/*!
+ * Play a short melody on a piezo buzzer.
+ *
+ * The square wave is generated by timer TC2 in hardware, so the buzzer must be connected to OC2A
+ * (D11).
+ *
+ * Connections
+ * -----------
+ *  - `D11`: Piezo buzzer (other leg to GND)
+ */
+#![no_std]
+#![no_main]
+
+use arduino_hal::tone::Tone;
+use panic_halt as _;
+
+// C major scale, in Hz
+const MELODY: [u32; 8] = [262, 294, 330, 349, 392, 440, 494, 523];
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+
+    let mut tone = Tone::new(dp.TC2, pins.d11.into_output());
+
+    loop {
+        for note in MELODY {
+            tone.play(note).unwrap();
+            arduino_hal::delay_ms(250);
+            tone.stop();
+            arduino_hal::delay_ms(50);
+        }
+        arduino_hal::delay_ms(1000);
+    }
+}
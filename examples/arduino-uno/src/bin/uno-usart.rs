@@ -17,7 +17,7 @@ fn main() -> ! {
 
     loop {
         // Read a byte from the serial connection
-        let b = nb::block!(serial.read()).unwrap_infallible();
+        let b = nb::block!(serial.read()).unwrap();
 
         // Answer
         ufmt::uwriteln!(&mut serial, "Got {}!\r", b).unwrap_infallible();
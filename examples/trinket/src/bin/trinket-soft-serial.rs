@@ -0,0 +1,85 @@
+/*!
+ * Echo everything received on a bit-banged serial port.
+ *
+ * Reception is interrupt-driven: the pin-change interrupt detects start bits and timer TC0 samples
+ * the following bits, so no bytes are lost while the main loop is busy.
+ *
+ * Connections
+ * -----------
+ *  - `#0`: TX, connect to RX of a USB-serial adapter
+ *  - `#2`: RX, connect to TX of a USB-serial adapter
+ */
+#![no_std]
+#![no_main]
+#![feature(abi_avr_interrupt)]
+
+use arduino_hal::hal::port::{PB0, PB2};
+use arduino_hal::pcint::{Pcint0, PcintBank};
+use arduino_hal::prelude::*;
+use arduino_hal::timer::{Mode, Prescaler, Timer};
+use arduino_hal::usart::SoftSerial;
+use avr_device::interrupt::Mutex;
+use core::cell::RefCell;
+use panic_halt as _;
+
+type Serial = SoftSerial<PB2, PB0>;
+type SampleTimer = Timer<arduino_hal::pac::TC0>;
+
+static SERIAL: Mutex<RefCell<Option<(Serial, SampleTimer)>>> = Mutex::new(RefCell::new(None));
+
+// Fires on changes of #2 (RX)
+#[avr_device::interrupt(attiny85)]
+fn PCINT0() {
+    avr_device::interrupt::free(|cs| {
+        if let Some((serial, timer)) = SERIAL.borrow(cs).borrow_mut().as_mut() {
+            serial.on_start_bit(timer);
+        }
+    })
+}
+
+#[avr_device::interrupt(attiny85)]
+fn TIMER0_COMPA() {
+    avr_device::interrupt::free(|cs| {
+        if let Some((serial, timer)) = SERIAL.borrow(cs).borrow_mut().as_mut() {
+            serial.on_timer(timer);
+        }
+    })
+}
+
+#[arduino_hal::entry]
+fn main() -> ! {
+    let dp = arduino_hal::Peripherals::take().unwrap();
+    let pins = arduino_hal::pins!(dp);
+    let mut exint = dp.EXINT;
+
+    let rx = pins.d2.into_pull_up_input();
+
+    // Enable the PCINT0 pin change interrupt for changes on RX
+    let mut bank = PcintBank::<Pcint0>::new(&mut exint);
+    bank.listen(&rx);
+
+    let mut serial = SoftSerial::new(rx, pins.d0.into_output(), 9600);
+    ufmt::uwriteln!(&mut serial, "Hello from Trinket!\r").unwrap_infallible();
+
+    // `listen()` reconfigures the timer for the baudrate.
+    let mut timer = Timer::new(dp.TC0, Mode::Ctc, Prescaler::Direct);
+    serial.listen(&mut timer);
+    avr_device::interrupt::free(|cs| SERIAL.borrow(cs).replace(Some((serial, timer))));
+
+    // Enable interrupts globally
+    unsafe { avr_device::interrupt::enable() };
+
+    loop {
+        avr_device::interrupt::free(|cs| {
+            if let Some((serial, _)) = SERIAL.borrow(cs).borrow_mut().as_mut() {
+                match serial.read() {
+                    Ok(b) => serial.write_byte(b),
+                    Err(nb::Error::WouldBlock) => (),
+                    Err(nb::Error::Other(_)) => {
+                        ufmt::uwriteln!(serial, "\r\nReceive error!\r").unwrap_infallible()
+                    }
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,313 @@
+//! External Interrupts
+//!
+//! # Example
+//!
+//! ```
+//! let dp = atmega_hal::Peripherals::take().unwrap();
+//! let pins = atmega_hal::pins!(dp);
+//! let mut exint = dp.EXINT;
+//!
+//! let mut int0 = pins
+//!     .pd2
+//!     .into_pull_up_input()
+//!     .enable_external_interrupt(&mut exint, Trigger::FallingEdge);
+//!
+//! // ...
+//!
+//! if int0.is_pending(&exint) {
+//!     int0.clear_pending(&mut exint);
+//! }
+//! ```
+
+#[allow(unused_imports)]
+use crate::port;
+pub use avr_hal_generic::exint::{EdgeTrigger, ExintOps, LevelOrEdgeTrigger, Trigger};
+
+/// Check the [`avr_hal_generic::exint::ExternalInterrupt`] documentation.
+pub type ExternalInterrupt<PIN, IMODE> =
+    avr_hal_generic::exint::ExternalInterrupt<crate::Atmega, crate::pac::EXINT, PIN, IMODE>;
+
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb"
+))]
+avr_hal_generic::impl_exint! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::EXINT,
+    interrupts: {
+        // INT0
+        port::PD2: {
+            sense: eicra[0],
+            mask: eimsk,
+            flag: eifr,
+            bit: 0,
+        },
+        // INT1
+        port::PD3: {
+            sense: eicra[2],
+            mask: eimsk,
+            flag: eifr,
+            bit: 1,
+        },
+    },
+}
+
+#[cfg(any(feature = "atmega164pa", feature = "atmega1284p"))]
+avr_hal_generic::impl_exint! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::EXINT,
+    interrupts: {
+        // INT0
+        port::PD2: {
+            sense: eicra[0],
+            mask: eimsk,
+            flag: eifr,
+            bit: 0,
+        },
+        // INT1
+        port::PD3: {
+            sense: eicra[2],
+            mask: eimsk,
+            flag: eifr,
+            bit: 1,
+        },
+        // INT2
+        port::PB2: {
+            sense: eicra[4],
+            mask: eimsk,
+            flag: eifr,
+            bit: 2,
+        },
+    },
+}
+
+#[cfg(feature = "atmega32u4")]
+avr_hal_generic::impl_exint! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::EXINT,
+    interrupts: {
+        // INT0
+        port::PD0: {
+            sense: eicra[0],
+            mask: eimsk,
+            flag: eifr,
+            bit: 0,
+        },
+        // INT1
+        port::PD1: {
+            sense: eicra[2],
+            mask: eimsk,
+            flag: eifr,
+            bit: 1,
+        },
+        // INT2
+        port::PD2: {
+            sense: eicra[4],
+            mask: eimsk,
+            flag: eifr,
+            bit: 2,
+        },
+        // INT3
+        port::PD3: {
+            sense: eicra[6],
+            mask: eimsk,
+            flag: eifr,
+            bit: 3,
+        },
+        // INT6
+        port::PE6: {
+            sense: eicrb[4],
+            mask: eimsk,
+            flag: eifr,
+            bit: 6,
+        },
+    },
+}
+
+// The "any edge" mode is reserved for INT0-INT3 of the ATmega128A.
+#[cfg(feature = "atmega128a")]
+avr_hal_generic::impl_exint! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::EXINT,
+    interrupts: {
+        // INT0
+        port::PD0: {
+            sense: eicra[0],
+            trigger: LevelOrEdgeTrigger,
+            mask: eimsk,
+            flag: eifr,
+            bit: 0,
+        },
+        // INT1
+        port::PD1: {
+            sense: eicra[2],
+            trigger: LevelOrEdgeTrigger,
+            mask: eimsk,
+            flag: eifr,
+            bit: 1,
+        },
+        // INT2
+        port::PD2: {
+            sense: eicra[4],
+            trigger: LevelOrEdgeTrigger,
+            mask: eimsk,
+            flag: eifr,
+            bit: 2,
+        },
+        // INT3
+        port::PD3: {
+            sense: eicra[6],
+            trigger: LevelOrEdgeTrigger,
+            mask: eimsk,
+            flag: eifr,
+            bit: 3,
+        },
+        // INT4
+        port::PE4: {
+            sense: eicrb[0],
+            mask: eimsk,
+            flag: eifr,
+            bit: 4,
+        },
+        // INT5
+        port::PE5: {
+            sense: eicrb[2],
+            mask: eimsk,
+            flag: eifr,
+            bit: 5,
+        },
+        // INT6
+        port::PE6: {
+            sense: eicrb[4],
+            mask: eimsk,
+            flag: eifr,
+            bit: 6,
+        },
+        // INT7
+        port::PE7: {
+            sense: eicrb[6],
+            mask: eimsk,
+            flag: eifr,
+            bit: 7,
+        },
+    },
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_exint! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::EXINT,
+    interrupts: {
+        // INT0
+        port::PD0: {
+            sense: eicra[0],
+            mask: eimsk,
+            flag: eifr,
+            bit: 0,
+        },
+        // INT1
+        port::PD1: {
+            sense: eicra[2],
+            mask: eimsk,
+            flag: eifr,
+            bit: 1,
+        },
+        // INT2
+        port::PD2: {
+            sense: eicra[4],
+            mask: eimsk,
+            flag: eifr,
+            bit: 2,
+        },
+        // INT3
+        port::PD3: {
+            sense: eicra[6],
+            mask: eimsk,
+            flag: eifr,
+            bit: 3,
+        },
+        // INT4
+        port::PE4: {
+            sense: eicrb[0],
+            mask: eimsk,
+            flag: eifr,
+            bit: 4,
+        },
+        // INT5
+        port::PE5: {
+            sense: eicrb[2],
+            mask: eimsk,
+            flag: eifr,
+            bit: 5,
+        },
+        // INT6
+        port::PE6: {
+            sense: eicrb[4],
+            mask: eimsk,
+            flag: eifr,
+            bit: 6,
+        },
+        // INT7
+        port::PE7: {
+            sense: eicrb[6],
+            mask: eimsk,
+            flag: eifr,
+            bit: 7,
+        },
+    },
+}
+
+#[cfg(feature = "atmega8")]
+avr_hal_generic::impl_exint! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::EXINT,
+    interrupts: {
+        // INT0
+        port::PD2: {
+            sense: mcucr[0],
+            mask: gicr,
+            flag: gifr,
+            bit: 6,
+        },
+        // INT1
+        port::PD3: {
+            sense: mcucr[2],
+            mask: gicr,
+            flag: gifr,
+            bit: 7,
+        },
+    },
+}
+
+#[cfg(feature = "atmega32a")]
+avr_hal_generic::impl_exint! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::EXINT,
+    interrupts: {
+        // INT0
+        port::PD2: {
+            sense: mcucr[0],
+            mask: gicr,
+            flag: gifr,
+            bit: 6,
+        },
+        // INT1
+        port::PD3: {
+            sense: mcucr[2],
+            mask: gicr,
+            flag: gifr,
+            bit: 7,
+        },
+        // INT2
+        port::PB2: {
+            sense: mcucsr[6],
+            trigger: EdgeTrigger,
+            mask: gicr,
+            flag: gifr,
+            bit: 5,
+        },
+    },
+}
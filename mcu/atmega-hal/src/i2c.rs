@@ -42,6 +42,35 @@ pub type I2c<CLOCK> = avr_hal_generic::i2c::I2c<
     feature = "atmega2560",
     feature = "atmega32u4"
 ))]
+pub type I2cTarget = avr_hal_generic::i2c::I2cTarget<
+    crate::Atmega,
+    crate::pac::TWI,
+    port::Pin<port::mode::Input, port::PD1>,
+    port::Pin<port::mode::Input, port::PD0>,
+>;
+#[cfg(any(
+    feature = "atmega128a",
+    feature = "atmega1280",
+    feature = "atmega2560",
+    feature = "atmega32u4"
+))]
+pub type InterruptI2c<CLOCK, const N: usize> = avr_hal_generic::i2c::InterruptI2c<
+    crate::Atmega,
+    crate::pac::TWI,
+    port::Pin<port::mode::Input, port::PD1>,
+    port::Pin<port::mode::Input, port::PD0>,
+    CLOCK,
+    N,
+>;
+#[cfg(any(feature = "atmega1280", feature = "atmega2560", feature = "atmega32u4"))]
+avr_hal_generic::impl_i2c_twi! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TWI,
+    sda: port::PD1,
+    scl: port::PD0,
+    address_mask_register: twamr,
+}
+#[cfg(feature = "atmega128a")]
 avr_hal_generic::impl_i2c_twi! {
     hal: crate::Atmega,
     peripheral: crate::pac::TWI,
@@ -58,11 +87,28 @@ pub type I2c<CLOCK> = avr_hal_generic::i2c::I2c<
     CLOCK,
 >;
 #[cfg(any(feature = "atmega164pa"))]
+pub type I2cTarget = avr_hal_generic::i2c::I2cTarget<
+    crate::Atmega,
+    crate::pac::TWI,
+    port::Pin<port::mode::Input, port::PC1>,
+    port::Pin<port::mode::Input, port::PC0>,
+>;
+#[cfg(any(feature = "atmega164pa"))]
+pub type InterruptI2c<CLOCK, const N: usize> = avr_hal_generic::i2c::InterruptI2c<
+    crate::Atmega,
+    crate::pac::TWI,
+    port::Pin<port::mode::Input, port::PC1>,
+    port::Pin<port::mode::Input, port::PC0>,
+    CLOCK,
+    N,
+>;
+#[cfg(any(feature = "atmega164pa"))]
 avr_hal_generic::impl_i2c_twi! {
     hal: crate::Atmega,
     peripheral: crate::pac::TWI,
     sda: port::PC1,
     scl: port::PC0,
+    address_mask_register: twamr,
 }
 
 #[cfg(any(
@@ -86,6 +132,41 @@ pub type I2c<CLOCK> = avr_hal_generic::i2c::I2c<
     feature = "atmega8",
     feature = "atmega88p"
 ))]
+pub type I2cTarget = avr_hal_generic::i2c::I2cTarget<
+    crate::Atmega,
+    crate::pac::TWI,
+    port::Pin<port::mode::Input, port::PC4>,
+    port::Pin<port::mode::Input, port::PC5>,
+>;
+#[cfg(any(
+    feature = "atmega328p",
+    feature = "atmega168",
+    feature = "atmega48p",
+    feature = "atmega8",
+    feature = "atmega88p"
+))]
+pub type InterruptI2c<CLOCK, const N: usize> = avr_hal_generic::i2c::InterruptI2c<
+    crate::Atmega,
+    crate::pac::TWI,
+    port::Pin<port::mode::Input, port::PC4>,
+    port::Pin<port::mode::Input, port::PC5>,
+    CLOCK,
+    N,
+>;
+#[cfg(any(
+    feature = "atmega328p",
+    feature = "atmega168",
+    feature = "atmega48p",
+    feature = "atmega88p"
+))]
+avr_hal_generic::impl_i2c_twi! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TWI,
+    sda: port::PC4,
+    scl: port::PC5,
+    address_mask_register: twamr,
+}
+#[cfg(feature = "atmega8")]
 avr_hal_generic::impl_i2c_twi! {
     hal: crate::Atmega,
     peripheral: crate::pac::TWI,
@@ -102,11 +183,28 @@ pub type I2c0<CLOCK> = avr_hal_generic::i2c::I2c<
     CLOCK,
 >;
 #[cfg(any(feature = "atmega328pb"))]
+pub type I2cTarget0 = avr_hal_generic::i2c::I2cTarget<
+    crate::Atmega,
+    crate::pac::TWI0,
+    port::Pin<port::mode::Input, port::PC4>,
+    port::Pin<port::mode::Input, port::PC5>,
+>;
+#[cfg(any(feature = "atmega328pb"))]
+pub type InterruptI2c0<CLOCK, const N: usize> = avr_hal_generic::i2c::InterruptI2c<
+    crate::Atmega,
+    crate::pac::TWI0,
+    port::Pin<port::mode::Input, port::PC4>,
+    port::Pin<port::mode::Input, port::PC5>,
+    CLOCK,
+    N,
+>;
+#[cfg(any(feature = "atmega328pb"))]
 avr_hal_generic::impl_i2c_twi! {
     hal: crate::Atmega,
     peripheral: crate::pac::TWI0,
     sda: port::PC4,
     scl: port::PC5,
+    address_mask_register: twamr,
 }
 #[cfg(any(feature = "atmega328pb"))]
 pub type I2c1<CLOCK> = avr_hal_generic::i2c::I2c<
@@ -117,11 +215,28 @@ pub type I2c1<CLOCK> = avr_hal_generic::i2c::I2c<
     CLOCK,
 >;
 #[cfg(any(feature = "atmega328pb"))]
+pub type I2cTarget1 = avr_hal_generic::i2c::I2cTarget<
+    crate::Atmega,
+    crate::pac::TWI1,
+    port::Pin<port::mode::Input, port::PE0>,
+    port::Pin<port::mode::Input, port::PE1>,
+>;
+#[cfg(any(feature = "atmega328pb"))]
+pub type InterruptI2c1<CLOCK, const N: usize> = avr_hal_generic::i2c::InterruptI2c<
+    crate::Atmega,
+    crate::pac::TWI1,
+    port::Pin<port::mode::Input, port::PE0>,
+    port::Pin<port::mode::Input, port::PE1>,
+    CLOCK,
+    N,
+>;
+#[cfg(any(feature = "atmega328pb"))]
 avr_hal_generic::impl_i2c_twi! {
     hal: crate::Atmega,
     peripheral: crate::pac::TWI1,
     sda: port::PE0,
     scl: port::PE1,
+    address_mask_register: twamr,
 }
 
 #[cfg(any(feature = "atmega1284p", feature = "atmega32a"))]
@@ -133,9 +248,320 @@ pub type I2c<CLOCK> = avr_hal_generic::i2c::I2c<
     CLOCK,
 >;
 #[cfg(any(feature = "atmega1284p", feature = "atmega32a"))]
+pub type I2cTarget = avr_hal_generic::i2c::I2cTarget<
+    crate::Atmega,
+    crate::pac::TWI,
+    port::Pin<port::mode::Input, port::PC1>,
+    port::Pin<port::mode::Input, port::PC0>,
+>;
+#[cfg(any(feature = "atmega1284p", feature = "atmega32a"))]
+pub type InterruptI2c<CLOCK, const N: usize> = avr_hal_generic::i2c::InterruptI2c<
+    crate::Atmega,
+    crate::pac::TWI,
+    port::Pin<port::mode::Input, port::PC1>,
+    port::Pin<port::mode::Input, port::PC0>,
+    CLOCK,
+    N,
+>;
+#[cfg(feature = "atmega1284p")]
 avr_hal_generic::impl_i2c_twi! {
     hal: crate::Atmega,
     peripheral: crate::pac::TWI,
     sda: port::PC1,
     scl: port::PC0,
+    address_mask_register: twamr,
+}
+#[cfg(feature = "atmega32a")]
+avr_hal_generic::impl_i2c_twi! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TWI,
+    sda: port::PC1,
+    scl: port::PC0,
+}
+
+/// Create the transfer buffer of an [`InterruptI2c`] and install its `TWI` interrupt handler.
+///
+/// The capacity of the buffer is given as a parameter.  It must hold all bytes written and read
+/// in one transaction.  The crate invoking this macro needs `#![feature(abi_avr_interrupt)]` and
+/// must not define its own handler for this interrupt.
+///
+/// # Example
+/// ```ignore
+/// atmega_hal::impl_interrupt_i2c! {
+///     static I2C_BUFFER: TWI<32>;
+/// }
+///
+/// let i2c = I2c::new(dp.TWI, sda, scl, 50_000);
+/// let mut i2c = InterruptI2c::new(i2c, &I2C_BUFFER);
+/// ```
+#[cfg(any(
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega48p",
+    feature = "atmega328p",
+    feature = "atmega328pb",
+    feature = "atmega1284p",
+    feature = "atmega164pa",
+    feature = "atmega32u4",
+    feature = "atmega128a",
+    feature = "atmega1280",
+    feature = "atmega2560",
+    feature = "atmega8",
+    feature = "atmega32a"
+))]
+#[macro_export]
+macro_rules! impl_interrupt_i2c {
+    ($vis:vis static $name:ident: $I2C:ident<$n:literal>;) => {
+        $crate::__twi_interrupts! {
+            $I2C => impl_interrupt_i2c { $vis static $name: [$n], }
+        }
+    };
+}
+
+// Pins and interrupt vector of each TWI peripheral, looked up by the macro above.  `$m` names the
+// `avr_hal_generic` macro which receives them together with `$args`.
+#[cfg(feature = "atmega88p")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __twi_interrupts {
+    (TWI => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega88p,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: TWI: $crate::pac::TWI,
+            sda: $crate::port::PC4,
+            scl: $crate::port::PC5,
+            vector: TWI,
+            $($args)*
+        }
+    };
+}
+
+#[cfg(feature = "atmega168")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __twi_interrupts {
+    (TWI => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega168,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: TWI: $crate::pac::TWI,
+            sda: $crate::port::PC4,
+            scl: $crate::port::PC5,
+            vector: TWI,
+            $($args)*
+        }
+    };
+}
+
+#[cfg(feature = "atmega48p")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __twi_interrupts {
+    (TWI => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega48p,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: TWI: $crate::pac::TWI,
+            sda: $crate::port::PC4,
+            scl: $crate::port::PC5,
+            vector: TWI,
+            $($args)*
+        }
+    };
+}
+
+#[cfg(feature = "atmega328p")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __twi_interrupts {
+    (TWI => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega328p,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: TWI: $crate::pac::TWI,
+            sda: $crate::port::PC4,
+            scl: $crate::port::PC5,
+            vector: TWI,
+            $($args)*
+        }
+    };
+}
+
+#[cfg(feature = "atmega328pb")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __twi_interrupts {
+    (TWI0 => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega328pb,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: TWI0: $crate::pac::TWI0,
+            sda: $crate::port::PC4,
+            scl: $crate::port::PC5,
+            vector: TWI0,
+            $($args)*
+        }
+    };
+    (TWI1 => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega328pb,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: TWI1: $crate::pac::TWI1,
+            sda: $crate::port::PE0,
+            scl: $crate::port::PE1,
+            vector: TWI1,
+            $($args)*
+        }
+    };
+}
+
+#[cfg(feature = "atmega1284p")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __twi_interrupts {
+    (TWI => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega1284p,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: TWI: $crate::pac::TWI,
+            sda: $crate::port::PC1,
+            scl: $crate::port::PC0,
+            vector: TWI,
+            $($args)*
+        }
+    };
+}
+
+#[cfg(feature = "atmega164pa")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __twi_interrupts {
+    (TWI => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega164pa,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: TWI: $crate::pac::TWI,
+            sda: $crate::port::PC1,
+            scl: $crate::port::PC0,
+            vector: TWI,
+            $($args)*
+        }
+    };
+}
+
+#[cfg(feature = "atmega32u4")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __twi_interrupts {
+    (TWI => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega32u4,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: TWI: $crate::pac::TWI,
+            sda: $crate::port::PD1,
+            scl: $crate::port::PD0,
+            vector: TWI,
+            $($args)*
+        }
+    };
+}
+
+#[cfg(feature = "atmega128a")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __twi_interrupts {
+    (TWI => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega128a,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: TWI: $crate::pac::TWI,
+            sda: $crate::port::PD1,
+            scl: $crate::port::PD0,
+            vector: TWI,
+            $($args)*
+        }
+    };
+}
+
+#[cfg(feature = "atmega1280")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __twi_interrupts {
+    (TWI => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega1280,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: TWI: $crate::pac::TWI,
+            sda: $crate::port::PD1,
+            scl: $crate::port::PD0,
+            vector: TWI,
+            $($args)*
+        }
+    };
+}
+
+#[cfg(feature = "atmega2560")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __twi_interrupts {
+    (TWI => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega2560,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: TWI: $crate::pac::TWI,
+            sda: $crate::port::PD1,
+            scl: $crate::port::PD0,
+            vector: TWI,
+            $($args)*
+        }
+    };
+}
+
+#[cfg(feature = "atmega8")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __twi_interrupts {
+    (TWI => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega8,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: TWI: $crate::pac::TWI,
+            sda: $crate::port::PC4,
+            scl: $crate::port::PC5,
+            vector: TWI,
+            $($args)*
+        }
+    };
+}
+
+#[cfg(feature = "atmega32a")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __twi_interrupts {
+    (TWI => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega32a,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: TWI: $crate::pac::TWI,
+            sda: $crate::port::PC1,
+            scl: $crate::port::PC0,
+            vector: TWI,
+            $($args)*
+        }
+    };
 }
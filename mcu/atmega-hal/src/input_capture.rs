@@ -0,0 +1,174 @@
+//! Input Capture
+//!
+//! # Example
+//!
+//! ```
+//! let dp = atmega_hal::Peripherals::take().unwrap();
+//! let pins = atmega_hal::pins!(dp);
+//!
+//! let mut capture = InputCapture::<_, _, atmega_hal::clock::MHz16>::new(
+//!     dp.TC1,
+//!     pins.pb0.into_floating_input(),
+//!     Settings::default(),
+//! );
+//!
+//! let pulse = capture.pulse_width_blocking();
+//! let pulse_us = capture.ticks_to_micros(pulse);
+//! ```
+
+#[allow(unused_imports)]
+use crate::port;
+pub use avr_hal_generic::input_capture::{Capture, Edge, InputCaptureOps, Measurement, Settings};
+
+/// Check the [`avr_hal_generic::input_capture::InputCapture`] documentation.
+pub type InputCapture<TC, ICP, CLOCK> =
+    avr_hal_generic::input_capture::InputCapture<crate::Atmega, TC, ICP, CLOCK>;
+
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb"
+))]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    pin: port::PB0,
+    icr: icr1,
+    tccrb: tccr1b { edge: ices1, noise_canceler: icnc1 },
+    timsk: timsk1 { capture: icie1 },
+    tifr: tifr1 { capture: icf1 },
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560", feature = "atmega32u4"))]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    pin: port::PD4,
+    icr: icr1,
+    tccrb: tccr1b { edge: ices1, noise_canceler: icnc1 },
+    timsk: timsk1 { capture: icie1 },
+    tifr: tifr1 { capture: icf1 },
+}
+
+#[cfg(any(feature = "atmega164pa", feature = "atmega1284p"))]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    pin: port::PD6,
+    icr: icr1,
+    tccrb: tccr1b { edge: ices1, noise_canceler: icnc1 },
+    timsk: timsk1 { capture: icie1 },
+    tifr: tifr1 { capture: icf1 },
+}
+
+#[cfg(feature = "atmega8")]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    pin: port::PB0,
+    icr: icr1,
+    tccrb: tccr1b { edge: ices1, noise_canceler: icnc1 },
+    timsk: timsk { capture: ticie1 },
+    tifr: tifr { capture: icf1 },
+}
+
+#[cfg(feature = "atmega32a")]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    pin: port::PD6,
+    icr: icr1,
+    tccrb: tccr1b { edge: ices1, noise_canceler: icnc1 },
+    timsk: timsk { capture: ticie1 },
+    tifr: tifr { capture: icf1 },
+}
+
+#[cfg(feature = "atmega128a")]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    pin: port::PD4,
+    icr: icr1,
+    tccrb: tccr1b { edge: ices1, noise_canceler: icnc1 },
+    timsk: timsk { capture: ticie1 },
+    tifr: tifr { capture: icf1 },
+}
+
+#[cfg(feature = "atmega328pb")]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    pin: port::PE2,
+    icr: icr3,
+    tccrb: tccr3b { edge: ices3, noise_canceler: icnc3 },
+    timsk: timsk3 { capture: icie3 },
+    tifr: tifr3 { capture: icf3 },
+}
+
+#[cfg(feature = "atmega32u4")]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    pin: port::PC7,
+    icr: icr3,
+    tccrb: tccr3b { edge: ices3, noise_canceler: icnc3 },
+    timsk: timsk3 { capture: icie3 },
+    tifr: tifr3 { capture: icf3 },
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    pin: port::PE7,
+    icr: icr3,
+    tccrb: tccr3b { edge: ices3, noise_canceler: icnc3 },
+    timsk: timsk3 { capture: icie3 },
+    tifr: tifr3 { capture: icf3 },
+}
+
+#[cfg(feature = "atmega1284p")]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    pin: port::PB5,
+    icr: icr3,
+    tccrb: tccr3b { edge: ices3, noise_canceler: icnc3 },
+    timsk: timsk3 { capture: icie3 },
+    tifr: tifr3 { capture: icf3 },
+}
+
+#[cfg(feature = "atmega328pb")]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC4,
+    pin: port::PE0,
+    icr: icr4,
+    tccrb: tccr4b { edge: ices4, noise_canceler: icnc4 },
+    timsk: timsk4 { capture: icie4 },
+    tifr: tifr4 { capture: icf4 },
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC4,
+    pin: port::PL0,
+    icr: icr4,
+    tccrb: tccr4b { edge: ices4, noise_canceler: icnc4 },
+    timsk: timsk4 { capture: icie4 },
+    tifr: tifr4 { capture: icf4 },
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC5,
+    pin: port::PL1,
+    icr: icr5,
+    tccrb: tccr5b { edge: ices5, noise_canceler: icnc5 },
+    timsk: timsk5 { capture: icie5 },
+    tifr: tifr5 { capture: icf5 },
+}
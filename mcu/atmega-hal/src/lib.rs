@@ -106,18 +106,25 @@ pub use avr_device::atmega88p as pac;
 #[cfg(feature = "rt")]
 pub use avr_device::entry;
 
+#[doc(hidden)]
+pub use avr_hal_generic;
+
 #[cfg(feature = "device-selected")]
 pub use pac::Peripherals;
 
 pub use avr_hal_generic::clock;
 pub use avr_hal_generic::delay;
 pub use avr_hal_generic::prelude;
+pub use avr_hal_generic::servo;
 
 #[cfg(feature = "device-selected")]
 pub mod adc;
 #[cfg(feature = "device-selected")]
 pub use adc::Adc;
 
+#[cfg(feature = "device-selected")]
+pub mod exint;
+
 #[cfg(feature = "device-selected")]
 pub mod i2c;
 #[cfg(feature = "device-selected")]
@@ -128,6 +135,12 @@ pub mod spi;
 #[cfg(feature = "device-selected")]
 pub use spi::Spi;
 
+#[cfg(feature = "device-selected")]
+pub mod input_capture;
+
+#[cfg(feature = "device-selected")]
+pub mod pcint;
+
 #[cfg(feature = "device-selected")]
 pub mod port;
 #[cfg(feature = "device-selected")]
@@ -141,6 +154,15 @@ pub mod usart;
 #[cfg(feature = "device-selected")]
 pub use usart::Usart;
 
+#[cfg(feature = "device-selected")]
+pub mod usart_spi;
+
+#[cfg(feature = "device-selected")]
+pub mod timer;
+
+#[cfg(feature = "device-selected")]
+pub mod tone;
+
 #[cfg(feature = "device-selected")]
 pub mod wdt;
 #[cfg(feature = "device-selected")]
@@ -0,0 +1,46 @@
+//! Pin-Change Interrupts
+//!
+//! # Example
+//!
+//! ```
+//! let dp = atmega_hal::Peripherals::take().unwrap();
+//! let pins = atmega_hal::pins!(dp);
+//! let mut exint = dp.EXINT;
+//!
+//! let a = pins.pd2.into_pull_up_input();
+//! let b = pins.pd3.into_pull_up_input();
+//!
+//! let mut bank = PcintBank::new(&mut exint);
+//! bank.listen(&a);
+//! bank.listen(&b);
+//!
+//! // In the interrupt handler of the bank:
+//! let changes = bank.changed();
+//! if changes.contains(&a) {
+//!     // ...
+//! }
+//! ```
+
+pub use avr_hal_generic::pcint::{PcintBankOps, PcintChanges, PcintPin};
+
+/// Check the [`avr_hal_generic::pcint::PcintBank`] documentation.
+pub type PcintBank<BANK> =
+    avr_hal_generic::pcint::PcintBank<crate::Atmega, crate::pac::EXINT, BANK>;
+
+// The banks are defined next to the ports in `crate::port`, together with the mapping of pins.
+
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega1280",
+    feature = "atmega2560"
+))]
+pub use crate::port::{Pcint0, Pcint1, Pcint2};
+
+#[cfg(any(feature = "atmega164pa", feature = "atmega328pb", feature = "atmega1284p"))]
+pub use crate::port::{Pcint0, Pcint1, Pcint2, Pcint3};
+
+#[cfg(feature = "atmega32u4")]
+pub use crate::port::Pcint0;
@@ -33,6 +33,46 @@ avr_hal_generic::impl_port_traditional! {
     }
 }
 
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p"
+))]
+avr_hal_generic::impl_pcint! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::EXINT,
+    banks: {
+        /// Pin-change interrupt bank `PCINT0`
+        Pcint0: {
+            control: pcicr[0],
+            flag: pcifr[0],
+            mask: pcmsk0,
+            pins: {
+                B: [0 => 0, 1 => 1, 2 => 2, 3 => 3, 4 => 4, 5 => 5, 6 => 6, 7 => 7],
+            },
+        },
+        /// Pin-change interrupt bank `PCINT1`
+        Pcint1: {
+            control: pcicr[1],
+            flag: pcifr[1],
+            mask: pcmsk1,
+            pins: {
+                C: [0 => 0, 1 => 1, 2 => 2, 3 => 3, 4 => 4, 5 => 5, 6 => 6],
+            },
+        },
+        /// Pin-change interrupt bank `PCINT2`
+        Pcint2: {
+            control: pcicr[2],
+            flag: pcifr[2],
+            mask: pcmsk2,
+            pins: {
+                D: [0 => 0, 1 => 1, 2 => 2, 3 => 3, 4 => 4, 5 => 5, 6 => 6, 7 => 7],
+            },
+        },
+    },
+}
+
 #[cfg(any(feature = "atmega164pa"))]
 avr_hal_generic::impl_port_traditional! {
     enum Ports {
@@ -43,6 +83,50 @@ avr_hal_generic::impl_port_traditional! {
     }
 }
 
+#[cfg(any(feature = "atmega164pa", feature = "atmega1284p"))]
+avr_hal_generic::impl_pcint! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::EXINT,
+    banks: {
+        /// Pin-change interrupt bank `PCINT0`
+        Pcint0: {
+            control: pcicr[0],
+            flag: pcifr[0],
+            mask: pcmsk0,
+            pins: {
+                A: [0 => 0, 1 => 1, 2 => 2, 3 => 3, 4 => 4, 5 => 5, 6 => 6, 7 => 7],
+            },
+        },
+        /// Pin-change interrupt bank `PCINT1`
+        Pcint1: {
+            control: pcicr[1],
+            flag: pcifr[1],
+            mask: pcmsk1,
+            pins: {
+                B: [0 => 0, 1 => 1, 2 => 2, 3 => 3, 4 => 4, 5 => 5, 6 => 6, 7 => 7],
+            },
+        },
+        /// Pin-change interrupt bank `PCINT2`
+        Pcint2: {
+            control: pcicr[2],
+            flag: pcifr[2],
+            mask: pcmsk2,
+            pins: {
+                C: [0 => 0, 1 => 1, 2 => 2, 3 => 3, 4 => 4, 5 => 5, 6 => 6, 7 => 7],
+            },
+        },
+        /// Pin-change interrupt bank `PCINT3`
+        Pcint3: {
+            control: pcicr[3],
+            flag: pcifr[3],
+            mask: pcmsk3,
+            pins: {
+                D: [0 => 0, 1 => 1, 2 => 2, 3 => 3, 4 => 4, 5 => 5, 6 => 6, 7 => 7],
+            },
+        },
+    },
+}
+
 #[cfg(feature = "atmega328pb")]
 avr_hal_generic::impl_port_traditional! {
     enum Ports {
@@ -53,6 +137,50 @@ avr_hal_generic::impl_port_traditional! {
     }
 }
 
+#[cfg(feature = "atmega328pb")]
+avr_hal_generic::impl_pcint! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::EXINT,
+    banks: {
+        /// Pin-change interrupt bank `PCINT0`
+        Pcint0: {
+            control: pcicr[0],
+            flag: pcifr[0],
+            mask: pcmsk0,
+            pins: {
+                B: [0 => 0, 1 => 1, 2 => 2, 3 => 3, 4 => 4, 5 => 5, 6 => 6, 7 => 7],
+            },
+        },
+        /// Pin-change interrupt bank `PCINT1`
+        Pcint1: {
+            control: pcicr[1],
+            flag: pcifr[1],
+            mask: pcmsk1,
+            pins: {
+                C: [0 => 0, 1 => 1, 2 => 2, 3 => 3, 4 => 4, 5 => 5, 6 => 6],
+            },
+        },
+        /// Pin-change interrupt bank `PCINT2`
+        Pcint2: {
+            control: pcicr[2],
+            flag: pcifr[2],
+            mask: pcmsk2,
+            pins: {
+                D: [0 => 0, 1 => 1, 2 => 2, 3 => 3, 4 => 4, 5 => 5, 6 => 6, 7 => 7],
+            },
+        },
+        /// Pin-change interrupt bank `PCINT3`
+        Pcint3: {
+            control: pcicr[3],
+            flag: pcifr[3],
+            mask: pcmsk3,
+            pins: {
+                E: [0 => 0, 1 => 1, 2 => 2, 3 => 3],
+            },
+        },
+    },
+}
+
 #[cfg(feature = "atmega32u4")]
 avr_hal_generic::impl_port_traditional! {
     enum Ports {
@@ -64,6 +192,23 @@ avr_hal_generic::impl_port_traditional! {
     }
 }
 
+#[cfg(feature = "atmega32u4")]
+avr_hal_generic::impl_pcint! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::EXINT,
+    banks: {
+        /// Pin-change interrupt bank `PCINT0`
+        Pcint0: {
+            control: pcicr[0],
+            flag: pcifr[0],
+            mask: pcmsk0,
+            pins: {
+                B: [0 => 0, 1 => 1, 2 => 2, 3 => 3, 4 => 4, 5 => 5, 6 => 6, 7 => 7],
+            },
+        },
+    },
+}
+
 #[cfg(any(feature = "atmega128a"))]
 avr_hal_generic::impl_port_traditional! {
     enum Ports {
@@ -94,6 +239,42 @@ avr_hal_generic::impl_port_traditional! {
     }
 }
 
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_pcint! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::EXINT,
+    banks: {
+        /// Pin-change interrupt bank `PCINT0`
+        Pcint0: {
+            control: pcicr[0],
+            flag: pcifr[0],
+            mask: pcmsk0,
+            pins: {
+                B: [0 => 0, 1 => 1, 2 => 2, 3 => 3, 4 => 4, 5 => 5, 6 => 6, 7 => 7],
+            },
+        },
+        /// Pin-change interrupt bank `PCINT1`
+        Pcint1: {
+            control: pcicr[1],
+            flag: pcifr[1],
+            mask: pcmsk1,
+            pins: {
+                E: [0 => 0],
+                J: [0 => 1, 1 => 2, 2 => 3, 3 => 4, 4 => 5, 5 => 6, 6 => 7],
+            },
+        },
+        /// Pin-change interrupt bank `PCINT2`
+        Pcint2: {
+            control: pcicr[2],
+            flag: pcifr[2],
+            mask: pcmsk2,
+            pins: {
+                K: [0 => 0, 1 => 1, 2 => 2, 3 => 3, 4 => 4, 5 => 5, 6 => 6, 7 => 7],
+            },
+        },
+    },
+}
+
 #[cfg(any(feature = "atmega1284p", feature = "atmega32a"))]
 avr_hal_generic::impl_port_traditional! {
     enum Ports {
@@ -1,4 +1,4 @@
-pub use avr_hal_generic::simple_pwm::{IntoPwmPin, Prescaler, PwmPinOps};
+pub use avr_hal_generic::simple_pwm::{IntoPwmPin, Prescaler, Pwm16Mode, PwmPinOps};
 
 #[allow(unused_imports)]
 use crate::port::*;
@@ -115,6 +115,67 @@ avr_hal_generic::impl_simple_pwm! {
     }
 }
 
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb"
+))]
+avr_hal_generic::impl_simple_pwm_16! {
+    /// Use `TC1` for 16-bit PWM with `ICR1` as TOP (pins `PB1`, `PB2`)
+    ///
+    /// # Example
+    /// ```
+    /// let mut timer1 =
+    ///     Timer1Pwm16::with_frequency::<MHz16>(dp.TC1, Pwm16Mode::Fast, 20_000).unwrap();
+    ///
+    /// let mut d9 = pins.d9.into_output().into_pwm(&mut timer1);
+    /// let mut d10 = pins.d10.into_output().into_pwm(&mut timer1);
+    ///
+    /// // 25% duty cycle
+    /// d9.set_duty(timer1.top() / 4);
+    /// d9.enable();
+    /// ```
+    pub struct Timer1Pwm16 {
+        timer: crate::pac::TC1,
+        icr: icr1,
+        init: |tim, wgm, prescaler| {
+            tim.tccr1a.modify(|_r, w| w.wgm1().bits(wgm & 0b11));
+            tim.tccr1b.modify(|_r, w| {
+                w.wgm1().bits(wgm >> 2);
+
+                match prescaler {
+                    Prescaler::Direct => w.cs1().direct(),
+                    Prescaler::Prescale8 => w.cs1().prescale_8(),
+                    Prescaler::Prescale64 => w.cs1().prescale_64(),
+                    Prescaler::Prescale256 => w.cs1().prescale_256(),
+                    Prescaler::Prescale1024 => w.cs1().prescale_1024(),
+                }
+            });
+        },
+        pins: {
+            PB1: {
+                ocr: ocr1a,
+                into_pwm: |tim| if enable {
+                    tim.tccr1a.modify(|_r, w| w.com1a().match_clear());
+                } else {
+                    tim.tccr1a.modify(|_r, w| w.com1a().disconnected());
+                },
+            },
+
+            PB2: {
+                ocr: ocr1b,
+                into_pwm: |tim| if enable {
+                    tim.tccr1a.modify(|_r, w| w.com1b().match_clear());
+                } else {
+                    tim.tccr1a.modify(|_r, w| w.com1b().disconnected());
+                },
+            },
+        },
+    }
+}
+
 #[cfg(any(
     feature = "atmega48p",
     feature = "atmega88p",
@@ -210,6 +271,48 @@ avr_hal_generic::impl_simple_pwm! {
     }
 }
 
+#[cfg(feature = "atmega328pb")]
+avr_hal_generic::impl_simple_pwm_16! {
+    /// Use `TC3` for 16-bit PWM with `ICR3` as TOP (pins `PD0`, `PD2`)
+    pub struct Timer3Pwm16 {
+        timer: crate::pac::TC3,
+        icr: icr3,
+        init: |tim, wgm, prescaler| {
+            tim.tccr3a.modify(|_r, w| w.wgm3().bits(wgm & 0b11));
+            tim.tccr3b.modify(|_r, w| {
+                unsafe { w.wgm3().bits(wgm >> 2) };
+
+                match prescaler {
+                    Prescaler::Direct => w.cs3().direct(),
+                    Prescaler::Prescale8 => w.cs3().prescale_8(),
+                    Prescaler::Prescale64 => w.cs3().prescale_64(),
+                    Prescaler::Prescale256 => w.cs3().prescale_256(),
+                    Prescaler::Prescale1024 => w.cs3().prescale_1024(),
+                }
+            });
+        },
+        pins: {
+            PD0: {
+                ocr: ocr3a,
+                into_pwm: |tim| if enable {
+                    tim.tccr3a.modify(|_r, w| w.com3a().match_clear());
+                } else {
+                    tim.tccr3a.modify(|_r, w| w.com3a().disconnected());
+                },
+            },
+
+            PD2: {
+                ocr: ocr3b,
+                into_pwm: |tim| if enable {
+                    tim.tccr3a.modify(|_r, w| w.com3b().match_clear());
+                } else {
+                    tim.tccr3a.modify(|_r, w| w.com3b().disconnected());
+                },
+            },
+        },
+    }
+}
+
 #[cfg(feature = "atmega328pb")]
 avr_hal_generic::impl_simple_pwm! {
     /// Use `TC4` for PWM (pins `PD1`, `PD2`)
@@ -251,6 +354,48 @@ avr_hal_generic::impl_simple_pwm! {
     }
 }
 
+#[cfg(feature = "atmega328pb")]
+avr_hal_generic::impl_simple_pwm_16! {
+    /// Use `TC4` for 16-bit PWM with `ICR4` as TOP (pins `PD1`, `PD2`)
+    pub struct Timer4Pwm16 {
+        timer: crate::pac::TC4,
+        icr: icr4,
+        init: |tim, wgm, prescaler| {
+            tim.tccr4a.modify(|_r, w| w.wgm4().bits(wgm & 0b11));
+            tim.tccr4b.modify(|_r, w| {
+                unsafe { w.wgm4().bits(wgm >> 2) };
+
+                match prescaler {
+                    Prescaler::Direct => w.cs4().direct(),
+                    Prescaler::Prescale8 => w.cs4().prescale_8(),
+                    Prescaler::Prescale64 => w.cs4().prescale_64(),
+                    Prescaler::Prescale256 => w.cs4().prescale_256(),
+                    Prescaler::Prescale1024 => w.cs4().prescale_1024(),
+                }
+            });
+        },
+        pins: {
+            PD1: {
+                ocr: ocr4a,
+                into_pwm: |tim| if enable {
+                    tim.tccr4a.modify(|_r, w| w.com4a().match_clear());
+                } else {
+                    tim.tccr4a.modify(|_r, w| w.com4a().disconnected());
+                },
+            },
+
+            PD2: {
+                ocr: ocr4b,
+                into_pwm: |tim| if enable {
+                    tim.tccr4a.modify(|_r, w| w.com4b().match_clear());
+                } else {
+                    tim.tccr4a.modify(|_r, w| w.com4b().disconnected());
+                },
+            },
+        },
+    }
+}
+
 #[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
 avr_hal_generic::impl_simple_pwm! {
     /// Use `TC0` for PWM (pins `PB7`, `PG5`)
@@ -357,6 +502,71 @@ avr_hal_generic::impl_simple_pwm! {
     }
 }
 
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_simple_pwm_16! {
+    /// Use `TC1` for 16-bit PWM with `ICR1` as TOP (pins `PB5`, `PB6`, `PB7`)
+    ///
+    /// # Example
+    /// ```
+    /// let mut timer1 =
+    ///     Timer1Pwm16::with_frequency::<MHz16>(dp.TC1, Pwm16Mode::Fast, 20_000).unwrap();
+    ///
+    /// let mut d11 = pins.d11.into_output().into_pwm(&mut timer1);
+    /// let mut d12 = pins.d12.into_output().into_pwm(&mut timer1);
+    /// let mut d13 = pins.d13.into_output().into_pwm(&mut timer1);
+    ///
+    /// // 25% duty cycle
+    /// d11.set_duty(timer1.top() / 4);
+    /// d11.enable();
+    /// ```
+    pub struct Timer1Pwm16 {
+        timer: crate::pac::TC1,
+        icr: icr1,
+        init: |tim, wgm, prescaler| {
+            tim.tccr1a.modify(|_r, w| w.wgm1().bits(wgm & 0b11));
+            tim.tccr1b.modify(|_r, w| {
+                w.wgm1().bits(wgm >> 2);
+
+                match prescaler {
+                    Prescaler::Direct => w.cs1().direct(),
+                    Prescaler::Prescale8 => w.cs1().prescale_8(),
+                    Prescaler::Prescale64 => w.cs1().prescale_64(),
+                    Prescaler::Prescale256 => w.cs1().prescale_256(),
+                    Prescaler::Prescale1024 => w.cs1().prescale_1024(),
+                }
+            });
+        },
+        pins: {
+            PB5: {
+                ocr: ocr1a,
+                into_pwm: |tim| if enable {
+                    tim.tccr1a.modify(|_r, w| w.com1a().match_clear());
+                } else {
+                    tim.tccr1a.modify(|_r, w| w.com1a().disconnected());
+                },
+            },
+
+            PB6: {
+                ocr: ocr1b,
+                into_pwm: |tim| if enable {
+                    tim.tccr1a.modify(|_r, w| w.com1b().match_clear());
+                } else {
+                    tim.tccr1a.modify(|_r, w| w.com1b().disconnected());
+                },
+            },
+
+            PB7: {
+                ocr: ocr1c,
+                into_pwm: |tim| if enable {
+                    tim.tccr1a.modify(|_r, w| w.com1c().match_clear());
+                } else {
+                    tim.tccr1a.modify(|_r, w| w.com1c().disconnected());
+                },
+            },
+        },
+    }
+}
+
 #[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
 avr_hal_generic::impl_simple_pwm! {
     /// Use `TC2` for PWM (pins `PB4`, `PH6`)
@@ -473,6 +683,72 @@ avr_hal_generic::impl_simple_pwm! {
     }
 }
 
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_simple_pwm_16! {
+    /// Use `TC3` for 16-bit PWM with `ICR3` as TOP (pins `PE3`, `PE4`, `PE5`)
+    ///
+    /// # Example
+    /// ```
+    /// let mut timer3 =
+    ///     Timer3Pwm16::with_frequency::<MHz16>(dp.TC3, Pwm16Mode::Fast, 20_000).unwrap();
+    ///
+    /// let mut d5 = pins.d5.into_output().into_pwm(&mut timer3);
+    /// let mut d2 = pins.d2.into_output().into_pwm(&mut timer3);
+    /// let mut d3 = pins.d3.into_output().into_pwm(&mut timer3);
+    ///
+    /// // 25% duty cycle
+    /// d5.set_duty(timer3.top() / 4);
+    /// d5.enable();
+    /// ```
+    pub struct Timer3Pwm16 {
+        timer: crate::pac::TC3,
+        icr: icr3,
+        init: |tim, wgm, prescaler| {
+            tim.tccr3a.modify(|_r, w| w.wgm3().bits(wgm & 0b11));
+            tim.tccr3b.modify(|_r, w| {
+                w.wgm3().bits(wgm >> 2);
+
+                match prescaler {
+                    Prescaler::Direct => w.cs3().direct(),
+                    Prescaler::Prescale8 => w.cs3().prescale_8(),
+                    Prescaler::Prescale64 => w.cs3().prescale_64(),
+                    Prescaler::Prescale256 => w.cs3().prescale_256(),
+                    Prescaler::Prescale1024 => w.cs3().prescale_1024(),
+                }
+            });
+        },
+        pins: {
+            PE3: {
+                ocr: ocr3a,
+                into_pwm: |tim| if enable {
+                    tim.tccr3a.modify(|_r, w| w.com3a().match_clear());
+                } else {
+                    tim.tccr3a.modify(|_r, w| w.com3a().disconnected());
+                },
+            },
+
+            PE4: {
+                ocr: ocr3b,
+                into_pwm: |tim| if enable {
+                    tim.tccr3a.modify(|_r, w| w.com3b().match_clear());
+                } else {
+                    tim.tccr3a.modify(|_r, w| w.com3b().disconnected());
+                },
+            },
+
+            PE5: {
+                ocr: ocr3c,
+                into_pwm: |tim| if enable {
+                    tim.tccr3a.modify(|_r, w| w.com3c().match_clear());
+                } else {
+                    tim.tccr3a.modify(|_r, w| w.com3c().disconnected());
+                },
+            },
+
+        },
+    }
+}
+
 #[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
 avr_hal_generic::impl_simple_pwm! {
     /// Use `TC4` for PWM (pins `PH3`, `PH4`, `PH5`)
@@ -537,26 +813,158 @@ avr_hal_generic::impl_simple_pwm! {
 }
 
 #[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
-avr_hal_generic::impl_simple_pwm! {
-    /// Use `TC5` for PWM (pins `PL3`, `PL4`, `PL5`)
+avr_hal_generic::impl_simple_pwm_16! {
+    /// Use `TC4` for 16-bit PWM with `ICR4` as TOP (pins `PH3`, `PH4`, `PH5`)
+    ///
+    /// # Example
+    /// ```
+    /// let mut timer4 =
+    ///     Timer4Pwm16::with_frequency::<MHz16>(dp.TC4, Pwm16Mode::Fast, 20_000).unwrap();
+    ///
+    /// let mut d6 = pins.d6.into_output().into_pwm(&mut timer4);
+    /// let mut d7 = pins.d7.into_output().into_pwm(&mut timer4);
+    /// let mut d8 = pins.d8.into_output().into_pwm(&mut timer4);
+    ///
+    /// // 25% duty cycle
+    /// d6.set_duty(timer4.top() / 4);
+    /// d6.enable();
+    /// ```
+    pub struct Timer4Pwm16 {
+        timer: crate::pac::TC4,
+        icr: icr4,
+        init: |tim, wgm, prescaler| {
+            tim.tccr4a.modify(|_r, w| w.wgm4().bits(wgm & 0b11));
+            tim.tccr4b.modify(|_r, w| {
+                w.wgm4().bits(wgm >> 2);
+
+                match prescaler {
+                    Prescaler::Direct => w.cs4().direct(),
+                    Prescaler::Prescale8 => w.cs4().prescale_8(),
+                    Prescaler::Prescale64 => w.cs4().prescale_64(),
+                    Prescaler::Prescale256 => w.cs4().prescale_256(),
+                    Prescaler::Prescale1024 => w.cs4().prescale_1024(),
+                }
+            });
+        },
+        pins: {
+            PH3: {
+                ocr: ocr4a,
+                into_pwm: |tim| if enable {
+                    tim.tccr4a.modify(|_r, w| w.com4a().match_clear());
+                } else {
+                    tim.tccr4a.modify(|_r, w| w.com4a().disconnected());
+                },
+            },
+
+            PH4: {
+                ocr: ocr4b,
+                into_pwm: |tim| if enable {
+                    tim.tccr4a.modify(|_r, w| w.com4b().match_clear());
+                } else {
+                    tim.tccr4a.modify(|_r, w| w.com4b().disconnected());
+                },
+            },
+
+            PH5: {
+                ocr: ocr4c,
+                into_pwm: |tim| if enable {
+                    tim.tccr4a.modify(|_r, w| w.com4c().match_clear());
+                } else {
+                    tim.tccr4a.modify(|_r, w| w.com4c().disconnected());
+                },
+            },
+
+        },
+    }
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_simple_pwm! {
+    /// Use `TC5` for PWM (pins `PL3`, `PL4`, `PL5`)
+    ///
+    /// # Example
+    /// ```
+    /// let mut timer5 = Timer5Pwm::new(dp.TC5, Prescaler::Prescale64);
+    ///
+    /// let mut d46 = pins.d46.into_output().into_pwm(&mut timer5);
+    /// let mut d45 = pins.d45.into_output().into_pwm(&mut timer5);
+    /// let mut d44 = pins.d44.into_output().into_pwm(&mut timer5);
+    ///
+    /// d46.set_duty(128);
+    /// d46.enable();
+    /// ```
+    pub struct Timer5Pwm {
+        timer: crate::pac::TC5,
+        init: |tim, prescaler| {
+            tim.tccr5a.modify(|_r, w| w.wgm5().bits(0b01));
+            tim.tccr5b.modify(|_r, w| {
+                w.wgm5().bits(0b01);
+
+                match prescaler {
+                    Prescaler::Direct => w.cs5().direct(),
+                    Prescaler::Prescale8 => w.cs5().prescale_8(),
+                    Prescaler::Prescale64 => w.cs5().prescale_64(),
+                    Prescaler::Prescale256 => w.cs5().prescale_256(),
+                    Prescaler::Prescale1024 => w.cs5().prescale_1024(),
+                }
+            });
+        },
+        pins: {
+            PL3: {
+                ocr: ocr5a,
+                into_pwm: |tim| if enable {
+                    tim.tccr5a.modify(|_r, w| w.com5a().match_clear());
+                } else {
+                    tim.tccr5a.modify(|_r, w| w.com5a().disconnected());
+                },
+            },
+
+            PL4: {
+                ocr: ocr5b,
+                into_pwm: |tim| if enable {
+                    tim.tccr5a.modify(|_r, w| w.com5b().match_clear());
+                } else {
+                    tim.tccr5a.modify(|_r, w| w.com5b().disconnected());
+                },
+            },
+
+            PL5: {
+                ocr: ocr5c,
+                into_pwm: |tim| if enable {
+                    tim.tccr5a.modify(|_r, w| w.com5c().match_clear());
+                } else {
+                    tim.tccr5a.modify(|_r, w| w.com5c().disconnected());
+                },
+            },
+
+        },
+    }
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_simple_pwm_16! {
+    /// Use `TC5` for 16-bit PWM with `ICR5` as TOP (pins `PL3`, `PL4`, `PL5`)
     ///
     /// # Example
     /// ```
-    /// let mut timer5 = Timer5Pwm::new(dp.TC5, Prescaler::Prescale64);
+    /// let mut timer5 =
+    ///     Timer5Pwm16::with_frequency::<MHz16>(dp.TC5, Pwm16Mode::Fast, 20_000).unwrap();
     ///
     /// let mut d46 = pins.d46.into_output().into_pwm(&mut timer5);
     /// let mut d45 = pins.d45.into_output().into_pwm(&mut timer5);
     /// let mut d44 = pins.d44.into_output().into_pwm(&mut timer5);
     ///
-    /// d46.set_duty(128);
+    /// // 25% duty cycle
+    /// d46.set_duty(timer5.top() / 4);
     /// d46.enable();
     /// ```
-    pub struct Timer5Pwm {
+    pub struct Timer5Pwm16 {
         timer: crate::pac::TC5,
-        init: |tim, prescaler| {
-            tim.tccr5a.modify(|_r, w| w.wgm5().bits(0b01));
+        icr: icr5,
+        init: |tim, wgm, prescaler| {
+            tim.tccr5a.modify(|_r, w| w.wgm5().bits(wgm & 0b11));
             tim.tccr5b.modify(|_r, w| {
-                w.wgm5().bits(0b01);
+                w.wgm5().bits(wgm >> 2);
 
                 match prescaler {
                     Prescaler::Direct => w.cs5().direct(),
@@ -707,6 +1115,71 @@ avr_hal_generic::impl_simple_pwm! {
     }
 }
 
+#[cfg(any(feature = "atmega32u4"))]
+avr_hal_generic::impl_simple_pwm_16! {
+    /// Use `TC1` for 16-bit PWM with `ICR1` as TOP (pins `PB5`, `PB6`, `PB7`)
+    ///
+    /// # Example
+    /// ```
+    /// let mut timer1 =
+    ///     Timer1Pwm16::with_frequency::<MHz16>(dp.TC1, Pwm16Mode::Fast, 20_000).unwrap();
+    ///
+    /// let mut d9 = pins.d9.into_output().into_pwm(&mut timer1);
+    /// let mut d10 = pins.d10.into_output().into_pwm(&mut timer1);
+    /// let mut d11 = pins.d11.into_output().into_pwm(&mut timer1);
+    ///
+    /// // 25% duty cycle
+    /// d9.set_duty(timer1.top() / 4);
+    /// d9.enable();
+    /// ```
+    pub struct Timer1Pwm16 {
+        timer: crate::pac::TC1,
+        icr: icr1,
+        init: |tim, wgm, prescaler| {
+            tim.tccr1a.modify(|_r, w| w.wgm1().bits(wgm & 0b11));
+            tim.tccr1b.modify(|_r, w| {
+                w.wgm1().bits(wgm >> 2);
+
+                match prescaler {
+                    Prescaler::Direct => w.cs1().direct(),
+                    Prescaler::Prescale8 => w.cs1().prescale_8(),
+                    Prescaler::Prescale64 => w.cs1().prescale_64(),
+                    Prescaler::Prescale256 => w.cs1().prescale_256(),
+                    Prescaler::Prescale1024 => w.cs1().prescale_1024(),
+                }
+            });
+        },
+        pins: {
+            PB5: {
+                ocr: ocr1a,
+                into_pwm: |tim| if enable {
+                    tim.tccr1a.modify(|_r, w| w.com1a().match_clear());
+                } else {
+                    tim.tccr1a.modify(|_r, w| w.com1a().disconnected());
+                },
+            },
+
+            PB6: {
+                ocr: ocr1b,
+                into_pwm: |tim| if enable {
+                    tim.tccr1a.modify(|_r, w| w.com1b().match_clear());
+                } else {
+                    tim.tccr1a.modify(|_r, w| w.com1b().disconnected());
+                },
+            },
+
+            PB7: {
+                ocr: ocr1c,
+                into_pwm: |tim| if enable {
+                    tim.tccr1a.modify(|_r, w| w.com1c().match_clear());
+                } else {
+                    tim.tccr1a.modify(|_r, w| w.com1c().disconnected());
+                },
+            },
+        },
+    }
+}
+
 #[cfg(any(feature = "atmega32u4"))]
 avr_hal_generic::impl_simple_pwm! {
     /// Use `TC3` for PWM (pins `PC6`)
@@ -747,6 +1220,51 @@ avr_hal_generic::impl_simple_pwm! {
     }
 }
 
+#[cfg(any(feature = "atmega32u4"))]
+avr_hal_generic::impl_simple_pwm_16! {
+    /// Use `TC3` for 16-bit PWM with `ICR3` as TOP (pins `PC6`)
+    ///
+    /// # Example
+    /// ```
+    /// let mut timer3 =
+    ///     Timer3Pwm16::with_frequency::<MHz16>(dp.TC3, Pwm16Mode::Fast, 20_000).unwrap();
+    ///
+    /// let mut d5 = pins.d5.into_output().into_pwm(&mut timer3);
+    ///
+    /// // 25% duty cycle
+    /// d5.set_duty(timer3.top() / 4);
+    /// d5.enable();
+    /// ```
+    pub struct Timer3Pwm16 {
+        timer: crate::pac::TC3,
+        icr: icr3,
+        init: |tim, wgm, prescaler| {
+            tim.tccr3a.modify(|_r, w| w.wgm3().bits(wgm & 0b11));
+            tim.tccr3b.modify(|_r, w| {
+                w.wgm3().bits(wgm >> 2);
+
+                match prescaler {
+                    Prescaler::Direct => w.cs3().direct(),
+                    Prescaler::Prescale8 => w.cs3().prescale_8(),
+                    Prescaler::Prescale64 => w.cs3().prescale_64(),
+                    Prescaler::Prescale256 => w.cs3().prescale_256(),
+                    Prescaler::Prescale1024 => w.cs3().prescale_1024(),
+                }
+            });
+        },
+        pins: {
+            PC6: {
+                ocr: ocr3a,
+                into_pwm: |tim| if enable {
+                    tim.tccr3a.modify(|_r, w| w.com3a().match_clear());
+                } else {
+                    tim.tccr3a.modify(|_r, w| w.com3a().disconnected());
+                },
+            },
+        },
+    }
+}
+
 #[cfg(any(feature = "atmega32u4"))]
 avr_hal_generic::impl_simple_pwm! {
     /// Use `TC4` for PWM (pins `PB6`, `PC7`, `PD7`)
@@ -908,6 +1426,61 @@ avr_hal_generic::impl_simple_pwm! {
     }
 }
 
+#[cfg(any(feature = "atmega1284p"))]
+avr_hal_generic::impl_simple_pwm_16! {
+    /// Use `TC1` for 16-bit PWM with `ICR1` as TOP (pins `PD5`, `PD4`)
+    ///
+    /// # Example
+    /// ```
+    /// let mut timer1 =
+    ///     Timer1Pwm16::with_frequency::<MHz16>(dp.TC1, Pwm16Mode::Fast, 20_000).unwrap();
+    ///
+    /// let mut d5 = pins.d5.into_output().into_pwm(&mut timer1);
+    /// let mut d4 = pins.d4.into_output().into_pwm(&mut timer1);
+    ///
+    /// // 25% duty cycle
+    /// d5.set_duty(timer1.top() / 4);
+    /// d5.enable();
+    /// ```
+    pub struct Timer1Pwm16 {
+        timer: crate::pac::TC1,
+        icr: icr1,
+        init: |tim, wgm, prescaler| {
+            tim.tccr1a.modify(|_r, w| w.wgm1().bits(wgm & 0b11));
+            tim.tccr1b.modify(|_r, w| {
+                w.wgm1().bits(wgm >> 2);
+
+                match prescaler {
+                    Prescaler::Direct => w.cs1().direct(),
+                    Prescaler::Prescale8 => w.cs1().prescale_8(),
+                    Prescaler::Prescale64 => w.cs1().prescale_64(),
+                    Prescaler::Prescale256 => w.cs1().prescale_256(),
+                    Prescaler::Prescale1024 => w.cs1().prescale_1024(),
+                }
+            });
+        },
+        pins: {
+            PD5: {
+                ocr: ocr1a,
+                into_pwm: |tim| if enable {
+                    tim.tccr1a.modify(|_r, w| w.com1a().match_clear());
+                } else {
+                    tim.tccr1a.modify(|_r, w| w.com1a().disconnected());
+                },
+            },
+
+            PD4: {
+                ocr: ocr1b,
+                into_pwm: |tim| if enable {
+                    tim.tccr1a.modify(|_r, w| w.com1b().match_clear());
+                } else {
+                    tim.tccr1a.modify(|_r, w| w.com1b().disconnected());
+                },
+            },
+        },
+    }
+}
+
 #[cfg(any(feature = "atmega1284p"))]
 avr_hal_generic::impl_simple_pwm! {
     /// Use `TC2` for PWM (pins `PD7`, `PD6`)
@@ -997,6 +1570,48 @@ avr_hal_generic::impl_simple_pwm! {
     }
 }
 
+#[cfg(any(feature = "atmega1284p"))]
+avr_hal_generic::impl_simple_pwm_16! {
+    /// Use `TC3` for 16-bit PWM with `ICR3` as TOP (pins `PB6`, `PB7`)
+    pub struct Timer3Pwm16 {
+        timer: crate::pac::TC3,
+        icr: icr3,
+        init: |tim, wgm, prescaler| {
+            tim.tccr3a.modify(|_r, w| w.wgm3().bits(wgm & 0b11));
+            tim.tccr3b.modify(|_r, w| {
+                w.wgm3().bits(wgm >> 2);
+
+                match prescaler {
+                    Prescaler::Direct => w.cs3().direct(),
+                    Prescaler::Prescale8 => w.cs3().prescale_8(),
+                    Prescaler::Prescale64 => w.cs3().prescale_64(),
+                    Prescaler::Prescale256 => w.cs3().prescale_256(),
+                    Prescaler::Prescale1024 => w.cs3().prescale_1024(),
+                }
+            });
+        },
+        pins: {
+            PB6: {
+                ocr: ocr3a,
+                into_pwm: |tim| if enable {
+                    tim.tccr3a.modify(|_r, w| w.com3a().match_clear());
+                } else {
+                    tim.tccr3a.modify(|_r, w| w.com3a().disconnected());
+                },
+            },
+
+            PB7: {
+                ocr: ocr3b,
+                into_pwm: |tim| if enable {
+                    tim.tccr3a.modify(|_r, w| w.com3b().match_clear());
+                } else {
+                    tim.tccr3a.modify(|_r, w| w.com3b().disconnected());
+                },
+            },
+        },
+    }
+}
+
 #[cfg(any(feature = "atmega8",))]
 avr_hal_generic::impl_simple_pwm! {
     /// Use `TC1` for PWM (pins `PB1`, `PB2`)
@@ -1049,6 +1664,61 @@ avr_hal_generic::impl_simple_pwm! {
     }
 }
 
+#[cfg(any(feature = "atmega8",))]
+avr_hal_generic::impl_simple_pwm_16! {
+    /// Use `TC1` for 16-bit PWM with `ICR1` as TOP (pins `PB1`, `PB2`)
+    ///
+    /// # Example
+    /// ```
+    /// let mut timer1 =
+    ///     Timer1Pwm16::with_frequency::<MHz16>(dp.TC1, Pwm16Mode::Fast, 20_000).unwrap();
+    ///
+    /// let mut b1 = pins.b1.into_output().into_pwm(&mut timer1);
+    /// let mut b2 = pins.b2.into_output().into_pwm(&mut timer1);
+    ///
+    /// // 25% duty cycle
+    /// b1.set_duty(timer1.top() / 4);
+    /// b1.enable();
+    /// ```
+    pub struct Timer1Pwm16 {
+        timer: crate::pac::TC1,
+        icr: icr1,
+        init: |tim, wgm, prescaler| {
+            tim.tccr1a.modify(|_r, w| w.wgm1().bits(wgm & 0b11));
+            tim.tccr1b.modify(|_r, w| {
+                w.wgm1().bits(wgm >> 2);
+
+                match prescaler {
+                    Prescaler::Direct => w.cs1().direct(),
+                    Prescaler::Prescale8 => w.cs1().prescale_8(),
+                    Prescaler::Prescale64 => w.cs1().prescale_64(),
+                    Prescaler::Prescale256 => w.cs1().prescale_256(),
+                    Prescaler::Prescale1024 => w.cs1().prescale_1024(),
+                }
+            });
+        },
+        pins: {
+            PB1: {
+                ocr: ocr1a,
+                into_pwm: |tim| if enable {
+                    tim.tccr1a.modify(|_r, w| w.com1a().match_clear());
+                } else {
+                    tim.tccr1a.modify(|_r, w| w.com1a().disconnected());
+                },
+            },
+
+            PB2: {
+                ocr: ocr1b,
+                into_pwm: |tim| if enable {
+                    tim.tccr1a.modify(|_r, w| w.com1b().match_clear());
+                } else {
+                    tim.tccr1a.modify(|_r, w| w.com1b().disconnected());
+                },
+            },
+        },
+    }
+}
+
 #[cfg(any(feature = "atmega8",))]
 avr_hal_generic::impl_simple_pwm! {
     /// Use `TC2` for PWM (pins `PB3`, `PD3`)
@@ -1178,3 +1848,57 @@ avr_hal_generic::impl_simple_pwm! {
         },
     }
 }
+
+#[cfg(any(feature = "atmega164pa"))]
+avr_hal_generic::impl_simple_pwm_16! {
+    /// Use `TC1` for 16-bit PWM with `ICR1` as TOP (pins `PD4`, `PD5`)
+    ///
+    /// # Example
+    /// ```
+    /// let mut timer1 =
+    ///     Timer1Pwm16::with_frequency::<MHz16>(dp.TC1, Pwm16Mode::Fast, 20_000).unwrap();
+    ///
+    /// let mut d4 = pins.pd4.into_output().into_pwm(&mut timer1);
+    /// let mut d5 = pins.pd5.into_output().into_pwm(&mut timer1);
+    ///
+    /// // 25% duty cycle
+    /// d4.set_duty(timer1.top() / 4);
+    /// d4.enable();
+    /// ```
+    pub struct Timer1Pwm16 {
+        timer: crate::pac::TC1,
+        icr: icr1,
+        init: |tim, wgm, prescaler| {
+            tim.tccr1a.modify(|_r, w| w.wgm1().bits(wgm & 0b11));
+            tim.tccr1b.modify(|_r, w| {
+                w.wgm1().bits(wgm >> 2);
+
+                match prescaler {
+                    Prescaler::Direct => w.cs1().running_no_prescaling(),
+                    Prescaler::Prescale8 => w.cs1().running_clk_8(),
+                    Prescaler::Prescale64 => w.cs1().running_clk_64(),
+                    Prescaler::Prescale256 => w.cs1().running_clk_256(),
+                    Prescaler::Prescale1024 => w.cs1().running_clk_1024(),
+                }
+            });
+        },
+        pins: {
+            PD4: {
+                ocr: ocr1a,
+                into_pwm: |tim| if enable {
+                    tim.tccr1a.modify(|_r, w| w.com1a().bits(0b11));
+                } else {
+                    tim.tccr1a.modify(|_r, w| w.com1a().bits(0b00));
+                },
+            },
+            PD5: {
+                ocr: ocr1b,
+                into_pwm: |tim| if enable {
+                    tim.tccr1a.modify(|_r, w| w.com1b().bits(0b11));
+                } else {
+                    tim.tccr1a.modify(|_r, w| w.com1b().bits(0b00));
+                },
+            },
+        },
+    }
+}
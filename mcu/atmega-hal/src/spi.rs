@@ -52,6 +52,20 @@ pub type Spi = avr_hal_generic::spi::Spi<
     feature = "atmega2560",
     feature = "atmega32u4"
 ))]
+pub type SpiPeripheral = avr_hal_generic::spi::SpiPeripheral<
+    crate::Atmega,
+    crate::pac::SPI,
+    port::PB1,
+    port::PB2,
+    port::PB3,
+    port::PB0,
+>;
+#[cfg(any(
+    feature = "atmega128a",
+    feature = "atmega1280",
+    feature = "atmega2560",
+    feature = "atmega32u4"
+))]
 avr_hal_generic::impl_spi! {
     hal: crate::Atmega,
     peripheral: crate::pac::SPI,
@@ -83,6 +97,21 @@ pub type Spi = avr_hal_generic::spi::Spi<
     feature = "atmega8",
     feature = "atmega88p"
 ))]
+pub type SpiPeripheral = avr_hal_generic::spi::SpiPeripheral<
+    crate::Atmega,
+    crate::pac::SPI,
+    port::PB5,
+    port::PB3,
+    port::PB4,
+    port::PB2,
+>;
+#[cfg(any(
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega48p",
+    feature = "atmega8",
+    feature = "atmega88p"
+))]
 avr_hal_generic::impl_spi! {
     hal: crate::Atmega,
     peripheral: crate::pac::SPI,
@@ -102,6 +131,15 @@ pub type Spi0 = avr_hal_generic::spi::Spi<
     port::PB2,
 >;
 #[cfg(feature = "atmega328pb")]
+pub type SpiPeripheral0 = avr_hal_generic::spi::SpiPeripheral<
+    crate::Atmega,
+    crate::pac::SPI0,
+    port::PB5,
+    port::PB3,
+    port::PB4,
+    port::PB2,
+>;
+#[cfg(feature = "atmega328pb")]
 avr_hal_generic::impl_spi! {
     hal: crate::Atmega,
     peripheral: crate::pac::SPI0,
@@ -120,6 +158,15 @@ pub type Spi1 = avr_hal_generic::spi::Spi<
     port::PE2,
 >;
 #[cfg(feature = "atmega328pb")]
+pub type SpiPeripheral1 = avr_hal_generic::spi::SpiPeripheral<
+    crate::Atmega,
+    crate::pac::SPI1,
+    port::PC1,
+    port::PE3,
+    port::PC0,
+    port::PE2,
+>;
+#[cfg(feature = "atmega328pb")]
 avr_hal_generic::impl_spi! {
     hal: crate::Atmega,
     peripheral: crate::pac::SPI1,
@@ -139,6 +186,15 @@ pub type Spi = avr_hal_generic::spi::Spi<
     port::PB4,
 >;
 #[cfg(any(feature = "atmega1284p", feature = "atmega32a"))]
+pub type SpiPeripheral = avr_hal_generic::spi::SpiPeripheral<
+    crate::Atmega,
+    crate::pac::SPI,
+    port::PB7,
+    port::PB5,
+    port::PB6,
+    port::PB4,
+>;
+#[cfg(any(feature = "atmega1284p", feature = "atmega32a"))]
 avr_hal_generic::impl_spi! {
     hal: crate::Atmega,
     peripheral: crate::pac::SPI,
@@ -0,0 +1,822 @@
+//! Timer/Counter
+//!
+//! # Example
+//!
+//! Complete example source code can be found in the repository:
+//! [`uno-timer.rs`](https://github.com/Rahix/avr-hal/blob/main/examples/arduino-uno/src/bin/uno-timer.rs)
+//!
+//! ```
+//! let dp = atmega_hal::Peripherals::take().unwrap();
+//!
+//! let mut timer1 = Timer1::<crate::CoreClock>::new(dp.TC1, Mode::Ctc, Prescaler::Prescale256);
+//! timer1.set_compare_a(15624);
+//! timer1.listen(Event::CompareMatchA);
+//! ```
+//!
+//! `TC0` of the ATmega8 is not supported, as it has no compare unit and can only count up to
+//! its overflow.
+
+pub use avr_hal_generic::timer::{Event, Mode, Prescaler, TimerOps};
+
+/// Check the [`avr_hal_generic::timer::Timer`] documentation.
+pub type Timer<TC, CLOCK> = avr_hal_generic::timer::Timer<crate::Atmega, TC, CLOCK>;
+
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb",
+    feature = "atmega1280",
+    feature = "atmega2560",
+    feature = "atmega1284p",
+    feature = "atmega32u4"
+))]
+/// 8-bit timer/counter `TC0`
+pub type Timer0<CLOCK> = Timer<crate::pac::TC0, CLOCK>;
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb",
+    feature = "atmega1280",
+    feature = "atmega2560",
+    feature = "atmega1284p",
+    feature = "atmega32u4"
+))]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC0,
+    counter: u8,
+    tcnt: tcnt0,
+    ocra: ocr0a,
+    ocrb: ocr0b,
+    timsk: timsk0 { overflow: toie0, compare_a: ocie0a, compare_b: ocie0b },
+    tifr: tifr0 { overflow: tov0, compare_a: ocf0a, compare_b: ocf0b },
+    set_mode: |tim, mode| {
+        tim.tccr0a.modify(|_r, w| match mode {
+            Mode::Normal => w.wgm0().normal_top(),
+            Mode::Ctc => w.wgm0().ctc(),
+            Mode::FastPwm => w.wgm0().pwm_fast(),
+            Mode::PhaseCorrectPwm => w.wgm0().pwm_phase(),
+        });
+        tim.tccr0b.modify(|_r, w| w.wgm02().clear_bit());
+    },
+    start: |tim, prescaler| {
+        tim.tccr0b.modify(|_r, w| match prescaler {
+            Prescaler::Direct => w.cs0().direct(),
+            Prescaler::Prescale8 => w.cs0().prescale_8(),
+            Prescaler::Prescale64 => w.cs0().prescale_64(),
+            Prescaler::Prescale256 => w.cs0().prescale_256(),
+            Prescaler::Prescale1024 => w.cs0().prescale_1024(),
+        });
+    },
+    stop: |tim| {
+        tim.tccr0b.modify(|_r, w| w.cs0().no_clock());
+    },
+}
+
+#[cfg(feature = "atmega164pa")]
+/// 8-bit timer/counter `TC0`
+pub type Timer0<CLOCK> = Timer<crate::pac::TC0, CLOCK>;
+#[cfg(feature = "atmega164pa")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC0,
+    counter: u8,
+    tcnt: tcnt0,
+    ocra: ocr0a,
+    ocrb: ocr0b,
+    timsk: timsk0 { overflow: toie0, compare_a: ocie0a, compare_b: ocie0b },
+    tifr: tifr0 { overflow: tov0, compare_a: ocf0a, compare_b: ocf0b },
+    set_mode: |tim, mode| {
+        tim.tccr0a.modify(|_r, w| match mode {
+            Mode::Normal => w.wgm0().bits(0b00),
+            Mode::Ctc => w.wgm0().bits(0b10),
+            Mode::FastPwm => w.wgm0().bits(0b11),
+            Mode::PhaseCorrectPwm => w.wgm0().bits(0b01),
+        });
+        tim.tccr0b.modify(|_r, w| w.wgm02().clear_bit());
+    },
+    start: |tim, prescaler| {
+        tim.tccr0b.modify(|_r, w| match prescaler {
+            Prescaler::Direct => w.cs0().running_no_prescaling(),
+            Prescaler::Prescale8 => w.cs0().running_clk_8(),
+            Prescaler::Prescale64 => w.cs0().running_clk_64(),
+            Prescaler::Prescale256 => w.cs0().running_clk_256(),
+            Prescaler::Prescale1024 => w.cs0().running_clk_1024(),
+        });
+    },
+    stop: |tim| {
+        tim.tccr0b.modify(|_r, w| w.cs0().no_clock_source_stopped());
+    },
+}
+
+#[cfg(feature = "atmega32a")]
+/// 8-bit timer/counter `TC0`
+///
+/// `TC0` only has a single compare register `OCR0`, which is used as compare register A.
+pub type Timer0<CLOCK> = Timer<crate::pac::TC0, CLOCK>;
+#[cfg(feature = "atmega32a")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC0,
+    counter: u8,
+    tcnt: tcnt0,
+    ocra: ocr0,
+    timsk: timsk { overflow: toie0, compare_a: ocie0 },
+    tifr: tifr { overflow: tov0, compare_a: ocf0 },
+    set_mode: |tim, mode| {
+        // WGM00 and WGM01 are not adjacent in TCCR0.
+        let (wgm0, wgm1) = match mode {
+            Mode::Normal => (false, false),
+            Mode::Ctc => (false, true),
+            Mode::FastPwm => (true, true),
+            Mode::PhaseCorrectPwm => (true, false),
+        };
+        tim.tccr0.modify(|_r, w| w.wgm00().bit(wgm0).wgm01().bit(wgm1));
+    },
+    start: |tim, prescaler| {
+        tim.tccr0.modify(|_r, w| match prescaler {
+            Prescaler::Direct => w.cs0().bits(0b001),
+            Prescaler::Prescale8 => w.cs0().bits(0b010),
+            Prescaler::Prescale64 => w.cs0().bits(0b011),
+            Prescaler::Prescale256 => w.cs0().bits(0b100),
+            Prescaler::Prescale1024 => w.cs0().bits(0b101),
+        });
+    },
+    stop: |tim| {
+        tim.tccr0.modify(|_r, w| w.cs0().bits(0b000));
+    },
+}
+
+#[cfg(feature = "atmega128a")]
+/// 8-bit timer/counter `TC0`
+///
+/// `TC0` only has a single compare register `OCR0`, which is used as compare register A.
+pub type Timer0<CLOCK> = Timer<crate::pac::TC0, CLOCK>;
+#[cfg(feature = "atmega128a")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC0,
+    counter: u8,
+    tcnt: tcnt0,
+    ocra: ocr0,
+    timsk: timsk { overflow: toie0, compare_a: ocie0 },
+    tifr: tifr { overflow: tov0, compare_a: ocf0 },
+    set_mode: |tim, mode| {
+        // WGM00 and WGM01 are not adjacent in TCCR0.
+        let (wgm0, wgm1) = match mode {
+            Mode::Normal => (false, false),
+            Mode::Ctc => (false, true),
+            Mode::FastPwm => (true, true),
+            Mode::PhaseCorrectPwm => (true, false),
+        };
+        tim.tccr0.modify(|_r, w| w.wgm00().bit(wgm0).wgm01().bit(wgm1));
+    },
+    start: |tim, prescaler| {
+        tim.tccr0.modify(|_r, w| match prescaler {
+            Prescaler::Direct => w.cs0().direct(),
+            Prescaler::Prescale8 => w.cs0().prescale_8(),
+            Prescaler::Prescale64 => w.cs0().prescale_64(),
+            Prescaler::Prescale256 => w.cs0().prescale_256(),
+            Prescaler::Prescale1024 => w.cs0().prescale_1024(),
+        });
+    },
+    stop: |tim| {
+        tim.tccr0.modify(|_r, w| w.cs0().no_clock());
+    },
+}
+
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb",
+    feature = "atmega1280",
+    feature = "atmega2560",
+    feature = "atmega1284p",
+    feature = "atmega32u4"
+))]
+/// 16-bit timer/counter `TC1`
+pub type Timer1<CLOCK> = Timer<crate::pac::TC1, CLOCK>;
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb",
+    feature = "atmega1280",
+    feature = "atmega2560",
+    feature = "atmega1284p",
+    feature = "atmega32u4"
+))]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    counter: u16,
+    tcnt: tcnt1,
+    ocra: ocr1a,
+    ocrb: ocr1b,
+    timsk: timsk1 { overflow: toie1, compare_a: ocie1a, compare_b: ocie1b },
+    tifr: tifr1 { overflow: tov1, compare_a: ocf1a, compare_b: ocf1b },
+    set_mode: |tim, mode| {
+        // WGM1[1:0] live in TCCR1A, WGM1[3:2] in TCCR1B.
+        let (wgm_a, wgm_b) = match mode {
+            Mode::Normal => (0b00, 0b00),
+            Mode::Ctc => (0b00, 0b01),
+            Mode::FastPwm => (0b01, 0b01),
+            Mode::PhaseCorrectPwm => (0b01, 0b00),
+        };
+        tim.tccr1a.modify(|_r, w| w.wgm1().bits(wgm_a));
+        tim.tccr1b.modify(|_r, w| w.wgm1().bits(wgm_b));
+    },
+    start: |tim, prescaler| {
+        tim.tccr1b.modify(|_r, w| match prescaler {
+            Prescaler::Direct => w.cs1().direct(),
+            Prescaler::Prescale8 => w.cs1().prescale_8(),
+            Prescaler::Prescale64 => w.cs1().prescale_64(),
+            Prescaler::Prescale256 => w.cs1().prescale_256(),
+            Prescaler::Prescale1024 => w.cs1().prescale_1024(),
+        });
+    },
+    stop: |tim| {
+        tim.tccr1b.modify(|_r, w| w.cs1().no_clock());
+    },
+}
+
+#[cfg(feature = "atmega164pa")]
+/// 16-bit timer/counter `TC1`
+pub type Timer1<CLOCK> = Timer<crate::pac::TC1, CLOCK>;
+#[cfg(feature = "atmega164pa")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    counter: u16,
+    tcnt: tcnt1,
+    ocra: ocr1a,
+    ocrb: ocr1b,
+    timsk: timsk1 { overflow: toie1, compare_a: ocie1a, compare_b: ocie1b },
+    tifr: tifr1 { overflow: tov1, compare_a: ocf1a, compare_b: ocf1b },
+    set_mode: |tim, mode| {
+        // WGM1[1:0] live in TCCR1A, WGM1[3:2] in TCCR1B.
+        let (wgm_a, wgm_b) = match mode {
+            Mode::Normal => (0b00, 0b00),
+            Mode::Ctc => (0b00, 0b01),
+            Mode::FastPwm => (0b01, 0b01),
+            Mode::PhaseCorrectPwm => (0b01, 0b00),
+        };
+        tim.tccr1a.modify(|_r, w| w.wgm1().bits(wgm_a));
+        tim.tccr1b.modify(|_r, w| w.wgm1().bits(wgm_b));
+    },
+    start: |tim, prescaler| {
+        tim.tccr1b.modify(|_r, w| match prescaler {
+            Prescaler::Direct => w.cs1().running_no_prescaling(),
+            Prescaler::Prescale8 => w.cs1().running_clk_8(),
+            Prescaler::Prescale64 => w.cs1().running_clk_64(),
+            Prescaler::Prescale256 => w.cs1().running_clk_256(),
+            Prescaler::Prescale1024 => w.cs1().running_clk_1024(),
+        });
+    },
+    stop: |tim| {
+        tim.tccr1b.modify(|_r, w| w.cs1().no_clock_source_stopped());
+    },
+}
+
+#[cfg(any(
+    feature = "atmega8",
+    feature = "atmega128a"
+))]
+/// 16-bit timer/counter `TC1`
+pub type Timer1<CLOCK> = Timer<crate::pac::TC1, CLOCK>;
+#[cfg(any(
+    feature = "atmega8",
+    feature = "atmega128a"
+))]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    counter: u16,
+    tcnt: tcnt1,
+    ocra: ocr1a,
+    ocrb: ocr1b,
+    timsk: timsk { overflow: toie1, compare_a: ocie1a, compare_b: ocie1b },
+    tifr: tifr { overflow: tov1, compare_a: ocf1a, compare_b: ocf1b },
+    set_mode: |tim, mode| {
+        // WGM1[1:0] live in TCCR1A, WGM1[3:2] in TCCR1B.
+        let (wgm_a, wgm_b) = match mode {
+            Mode::Normal => (0b00, 0b00),
+            Mode::Ctc => (0b00, 0b01),
+            Mode::FastPwm => (0b01, 0b01),
+            Mode::PhaseCorrectPwm => (0b01, 0b00),
+        };
+        tim.tccr1a.modify(|_r, w| w.wgm1().bits(wgm_a));
+        tim.tccr1b.modify(|_r, w| w.wgm1().bits(wgm_b));
+    },
+    start: |tim, prescaler| {
+        tim.tccr1b.modify(|_r, w| match prescaler {
+            Prescaler::Direct => w.cs1().direct(),
+            Prescaler::Prescale8 => w.cs1().prescale_8(),
+            Prescaler::Prescale64 => w.cs1().prescale_64(),
+            Prescaler::Prescale256 => w.cs1().prescale_256(),
+            Prescaler::Prescale1024 => w.cs1().prescale_1024(),
+        });
+    },
+    stop: |tim| {
+        tim.tccr1b.modify(|_r, w| w.cs1().no_clock());
+    },
+}
+
+#[cfg(feature = "atmega32a")]
+/// 16-bit timer/counter `TC1`
+pub type Timer1<CLOCK> = Timer<crate::pac::TC1, CLOCK>;
+#[cfg(feature = "atmega32a")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC1,
+    counter: u16,
+    tcnt: tcnt1,
+    ocra: ocr1a,
+    ocrb: ocr1b,
+    timsk: timsk { overflow: toie1, compare_a: ocie1a, compare_b: ocie1b },
+    tifr: tifr { overflow: tov1, compare_a: ocf1a, compare_b: ocf1b },
+    set_mode: |tim, mode| {
+        // WGM1[1:0] live in TCCR1A, WGM1[3:2] in TCCR1B.
+        let (wgm_a, wgm_b) = match mode {
+            Mode::Normal => (0b00, 0b00),
+            Mode::Ctc => (0b00, 0b01),
+            Mode::FastPwm => (0b01, 0b01),
+            Mode::PhaseCorrectPwm => (0b01, 0b00),
+        };
+        tim.tccr1a.modify(|_r, w| w.wgm1().bits(wgm_a));
+        tim.tccr1b.modify(|_r, w| w.wgm1().bits(wgm_b));
+    },
+    start: |tim, prescaler| {
+        tim.tccr1b.modify(|_r, w| match prescaler {
+            Prescaler::Direct => w.cs1().bits(0b001),
+            Prescaler::Prescale8 => w.cs1().bits(0b010),
+            Prescaler::Prescale64 => w.cs1().bits(0b011),
+            Prescaler::Prescale256 => w.cs1().bits(0b100),
+            Prescaler::Prescale1024 => w.cs1().bits(0b101),
+        });
+    },
+    stop: |tim| {
+        tim.tccr1b.modify(|_r, w| w.cs1().bits(0b000));
+    },
+}
+
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb",
+    feature = "atmega1280",
+    feature = "atmega2560",
+    feature = "atmega1284p"
+))]
+/// 8-bit timer/counter `TC2`
+pub type Timer2<CLOCK> = Timer<crate::pac::TC2, CLOCK>;
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb",
+    feature = "atmega1280",
+    feature = "atmega2560",
+    feature = "atmega1284p"
+))]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC2,
+    counter: u8,
+    tcnt: tcnt2,
+    ocra: ocr2a,
+    ocrb: ocr2b,
+    timsk: timsk2 { overflow: toie2, compare_a: ocie2a, compare_b: ocie2b },
+    tifr: tifr2 { overflow: tov2, compare_a: ocf2a, compare_b: ocf2b },
+    set_mode: |tim, mode| {
+        tim.tccr2a.modify(|_r, w| match mode {
+            Mode::Normal => w.wgm2().normal_top(),
+            Mode::Ctc => w.wgm2().ctc(),
+            Mode::FastPwm => w.wgm2().pwm_fast(),
+            Mode::PhaseCorrectPwm => w.wgm2().pwm_phase(),
+        });
+        tim.tccr2b.modify(|_r, w| w.wgm22().clear_bit());
+    },
+    start: |tim, prescaler| {
+        tim.tccr2b.modify(|_r, w| match prescaler {
+            Prescaler::Direct => w.cs2().direct(),
+            Prescaler::Prescale8 => w.cs2().prescale_8(),
+            Prescaler::Prescale64 => w.cs2().prescale_64(),
+            Prescaler::Prescale256 => w.cs2().prescale_256(),
+            Prescaler::Prescale1024 => w.cs2().prescale_1024(),
+        });
+    },
+    stop: |tim| {
+        tim.tccr2b.modify(|_r, w| w.cs2().no_clock());
+    },
+}
+
+#[cfg(feature = "atmega164pa")]
+/// 8-bit timer/counter `TC2`
+pub type Timer2<CLOCK> = Timer<crate::pac::TC2, CLOCK>;
+#[cfg(feature = "atmega164pa")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC2,
+    counter: u8,
+    tcnt: tcnt2,
+    ocra: ocr2a,
+    ocrb: ocr2b,
+    timsk: timsk2 { overflow: toie2, compare_a: ocie2a, compare_b: ocie2b },
+    tifr: tifr2 { overflow: tov2, compare_a: ocf2a, compare_b: ocf2b },
+    set_mode: |tim, mode| {
+        tim.tccr2a.modify(|_r, w| match mode {
+            Mode::Normal => w.wgm2().bits(0b00),
+            Mode::Ctc => w.wgm2().bits(0b10),
+            Mode::FastPwm => w.wgm2().bits(0b11),
+            Mode::PhaseCorrectPwm => w.wgm2().bits(0b01),
+        });
+        tim.tccr2b.modify(|_r, w| w.wgm22().clear_bit());
+    },
+    start: |tim, prescaler| {
+        tim.tccr2b.modify(|_r, w| match prescaler {
+            Prescaler::Direct => w.cs2().running_no_prescaling(),
+            Prescaler::Prescale8 => w.cs2().running_clk_8(),
+            Prescaler::Prescale64 => w.cs2().running_clk_64(),
+            Prescaler::Prescale256 => w.cs2().running_clk_256(),
+            Prescaler::Prescale1024 => w.cs2().running_clk_1024(),
+        });
+    },
+    stop: |tim| {
+        tim.tccr2b.modify(|_r, w| w.cs2().no_clock_source_stopped());
+    },
+}
+
+#[cfg(feature = "atmega8")]
+/// 8-bit timer/counter `TC2`
+///
+/// `TC2` only has a single compare register `OCR2`, which is used as compare register A.
+pub type Timer2<CLOCK> = Timer<crate::pac::TC2, CLOCK>;
+#[cfg(feature = "atmega8")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC2,
+    counter: u8,
+    tcnt: tcnt2,
+    ocra: ocr2,
+    timsk: timsk { overflow: toie2, compare_a: ocie2 },
+    tifr: tifr { overflow: tov2, compare_a: ocf2 },
+    set_mode: |tim, mode| {
+        // WGM20 and WGM21 are not adjacent in TCCR2.
+        let (wgm0, wgm1) = match mode {
+            Mode::Normal => (false, false),
+            Mode::Ctc => (false, true),
+            Mode::FastPwm => (true, true),
+            Mode::PhaseCorrectPwm => (true, false),
+        };
+        tim.tccr2.modify(|_r, w| w.wgm20().bit(wgm0).wgm21().bit(wgm1));
+    },
+    start: |tim, prescaler| {
+        tim.tccr2.modify(|_r, w| match prescaler {
+            Prescaler::Direct => w.cs2().direct(),
+            Prescaler::Prescale8 => w.cs2().prescale_8(),
+            Prescaler::Prescale64 => w.cs2().prescale_64(),
+            Prescaler::Prescale256 => w.cs2().prescale_256(),
+            Prescaler::Prescale1024 => w.cs2().prescale_1024(),
+        });
+    },
+    stop: |tim| {
+        tim.tccr2.modify(|_r, w| w.cs2().no_clock());
+    },
+}
+
+#[cfg(feature = "atmega32a")]
+/// 8-bit timer/counter `TC2`
+///
+/// `TC2` only has a single compare register `OCR2`, which is used as compare register A.
+pub type Timer2<CLOCK> = Timer<crate::pac::TC2, CLOCK>;
+#[cfg(feature = "atmega32a")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC2,
+    counter: u8,
+    tcnt: tcnt2,
+    ocra: ocr2,
+    timsk: timsk { overflow: toie2, compare_a: ocie2 },
+    tifr: tifr { overflow: tov2, compare_a: ocf2 },
+    set_mode: |tim, mode| {
+        // WGM20 and WGM21 are not adjacent in TCCR2.
+        let (wgm0, wgm1) = match mode {
+            Mode::Normal => (false, false),
+            Mode::Ctc => (false, true),
+            Mode::FastPwm => (true, true),
+            Mode::PhaseCorrectPwm => (true, false),
+        };
+        tim.tccr2.modify(|_r, w| w.wgm20().bit(wgm0).wgm21().bit(wgm1));
+    },
+    start: |tim, prescaler| {
+        tim.tccr2.modify(|_r, w| match prescaler {
+            Prescaler::Direct => w.cs2().bits(0b001),
+            Prescaler::Prescale8 => w.cs2().bits(0b010),
+            Prescaler::Prescale64 => w.cs2().bits(0b100),
+            Prescaler::Prescale256 => w.cs2().bits(0b110),
+            Prescaler::Prescale1024 => w.cs2().bits(0b111),
+        });
+    },
+    stop: |tim| {
+        tim.tccr2.modify(|_r, w| w.cs2().bits(0b000));
+    },
+}
+
+#[cfg(feature = "atmega128a")]
+/// 8-bit timer/counter `TC2`
+///
+/// `TC2` only has a single compare register `OCR2`, which is used as compare register A.
+pub type Timer2<CLOCK> = Timer<crate::pac::TC2, CLOCK>;
+#[cfg(feature = "atmega128a")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC2,
+    counter: u8,
+    tcnt: tcnt2,
+    ocra: ocr2,
+    timsk: timsk { overflow: toie2, compare_a: ocie2 },
+    tifr: tifr { overflow: tov2, compare_a: ocf2 },
+    set_mode: |tim, mode| {
+        // WGM20 and WGM21 are not adjacent in TCCR2.
+        let (wgm0, wgm1) = match mode {
+            Mode::Normal => (false, false),
+            Mode::Ctc => (false, true),
+            Mode::FastPwm => (true, true),
+            Mode::PhaseCorrectPwm => (true, false),
+        };
+        tim.tccr2.modify(|_r, w| w.wgm20().bit(wgm0).wgm21().bit(wgm1));
+    },
+    start: |tim, prescaler| {
+        tim.tccr2.modify(|_r, w| match prescaler {
+            Prescaler::Direct => w.cs2().bits(0b001),
+            Prescaler::Prescale8 => w.cs2().bits(0b010),
+            Prescaler::Prescale64 => w.cs2().bits(0b011),
+            Prescaler::Prescale256 => w.cs2().bits(0b100),
+            Prescaler::Prescale1024 => w.cs2().bits(0b101),
+        });
+    },
+    stop: |tim| {
+        tim.tccr2.modify(|_r, w| w.cs2().bits(0b000));
+    },
+}
+
+#[cfg(any(
+    feature = "atmega1280",
+    feature = "atmega2560",
+    feature = "atmega1284p",
+    feature = "atmega32u4"
+))]
+/// 16-bit timer/counter `TC3`
+pub type Timer3<CLOCK> = Timer<crate::pac::TC3, CLOCK>;
+#[cfg(any(
+    feature = "atmega1280",
+    feature = "atmega2560",
+    feature = "atmega1284p",
+    feature = "atmega32u4"
+))]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    counter: u16,
+    tcnt: tcnt3,
+    ocra: ocr3a,
+    ocrb: ocr3b,
+    timsk: timsk3 { overflow: toie3, compare_a: ocie3a, compare_b: ocie3b },
+    tifr: tifr3 { overflow: tov3, compare_a: ocf3a, compare_b: ocf3b },
+    set_mode: |tim, mode| {
+        // WGM3[1:0] live in TCCR3A, WGM3[3:2] in TCCR3B.
+        let (wgm_a, wgm_b) = match mode {
+            Mode::Normal => (0b00, 0b00),
+            Mode::Ctc => (0b00, 0b01),
+            Mode::FastPwm => (0b01, 0b01),
+            Mode::PhaseCorrectPwm => (0b01, 0b00),
+        };
+        tim.tccr3a.modify(|_r, w| w.wgm3().bits(wgm_a));
+        tim.tccr3b.modify(|_r, w| w.wgm3().bits(wgm_b));
+    },
+    start: |tim, prescaler| {
+        tim.tccr3b.modify(|_r, w| match prescaler {
+            Prescaler::Direct => w.cs3().direct(),
+            Prescaler::Prescale8 => w.cs3().prescale_8(),
+            Prescaler::Prescale64 => w.cs3().prescale_64(),
+            Prescaler::Prescale256 => w.cs3().prescale_256(),
+            Prescaler::Prescale1024 => w.cs3().prescale_1024(),
+        });
+    },
+    stop: |tim| {
+        tim.tccr3b.modify(|_r, w| w.cs3().no_clock());
+    },
+}
+
+#[cfg(feature = "atmega328pb")]
+/// 16-bit timer/counter `TC3`
+pub type Timer3<CLOCK> = Timer<crate::pac::TC3, CLOCK>;
+#[cfg(feature = "atmega328pb")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    counter: u16,
+    tcnt: tcnt3,
+    ocra: ocr3a,
+    ocrb: ocr3b,
+    timsk: timsk3 { overflow: toie3, compare_a: ocie3a, compare_b: ocie3b },
+    tifr: tifr3 { overflow: tov3, compare_a: ocf3a, compare_b: ocf3b },
+    set_mode: |tim, mode| {
+        // WGM3[1:0] live in TCCR3A, WGM3[3:2] in TCCR3B.
+        let (wgm_a, wgm_b) = match mode {
+            Mode::Normal => (0b00, 0b00),
+            Mode::Ctc => (0b00, 0b01),
+            Mode::FastPwm => (0b01, 0b01),
+            Mode::PhaseCorrectPwm => (0b01, 0b00),
+        };
+        tim.tccr3a.modify(|_r, w| w.wgm3().bits(wgm_a));
+        tim.tccr3b.modify(|_r, w| unsafe { w.wgm3().bits(wgm_b) });
+    },
+    start: |tim, prescaler| {
+        tim.tccr3b.modify(|_r, w| match prescaler {
+            Prescaler::Direct => w.cs3().direct(),
+            Prescaler::Prescale8 => w.cs3().prescale_8(),
+            Prescaler::Prescale64 => w.cs3().prescale_64(),
+            Prescaler::Prescale256 => w.cs3().prescale_256(),
+            Prescaler::Prescale1024 => w.cs3().prescale_1024(),
+        });
+    },
+    stop: |tim| {
+        tim.tccr3b.modify(|_r, w| w.cs3().no_clock());
+    },
+}
+
+#[cfg(feature = "atmega128a")]
+/// 16-bit timer/counter `TC3`
+pub type Timer3<CLOCK> = Timer<crate::pac::TC3, CLOCK>;
+#[cfg(feature = "atmega128a")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC3,
+    counter: u16,
+    tcnt: tcnt3,
+    ocra: ocr3a,
+    ocrb: ocr3b,
+    timsk: etimsk { overflow: toie3, compare_a: ocie3a, compare_b: ocie3b },
+    tifr: etifr { overflow: tov3, compare_a: ocf3a, compare_b: ocf3b },
+    set_mode: |tim, mode| {
+        // WGM3[1:0] live in TCCR3A, WGM3[3:2] in TCCR3B.
+        let (wgm_a, wgm_b) = match mode {
+            Mode::Normal => (0b00, 0b00),
+            Mode::Ctc => (0b00, 0b01),
+            Mode::FastPwm => (0b01, 0b01),
+            Mode::PhaseCorrectPwm => (0b01, 0b00),
+        };
+        tim.tccr3a.modify(|_r, w| w.wgm3().bits(wgm_a));
+        tim.tccr3b.modify(|_r, w| w.wgm3().bits(wgm_b));
+    },
+    start: |tim, prescaler| {
+        tim.tccr3b.modify(|_r, w| match prescaler {
+            Prescaler::Direct => w.cs3().direct(),
+            Prescaler::Prescale8 => w.cs3().prescale_8(),
+            Prescaler::Prescale64 => w.cs3().prescale_64(),
+            Prescaler::Prescale256 => w.cs3().prescale_256(),
+            Prescaler::Prescale1024 => w.cs3().prescale_1024(),
+        });
+    },
+    stop: |tim| {
+        tim.tccr3b.modify(|_r, w| w.cs3().no_clock());
+    },
+}
+
+#[cfg(any(
+    feature = "atmega1280",
+    feature = "atmega2560"
+))]
+/// 16-bit timer/counter `TC4`
+pub type Timer4<CLOCK> = Timer<crate::pac::TC4, CLOCK>;
+#[cfg(any(
+    feature = "atmega1280",
+    feature = "atmega2560"
+))]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC4,
+    counter: u16,
+    tcnt: tcnt4,
+    ocra: ocr4a,
+    ocrb: ocr4b,
+    timsk: timsk4 { overflow: toie4, compare_a: ocie4a, compare_b: ocie4b },
+    tifr: tifr4 { overflow: tov4, compare_a: ocf4a, compare_b: ocf4b },
+    set_mode: |tim, mode| {
+        // WGM4[1:0] live in TCCR4A, WGM4[3:2] in TCCR4B.
+        let (wgm_a, wgm_b) = match mode {
+            Mode::Normal => (0b00, 0b00),
+            Mode::Ctc => (0b00, 0b01),
+            Mode::FastPwm => (0b01, 0b01),
+            Mode::PhaseCorrectPwm => (0b01, 0b00),
+        };
+        tim.tccr4a.modify(|_r, w| w.wgm4().bits(wgm_a));
+        tim.tccr4b.modify(|_r, w| w.wgm4().bits(wgm_b));
+    },
+    start: |tim, prescaler| {
+        tim.tccr4b.modify(|_r, w| match prescaler {
+            Prescaler::Direct => w.cs4().direct(),
+            Prescaler::Prescale8 => w.cs4().prescale_8(),
+            Prescaler::Prescale64 => w.cs4().prescale_64(),
+            Prescaler::Prescale256 => w.cs4().prescale_256(),
+            Prescaler::Prescale1024 => w.cs4().prescale_1024(),
+        });
+    },
+    stop: |tim| {
+        tim.tccr4b.modify(|_r, w| w.cs4().no_clock());
+    },
+}
+
+#[cfg(feature = "atmega328pb")]
+/// 16-bit timer/counter `TC4`
+pub type Timer4<CLOCK> = Timer<crate::pac::TC4, CLOCK>;
+#[cfg(feature = "atmega328pb")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC4,
+    counter: u16,
+    tcnt: tcnt4,
+    ocra: ocr4a,
+    ocrb: ocr4b,
+    timsk: timsk4 { overflow: toie4, compare_a: ocie4a, compare_b: ocie4b },
+    tifr: tifr4 { overflow: tov4, compare_a: ocf4a, compare_b: ocf4b },
+    set_mode: |tim, mode| {
+        // WGM4[1:0] live in TCCR4A, WGM4[3:2] in TCCR4B.
+        let (wgm_a, wgm_b) = match mode {
+            Mode::Normal => (0b00, 0b00),
+            Mode::Ctc => (0b00, 0b01),
+            Mode::FastPwm => (0b01, 0b01),
+            Mode::PhaseCorrectPwm => (0b01, 0b00),
+        };
+        tim.tccr4a.modify(|_r, w| w.wgm4().bits(wgm_a));
+        tim.tccr4b.modify(|_r, w| unsafe { w.wgm4().bits(wgm_b) });
+    },
+    start: |tim, prescaler| {
+        tim.tccr4b.modify(|_r, w| match prescaler {
+            Prescaler::Direct => w.cs4().direct(),
+            Prescaler::Prescale8 => w.cs4().prescale_8(),
+            Prescaler::Prescale64 => w.cs4().prescale_64(),
+            Prescaler::Prescale256 => w.cs4().prescale_256(),
+            Prescaler::Prescale1024 => w.cs4().prescale_1024(),
+        });
+    },
+    stop: |tim| {
+        tim.tccr4b.modify(|_r, w| w.cs4().no_clock());
+    },
+}
+
+#[cfg(any(
+    feature = "atmega1280",
+    feature = "atmega2560"
+))]
+/// 16-bit timer/counter `TC5`
+pub type Timer5<CLOCK> = Timer<crate::pac::TC5, CLOCK>;
+#[cfg(any(
+    feature = "atmega1280",
+    feature = "atmega2560"
+))]
+avr_hal_generic::impl_timer! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::TC5,
+    counter: u16,
+    tcnt: tcnt5,
+    ocra: ocr5a,
+    ocrb: ocr5b,
+    timsk: timsk5 { overflow: toie5, compare_a: ocie5a, compare_b: ocie5b },
+    tifr: tifr5 { overflow: tov5, compare_a: ocf5a, compare_b: ocf5b },
+    set_mode: |tim, mode| {
+        // WGM5[1:0] live in TCCR5A, WGM5[3:2] in TCCR5B.
+        let (wgm_a, wgm_b) = match mode {
+            Mode::Normal => (0b00, 0b00),
+            Mode::Ctc => (0b00, 0b01),
+            Mode::FastPwm => (0b01, 0b01),
+            Mode::PhaseCorrectPwm => (0b01, 0b00),
+        };
+        tim.tccr5a.modify(|_r, w| w.wgm5().bits(wgm_a));
+        tim.tccr5b.modify(|_r, w| w.wgm5().bits(wgm_b));
+    },
+    start: |tim, prescaler| {
+        tim.tccr5b.modify(|_r, w| match prescaler {
+            Prescaler::Direct => w.cs5().direct(),
+            Prescaler::Prescale8 => w.cs5().prescale_8(),
+            Prescaler::Prescale64 => w.cs5().prescale_64(),
+            Prescaler::Prescale256 => w.cs5().prescale_256(),
+            Prescaler::Prescale1024 => w.cs5().prescale_1024(),
+        });
+    },
+    stop: |tim| {
+        tim.tccr5b.modify(|_r, w| w.cs5().no_clock());
+    },
+}
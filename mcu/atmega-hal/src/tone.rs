@@ -0,0 +1,189 @@
+//! Tone Generators
+//!
+//! # Example
+//!
+//! ```
+//! let dp = atmega_hal::Peripherals::take().unwrap();
+//! let pins = atmega_hal::pins!(dp);
+//!
+//! let mut tone = Tone::<_, _, atmega_hal::clock::MHz16>::new(dp.TC1, pins.pb1.into_output());
+//! tone.play(440).unwrap();
+//! ```
+
+#[allow(unused_imports)]
+use crate::port;
+pub use avr_hal_generic::tone::{ToneError, ToneOps};
+
+/// Check the [`avr_hal_generic::tone::Tone`] documentation.
+pub type Tone<TC, PIN, CLOCK> = avr_hal_generic::tone::Tone<crate::Atmega, TC, PIN, CLOCK>;
+
+/// Check the [`avr_hal_generic::tone::SoftTone`] documentation.
+pub type SoftTone<TC, PIN, CLOCK> = avr_hal_generic::tone::SoftTone<crate::Atmega, TC, PIN, CLOCK>;
+
+#[cfg(any(
+    feature = "atmega48p",
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb"
+))]
+avr_hal_generic::impl_tone! {
+    hal: crate::Atmega,
+    timers: {
+        crate::pac::TC0: {
+            pin: port::PD6,
+            com: tccr0a[6],
+        },
+        crate::pac::TC1: {
+            pin: port::PB1,
+            com: tccr1a[6],
+        },
+        crate::pac::TC2: {
+            pin: port::PB3,
+            com: tccr2a[6],
+        },
+    },
+}
+
+#[cfg(feature = "atmega328pb")]
+avr_hal_generic::impl_tone! {
+    hal: crate::Atmega,
+    timers: {
+        crate::pac::TC3: {
+            pin: port::PD0,
+            com: tccr3a[6],
+        },
+        crate::pac::TC4: {
+            pin: port::PD1,
+            com: tccr4a[6],
+        },
+    },
+}
+
+#[cfg(any(
+    feature = "atmega1280",
+    feature = "atmega2560"
+))]
+avr_hal_generic::impl_tone! {
+    hal: crate::Atmega,
+    timers: {
+        crate::pac::TC0: {
+            pin: port::PB7,
+            com: tccr0a[6],
+        },
+        crate::pac::TC1: {
+            pin: port::PB5,
+            com: tccr1a[6],
+        },
+        crate::pac::TC2: {
+            pin: port::PB4,
+            com: tccr2a[6],
+        },
+        crate::pac::TC3: {
+            pin: port::PE3,
+            com: tccr3a[6],
+        },
+        crate::pac::TC4: {
+            pin: port::PH3,
+            com: tccr4a[6],
+        },
+        crate::pac::TC5: {
+            pin: port::PL3,
+            com: tccr5a[6],
+        },
+    },
+}
+
+#[cfg(feature = "atmega1284p")]
+avr_hal_generic::impl_tone! {
+    hal: crate::Atmega,
+    timers: {
+        crate::pac::TC0: {
+            pin: port::PB3,
+            com: tccr0a[6],
+        },
+        crate::pac::TC1: {
+            pin: port::PD5,
+            com: tccr1a[6],
+        },
+        crate::pac::TC2: {
+            pin: port::PD7,
+            com: tccr2a[6],
+        },
+        crate::pac::TC3: {
+            pin: port::PB6,
+            com: tccr3a[6],
+        },
+    },
+}
+
+#[cfg(feature = "atmega164pa")]
+avr_hal_generic::impl_tone! {
+    hal: crate::Atmega,
+    timers: {
+        crate::pac::TC0: {
+            pin: port::PB3,
+            com: tccr0a[6],
+        },
+        crate::pac::TC1: {
+            pin: port::PD5,
+            com: tccr1a[6],
+        },
+        crate::pac::TC2: {
+            pin: port::PD7,
+            com: tccr2a[6],
+        },
+    },
+}
+
+#[cfg(feature = "atmega32u4")]
+avr_hal_generic::impl_tone! {
+    hal: crate::Atmega,
+    timers: {
+        crate::pac::TC0: {
+            pin: port::PB7,
+            com: tccr0a[6],
+        },
+        crate::pac::TC1: {
+            pin: port::PB5,
+            com: tccr1a[6],
+        },
+        crate::pac::TC3: {
+            pin: port::PC6,
+            com: tccr3a[6],
+        },
+    },
+}
+
+#[cfg(feature = "atmega8")]
+avr_hal_generic::impl_tone! {
+    hal: crate::Atmega,
+    timers: {
+        crate::pac::TC1: {
+            pin: port::PB1,
+            com: tccr1a[6],
+        },
+    },
+}
+
+#[cfg(feature = "atmega32a")]
+avr_hal_generic::impl_tone! {
+    hal: crate::Atmega,
+    timers: {
+        crate::pac::TC1: {
+            pin: port::PD5,
+            com: tccr1a[6],
+        },
+    },
+}
+
+#[cfg(feature = "atmega128a")]
+avr_hal_generic::impl_tone! {
+    hal: crate::Atmega,
+    timers: {
+        crate::pac::TC1: {
+            pin: port::PB5,
+            com: tccr1a[6],
+        },
+    },
+}
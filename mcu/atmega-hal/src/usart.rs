@@ -39,6 +39,10 @@ pub type UsartWriter<USART, RX, TX, CLOCK> =
     avr_hal_generic::usart::UsartWriter<crate::Atmega, USART, RX, TX, CLOCK>;
 pub type UsartReader<USART, RX, TX, CLOCK> =
     avr_hal_generic::usart::UsartReader<crate::Atmega, USART, RX, TX, CLOCK>;
+pub type BufferedUsart<USART, RX, TX, CLOCK, const RXN: usize, const TXN: usize> =
+    avr_hal_generic::usart::BufferedUsart<crate::Atmega, USART, RX, TX, CLOCK, RXN, TXN>;
+pub type Rs485<USART, RX, TX, CLOCK, DE> =
+    avr_hal_generic::usart::Rs485<crate::Atmega, USART, RX, TX, CLOCK, DE>;
 
 #[cfg(any(
     feature = "atmega88p",
@@ -186,7 +190,10 @@ impl
         crate::port::Pin<crate::port::mode::Output, port::PD1>,
     > for crate::pac::USART
 {
-    fn raw_init<CLOCK>(&mut self, baudrate: crate::usart::Baudrate<CLOCK>) {
+    fn raw_init<CLOCK>(&mut self, settings: crate::usart::UsartSettings<CLOCK>) {
+        let baudrate = settings.baudrate;
+        let format = settings.format;
+
         // msb of ubrrh has to be 0 to set ubrrh register. (see atmega8 datasheet)
         let ubrrh: u8 = ((baudrate.ubrr >> 8) & 0x0F) as u8;
         let ubrrl: u8 = (baudrate.ubrr & 0xFF) as u8;
@@ -199,18 +206,29 @@ impl
         self.ucsrb.write(|w| w
             .txen().set_bit()
             .rxen().set_bit()
+            .ucsz2().bit(format.data_bits == DataBits::Nine)
         );
 
-        // Set frame format to 8n1 for now.  At some point, this should be made
-        // configurable, similar to what is done in other HALs.
-        #[rustfmt::skip]
-        self.ucsrc().write(|w| w
-            .ursel().set_bit() // sets the ucsrc instead of ubrrh (ubrrh and ucsrc share same location on ATmega8, see atmega8 datasheet)
-            .umsel().usart_async()
-            .ucsz().chr8()
-            .usbs().stop1()
-            .upm().disabled()
-        );
+        self.ucsrc().write(|w| {
+            // sets the ucsrc instead of ubrrh (ubrrh and ucsrc share same location on ATmega8,
+            // see atmega8 datasheet)
+            let w = w.ursel().set_bit().umsel().usart_async();
+            let w = match format.data_bits {
+                DataBits::Five => w.ucsz().chr5(),
+                DataBits::Six => w.ucsz().chr6(),
+                DataBits::Seven => w.ucsz().chr7(),
+                DataBits::Eight | DataBits::Nine => w.ucsz().chr8(),
+            };
+            let w = match format.stop_bits {
+                StopBits::One => w.usbs().stop1(),
+                StopBits::Two => w.usbs().stop2(),
+            };
+            match format.parity {
+                Parity::None => w.upm().disabled(),
+                Parity::Even => w.upm().parity_even(),
+                Parity::Odd => w.upm().parity_odd(),
+            }
+        });
     }
 
     fn raw_deinit(&mut self) {
@@ -238,12 +256,72 @@ impl
         Ok(())
     }
 
-    fn raw_read(&mut self) -> avr_hal_generic::nb::Result<u8, core::convert::Infallible> {
+    fn raw_read(&mut self) -> avr_hal_generic::nb::Result<u8, crate::usart::Error> {
+        static OVERRUN: crate::usart::PendingOverrun = crate::usart::PendingOverrun::new();
+        if OVERRUN.take() {
+            return Err(avr_hal_generic::nb::Error::Other(crate::usart::Error::Overrun));
+        }
+
+        let ucsra = self.ucsra.read();
+        if ucsra.rxc().bit_is_clear() {
+            return Err(avr_hal_generic::nb::Error::WouldBlock);
+        }
+
+        // The error flags belong to the frame in the receive buffer, so they must be read before
+        // the data register.  The parity error flag is named differently on ATmega8 and
+        // ATmega32A, so the bits are checked directly: FE (4), DOR (3) and UPE/PE (2).
+        let byte = self.udr.read().bits();
+        if ucsra.bits() & (1 << 3) != 0 {
+            OVERRUN.set();
+        }
+        if ucsra.bits() & (1 << 4) != 0 {
+            Err(avr_hal_generic::nb::Error::Other(crate::usart::Error::FrameFormat))
+        } else if ucsra.bits() & (1 << 2) != 0 {
+            Err(avr_hal_generic::nb::Error::Other(crate::usart::Error::Parity))
+        } else {
+            Ok(byte)
+        }
+    }
+
+    fn raw_write_9bit(
+        &mut self,
+        data: u16,
+    ) -> avr_hal_generic::nb::Result<(), core::convert::Infallible> {
+        self.raw_flush()?;
+
+        // The ninth bit must be written before the low byte.
+        self.ucsrb.modify(|_, w| w.txb8().bit(data & 0x100 != 0));
+        self.udr.write(|w| w.bits(data as u8));
+        Ok(())
+    }
+
+    fn raw_read_ready(&mut self) -> bool {
+        self.ucsra.read().rxc().bit_is_set()
+    }
+
+    fn raw_read_9bit(&mut self) -> avr_hal_generic::nb::Result<u16, crate::usart::Error> {
         if self.ucsra.read().rxc().bit_is_clear() {
             return Err(avr_hal_generic::nb::Error::WouldBlock);
         }
 
-        Ok(self.udr.read().bits())
+        // The ninth bit must be read before the low byte.
+        let high = self.ucsrb.read().rxb8().bit() as u16;
+        let low = self.raw_read()?;
+        Ok(high << 8 | low as u16)
+    }
+
+    fn raw_multiprocessor_mode(&mut self, state: bool) {
+        // Writing back a set TXC flag would clear it, so write it as zero.
+        self.ucsra.modify(|_, w| w.mpcm().bit(state).txc().clear_bit());
+    }
+
+    fn raw_is_tx_complete(&mut self) -> bool {
+        self.ucsra.read().txc().bit_is_set()
+    }
+
+    fn raw_clear_tx_complete(&mut self) {
+        // The flag is cleared by writing a logical one.
+        self.ucsra.modify(|_, w| w.txc().set_bit());
     }
 
     fn raw_interrupt(&mut self, event: crate::usart::Event, state: bool) {
@@ -267,7 +345,10 @@ impl
         crate::port::Pin<crate::port::mode::Output, port::PD3>,
     > for crate::pac::USART1
 {
-    fn raw_init<CLOCK>(&mut self, baudrate: crate::usart::Baudrate<CLOCK>) {
+    fn raw_init<CLOCK>(&mut self, settings: crate::usart::UsartSettings<CLOCK>) {
+        let baudrate = settings.baudrate;
+        let format = settings.format;
+
         let ubrr1h: u8 = (baudrate.ubrr >> 8) as u8;
         let ubrr1l: u8 = baudrate.ubrr as u8;
         self.ubrr1h.write(|w| w.bits(ubrr1h));
@@ -279,17 +360,27 @@ impl
         self.ucsr1b.write(|w| w
             .txen1().set_bit()
             .rxen1().set_bit()
+            .ucsz12().bit(format.data_bits == DataBits::Nine)
         );
 
-        // Set frame format to 8n1 for now.  At some point, this should be made
-        // configurable, similar to what is done in other HALs.
-        #[rustfmt::skip]
-        self.ucsr1c.write(|w| w
-            .umsel1().usart_async()
-            .ucsz1().chr8()
-            .usbs1().stop1()
-            .upm1().disabled()
-        );
+        self.ucsr1c.write(|w| {
+            let w = w.umsel1().usart_async();
+            let w = match format.data_bits {
+                DataBits::Five => w.ucsz1().chr5(),
+                DataBits::Six => w.ucsz1().chr6(),
+                DataBits::Seven => w.ucsz1().chr7(),
+                DataBits::Eight | DataBits::Nine => w.ucsz1().chr8(),
+            };
+            let w = match format.stop_bits {
+                StopBits::One => w.usbs1().stop1(),
+                StopBits::Two => w.usbs1().stop2(),
+            };
+            match format.parity {
+                Parity::None => w.upm1().disabled(),
+                Parity::Even => w.upm1().parity_even(),
+                Parity::Odd => w.upm1().parity_odd(),
+            }
+        });
     }
 
     fn raw_deinit(&mut self) {
@@ -317,12 +408,71 @@ impl
         Ok(())
     }
 
-    fn raw_read(&mut self) -> avr_hal_generic::nb::Result<u8, core::convert::Infallible> {
+    fn raw_read(&mut self) -> avr_hal_generic::nb::Result<u8, crate::usart::Error> {
+        static OVERRUN: crate::usart::PendingOverrun = crate::usart::PendingOverrun::new();
+        if OVERRUN.take() {
+            return Err(avr_hal_generic::nb::Error::Other(crate::usart::Error::Overrun));
+        }
+
+        let ucsr1a = self.ucsr1a.read();
+        if ucsr1a.rxc1().bit_is_clear() {
+            return Err(avr_hal_generic::nb::Error::WouldBlock);
+        }
+
+        // The error flags belong to the frame in the receive buffer, so they must be read before
+        // the data register.
+        let byte = self.udr1.read().bits();
+        if ucsr1a.dor1().bit_is_set() {
+            OVERRUN.set();
+        }
+        if ucsr1a.fe1().bit_is_set() {
+            Err(avr_hal_generic::nb::Error::Other(crate::usart::Error::FrameFormat))
+        } else if ucsr1a.upe1().bit_is_set() {
+            Err(avr_hal_generic::nb::Error::Other(crate::usart::Error::Parity))
+        } else {
+            Ok(byte)
+        }
+    }
+
+    fn raw_write_9bit(
+        &mut self,
+        data: u16,
+    ) -> avr_hal_generic::nb::Result<(), core::convert::Infallible> {
+        self.raw_flush()?;
+
+        // The ninth bit must be written before the low byte.
+        self.ucsr1b.modify(|_, w| w.txb81().bit(data & 0x100 != 0));
+        self.udr1.write(|w| w.bits(data as u8));
+        Ok(())
+    }
+
+    fn raw_read_ready(&mut self) -> bool {
+        self.ucsr1a.read().rxc1().bit_is_set()
+    }
+
+    fn raw_read_9bit(&mut self) -> avr_hal_generic::nb::Result<u16, crate::usart::Error> {
         if self.ucsr1a.read().rxc1().bit_is_clear() {
             return Err(avr_hal_generic::nb::Error::WouldBlock);
         }
 
-        Ok(self.udr1.read().bits())
+        // The ninth bit must be read before the low byte.
+        let high = self.ucsr1b.read().rxb81().bit() as u16;
+        let low = self.raw_read()?;
+        Ok(high << 8 | low as u16)
+    }
+
+    fn raw_multiprocessor_mode(&mut self, state: bool) {
+        // Writing back a set TXC flag would clear it, so write it as zero.
+        self.ucsr1a.modify(|_, w| w.mpcm1().bit(state).txc1().clear_bit());
+    }
+
+    fn raw_is_tx_complete(&mut self) -> bool {
+        self.ucsr1a.read().txc1().bit_is_set()
+    }
+
+    fn raw_clear_tx_complete(&mut self) {
+        // The flag is cleared by writing a logical one.
+        self.ucsr1a.modify(|_, w| w.txc1().set_bit());
     }
 
     fn raw_interrupt(&mut self, event: crate::usart::Event, state: bool) {
@@ -347,7 +497,10 @@ impl
         crate::port::Pin<crate::port::mode::Output, port::PE1>,
     > for crate::pac::USART0
 {
-    fn raw_init<CLOCK>(&mut self, baudrate: crate::usart::Baudrate<CLOCK>) {
+    fn raw_init<CLOCK>(&mut self, settings: crate::usart::UsartSettings<CLOCK>) {
+        let baudrate = settings.baudrate;
+        let format = settings.format;
+
         let ubrr0h: u8 = (baudrate.ubrr >> 8) as u8;
         let ubrr0l: u8 = baudrate.ubrr as u8;
         self.ubrr0h.write(|w| w.bits(ubrr0h));
@@ -355,17 +508,31 @@ impl
         self.ucsr0a.write(|w| w.u2x0().bit(baudrate.u2x));
 
         // Enable receiver and transmitter but leave interrupts disabled.
-        self.ucsr0b.write(|w| w.txen0().set_bit().rxen0().set_bit());
-
-        // Set frame format to 8n1 for now.  At some point, this should be made
-        // configurable, similar to what is done in other HALs.
         #[rustfmt::skip]
-        self.ucsr0c.write(|w| w
-            .umsel0().usart_async()
-            .ucsz0().chr8()
-            .usbs0().stop1()
-            .upm0().disabled()
+        self.ucsr0b.write(|w| w
+            .txen0().set_bit()
+            .rxen0().set_bit()
+            .ucsz02().bit(format.data_bits == DataBits::Nine)
         );
+
+        self.ucsr0c.write(|w| {
+            let w = w.umsel0().usart_async();
+            let w = match format.data_bits {
+                DataBits::Five => w.ucsz0().chr5(),
+                DataBits::Six => w.ucsz0().chr6(),
+                DataBits::Seven => w.ucsz0().chr7(),
+                DataBits::Eight | DataBits::Nine => w.ucsz0().chr8(),
+            };
+            let w = match format.stop_bits {
+                StopBits::One => w.usbs0().stop1(),
+                StopBits::Two => w.usbs0().stop2(),
+            };
+            match format.parity {
+                Parity::None => w.upm0().disabled(),
+                Parity::Even => w.upm0().parity_even(),
+                Parity::Odd => w.upm0().parity_odd(),
+            }
+        });
     }
 
     fn raw_deinit(&mut self) {
@@ -393,12 +560,71 @@ impl
         Ok(())
     }
 
-    fn raw_read(&mut self) -> avr_hal_generic::nb::Result<u8, core::convert::Infallible> {
+    fn raw_read(&mut self) -> avr_hal_generic::nb::Result<u8, crate::usart::Error> {
+        static OVERRUN: crate::usart::PendingOverrun = crate::usart::PendingOverrun::new();
+        if OVERRUN.take() {
+            return Err(avr_hal_generic::nb::Error::Other(crate::usart::Error::Overrun));
+        }
+
+        let ucsr0a = self.ucsr0a.read();
+        if ucsr0a.rxc0().bit_is_clear() {
+            return Err(avr_hal_generic::nb::Error::WouldBlock);
+        }
+
+        // The error flags belong to the frame in the receive buffer, so they must be read before
+        // the data register.
+        let byte = self.udr0.read().bits();
+        if ucsr0a.dor0().bit_is_set() {
+            OVERRUN.set();
+        }
+        if ucsr0a.fe0().bit_is_set() {
+            Err(avr_hal_generic::nb::Error::Other(crate::usart::Error::FrameFormat))
+        } else if ucsr0a.upe0().bit_is_set() {
+            Err(avr_hal_generic::nb::Error::Other(crate::usart::Error::Parity))
+        } else {
+            Ok(byte)
+        }
+    }
+
+    fn raw_write_9bit(
+        &mut self,
+        data: u16,
+    ) -> avr_hal_generic::nb::Result<(), core::convert::Infallible> {
+        self.raw_flush()?;
+
+        // The ninth bit must be written before the low byte.
+        self.ucsr0b.modify(|_, w| w.txb80().bit(data & 0x100 != 0));
+        self.udr0.write(|w| w.bits(data as u8));
+        Ok(())
+    }
+
+    fn raw_read_ready(&mut self) -> bool {
+        self.ucsr0a.read().rxc0().bit_is_set()
+    }
+
+    fn raw_read_9bit(&mut self) -> avr_hal_generic::nb::Result<u16, crate::usart::Error> {
         if self.ucsr0a.read().rxc0().bit_is_clear() {
             return Err(avr_hal_generic::nb::Error::WouldBlock);
         }
 
-        Ok(self.udr0.read().bits())
+        // The ninth bit must be read before the low byte.
+        let high = self.ucsr0b.read().rxb80().bit() as u16;
+        let low = self.raw_read()?;
+        Ok(high << 8 | low as u16)
+    }
+
+    fn raw_multiprocessor_mode(&mut self, state: bool) {
+        // Writing back a set TXC flag would clear it, so write it as zero.
+        self.ucsr0a.modify(|_, w| w.mpcm0().bit(state).txc0().clear_bit());
+    }
+
+    fn raw_is_tx_complete(&mut self) -> bool {
+        self.ucsr0a.read().txc0().bit_is_set()
+    }
+
+    fn raw_clear_tx_complete(&mut self) {
+        // The flag is cleared by writing a logical one.
+        self.ucsr0a.modify(|_, w| w.txc0().set_bit());
     }
 
     fn raw_interrupt(&mut self, event: crate::usart::Event, state: bool) {
@@ -411,3 +637,419 @@ impl
         }
     }
 }
+
+/// Create the buffers of a [`BufferedUsart`] and install its `USART#_RX` and `USART#_UDRE`
+/// interrupt handlers.
+///
+/// The capacities of the RX and TX buffers are given as parameters.  The crate invoking this macro
+/// needs `#![feature(abi_avr_interrupt)]` and must not define its own handlers for these two
+/// interrupts.
+///
+/// # Example
+/// ```ignore
+/// atmega_hal::impl_buffered_usart! {
+///     static SERIAL_BUFFERS: USART0<64, 32>;
+/// }
+///
+/// let serial = Usart::new(dp.USART0, pins.pd0, pins.pd1.into_output(), baudrate);
+/// let mut serial = BufferedUsart::new(serial, &SERIAL_BUFFERS);
+/// ```
+#[cfg(any(
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb",
+    feature = "atmega1284p",
+    feature = "atmega164pa",
+    feature = "atmega32u4",
+    feature = "atmega128a",
+    feature = "atmega1280",
+    feature = "atmega2560",
+    feature = "atmega8",
+    feature = "atmega32a"
+))]
+#[macro_export]
+macro_rules! impl_buffered_usart {
+    ($vis:vis static $name:ident: $USART:ident<$rxn:literal, $txn:literal>;) => {
+        $crate::__usart_interrupts! {
+            $USART => impl_buffered_usart { $vis static $name: [$rxn, $txn], }
+        }
+    };
+}
+
+/// Create the shared driver-enable pin of an [`Rs485`] and install its `USART#_TX` interrupt
+/// handler.
+///
+/// The type of the driver-enable pin is given as a parameter.  The crate invoking this macro needs
+/// `#![feature(abi_avr_interrupt)]` and must not define its own handler for this interrupt.
+///
+/// # Example
+/// ```ignore
+/// atmega_hal::impl_rs485! {
+///     static RS485_DE: USART0<PD2>;
+/// }
+///
+/// let serial = Usart::new(dp.USART0, pins.pd0, pins.pd1.into_output(), baudrate);
+/// let mut bus = Rs485::new(serial, pins.pd2.into_output());
+/// bus.listen_tx_complete(&RS485_DE);
+/// ```
+#[cfg(any(
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb",
+    feature = "atmega1284p",
+    feature = "atmega164pa",
+    feature = "atmega32u4",
+    feature = "atmega128a",
+    feature = "atmega1280",
+    feature = "atmega2560",
+    feature = "atmega8",
+    feature = "atmega32a"
+))]
+#[macro_export]
+macro_rules! impl_rs485 {
+    ($vis:vis static $name:ident: $USART:ident<$de:ident>;) => {
+        $crate::__usart_interrupts! {
+            $USART => impl_rs485 { $vis static $name: [$crate::port::$de], }
+        }
+    };
+}
+
+// Pins and interrupt vectors of each USART, looked up by the macros above.  `$m` names the
+// `avr_hal_generic` macro which receives them together with `$args`.
+#[cfg(feature = "atmega88p")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __usart_interrupts {
+    (USART0 => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega88p,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: USART0: $crate::pac::USART0,
+            rx: $crate::port::PD0,
+            tx: $crate::port::PD1,
+            vectors: { rx: USART_RX, udre: USART_UDRE, tx: USART_TX },
+            $($args)*
+        }
+    };
+}
+
+#[cfg(feature = "atmega168")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __usart_interrupts {
+    (USART0 => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega168,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: USART0: $crate::pac::USART0,
+            rx: $crate::port::PD0,
+            tx: $crate::port::PD1,
+            vectors: { rx: USART_RX, udre: USART_UDRE, tx: USART_TX },
+            $($args)*
+        }
+    };
+}
+
+#[cfg(feature = "atmega328p")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __usart_interrupts {
+    (USART0 => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega328p,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: USART0: $crate::pac::USART0,
+            rx: $crate::port::PD0,
+            tx: $crate::port::PD1,
+            vectors: { rx: USART_RX, udre: USART_UDRE, tx: USART_TX },
+            $($args)*
+        }
+    };
+}
+
+#[cfg(feature = "atmega328pb")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __usart_interrupts {
+    (USART0 => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega328pb,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: USART0: $crate::pac::USART0,
+            rx: $crate::port::PD0,
+            tx: $crate::port::PD1,
+            vectors: { rx: USART0_RX, udre: USART0_UDRE, tx: USART0_TX },
+            $($args)*
+        }
+    };
+    (USART1 => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega328pb,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: USART1: $crate::pac::USART1,
+            rx: $crate::port::PB4,
+            tx: $crate::port::PB3,
+            vectors: { rx: USART1_RX, udre: USART1_UDRE, tx: USART1_TX },
+            $($args)*
+        }
+    };
+}
+
+#[cfg(feature = "atmega1284p")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __usart_interrupts {
+    (USART0 => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega1284p,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: USART0: $crate::pac::USART0,
+            rx: $crate::port::PD0,
+            tx: $crate::port::PD1,
+            vectors: { rx: USART0_RX, udre: USART0_UDRE, tx: USART0_TX },
+            $($args)*
+        }
+    };
+    (USART1 => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega1284p,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: USART1: $crate::pac::USART1,
+            rx: $crate::port::PD2,
+            tx: $crate::port::PD3,
+            vectors: { rx: USART1_RX, udre: USART1_UDRE, tx: USART1_TX },
+            $($args)*
+        }
+    };
+}
+
+#[cfg(feature = "atmega164pa")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __usart_interrupts {
+    (USART0 => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega164pa,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: USART0: $crate::pac::USART0,
+            rx: $crate::port::PD0,
+            tx: $crate::port::PD1,
+            vectors: { rx: USART0_RX, udre: USART0_UDRE, tx: USART0_TX },
+            $($args)*
+        }
+    };
+    (USART1 => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega164pa,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: USART1: $crate::pac::USART1,
+            rx: $crate::port::PD2,
+            tx: $crate::port::PD3,
+            vectors: { rx: USART1_RX, udre: USART1_UDRE, tx: USART1_TX },
+            $($args)*
+        }
+    };
+}
+
+#[cfg(feature = "atmega32u4")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __usart_interrupts {
+    (USART1 => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega32u4,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: USART1: $crate::pac::USART1,
+            rx: $crate::port::PD2,
+            tx: $crate::port::PD3,
+            vectors: { rx: USART1_RX, udre: USART1_UDRE, tx: USART1_TX },
+            $($args)*
+        }
+    };
+}
+
+#[cfg(feature = "atmega128a")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __usart_interrupts {
+    (USART0 => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega128a,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: USART0: $crate::pac::USART0,
+            rx: $crate::port::PE0,
+            tx: $crate::port::PE1,
+            vectors: { rx: USART0_RX, udre: USART0_UDRE, tx: USART0_TX },
+            $($args)*
+        }
+    };
+    (USART1 => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega128a,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: USART1: $crate::pac::USART1,
+            rx: $crate::port::PD2,
+            tx: $crate::port::PD3,
+            vectors: { rx: USART1_RX, udre: USART1_UDRE, tx: USART1_TX },
+            $($args)*
+        }
+    };
+}
+
+#[cfg(feature = "atmega1280")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __usart_interrupts {
+    (USART0 => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega1280,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: USART0: $crate::pac::USART0,
+            rx: $crate::port::PE0,
+            tx: $crate::port::PE1,
+            vectors: { rx: USART0_RX, udre: USART0_UDRE, tx: USART0_TX },
+            $($args)*
+        }
+    };
+    (USART1 => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega1280,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: USART1: $crate::pac::USART1,
+            rx: $crate::port::PD2,
+            tx: $crate::port::PD3,
+            vectors: { rx: USART1_RX, udre: USART1_UDRE, tx: USART1_TX },
+            $($args)*
+        }
+    };
+    (USART2 => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega1280,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: USART2: $crate::pac::USART2,
+            rx: $crate::port::PH0,
+            tx: $crate::port::PH1,
+            vectors: { rx: USART2_RX, udre: USART2_UDRE, tx: USART2_TX },
+            $($args)*
+        }
+    };
+    (USART3 => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega1280,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: USART3: $crate::pac::USART3,
+            rx: $crate::port::PJ0,
+            tx: $crate::port::PJ1,
+            vectors: { rx: USART3_RX, udre: USART3_UDRE, tx: USART3_TX },
+            $($args)*
+        }
+    };
+}
+
+#[cfg(feature = "atmega2560")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __usart_interrupts {
+    (USART0 => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega2560,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: USART0: $crate::pac::USART0,
+            rx: $crate::port::PE0,
+            tx: $crate::port::PE1,
+            vectors: { rx: USART0_RX, udre: USART0_UDRE, tx: USART0_TX },
+            $($args)*
+        }
+    };
+    (USART1 => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega2560,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: USART1: $crate::pac::USART1,
+            rx: $crate::port::PD2,
+            tx: $crate::port::PD3,
+            vectors: { rx: USART1_RX, udre: USART1_UDRE, tx: USART1_TX },
+            $($args)*
+        }
+    };
+    (USART2 => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega2560,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: USART2: $crate::pac::USART2,
+            rx: $crate::port::PH0,
+            tx: $crate::port::PH1,
+            vectors: { rx: USART2_RX, udre: USART2_UDRE, tx: USART2_TX },
+            $($args)*
+        }
+    };
+    (USART3 => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega2560,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: USART3: $crate::pac::USART3,
+            rx: $crate::port::PJ0,
+            tx: $crate::port::PJ1,
+            vectors: { rx: USART3_RX, udre: USART3_UDRE, tx: USART3_TX },
+            $($args)*
+        }
+    };
+}
+
+#[cfg(feature = "atmega8")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __usart_interrupts {
+    (USART => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega8,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: USART: $crate::pac::USART,
+            rx: $crate::port::PD0,
+            tx: $crate::port::PD1,
+            vectors: { rx: USART_RXC, udre: USART_UDRE, tx: USART_TXC },
+            $($args)*
+        }
+    };
+}
+
+#[cfg(feature = "atmega32a")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __usart_interrupts {
+    (USART => $m:ident { $($args:tt)* }) => {
+        $crate::avr_hal_generic::$m! {
+            mcu: atmega32a,
+            hal: $crate::Atmega,
+            peripherals: $crate::pac::Peripherals,
+            peripheral: USART: $crate::pac::USART,
+            rx: $crate::port::PD0,
+            tx: $crate::port::PD1,
+            vectors: { rx: USART_RXC, udre: USART_UDRE, tx: USART_TXC },
+            $($args)*
+        }
+    };
+}
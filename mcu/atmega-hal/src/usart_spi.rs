@@ -0,0 +1,191 @@
+//! USART in Master SPI Mode (MSPIM)
+//!
+//! Each USART can be used as an additional SPI bus.  `XCK#` is the clock, `TXD#` is MOSI and
+//! `RXD#` is MISO.  Any output pin can be used as chip-select.
+//!
+//! # Example
+//! ```
+//! let dp = atmega_hal::Peripherals::take().unwrap();
+//! let pins = atmega_hal::pins!(dp);
+//!
+//! let (mut spi, mut cs) = usart_spi::Usart0Spi::new(
+//!     dp.USART0,
+//!     pins.pd4.into_output(),
+//!     pins.pd1.into_output(),
+//!     pins.pd0.into_pull_up_input(),
+//!     pins.pd7.into_output(),
+//!     spi::Settings::default(),
+//! );
+//!
+//! cs.set_low().unwrap();
+//! spi.write(&[0x9f]).unwrap();
+//! cs.set_high().unwrap();
+//! ```
+//!
+//! The USARTs of ATmega8, ATmega32A and ATmega128A do not support Master SPI Mode.
+
+#[allow(unused_imports)]
+use crate::port;
+pub use avr_hal_generic::usart_spi::*;
+
+#[cfg(any(
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb"
+))]
+pub type Usart0Spi<CSPIN> = UsartSpi<
+    crate::Atmega,
+    crate::pac::USART0,
+    port::PD4,
+    port::PD1,
+    port::PD0,
+    CSPIN,
+>;
+#[cfg(any(
+    feature = "atmega88p",
+    feature = "atmega168",
+    feature = "atmega328p",
+    feature = "atmega328pb"
+))]
+avr_hal_generic::impl_usart_spi! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::USART0,
+    register_suffix: 0,
+    sclk: port::PD4,
+    mosi: port::PD1,
+    miso: port::PD0,
+}
+
+#[cfg(feature = "atmega328pb")]
+pub type Usart1Spi<CSPIN> = UsartSpi<
+    crate::Atmega,
+    crate::pac::USART1,
+    port::PB5,
+    port::PB3,
+    port::PB4,
+    CSPIN,
+>;
+#[cfg(feature = "atmega328pb")]
+avr_hal_generic::impl_usart_spi! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::USART1,
+    register_suffix: 1,
+    sclk: port::PB5,
+    mosi: port::PB3,
+    miso: port::PB4,
+}
+
+#[cfg(any(feature = "atmega1284p", feature = "atmega164pa"))]
+pub type Usart0Spi<CSPIN> = UsartSpi<
+    crate::Atmega,
+    crate::pac::USART0,
+    port::PB0,
+    port::PD1,
+    port::PD0,
+    CSPIN,
+>;
+#[cfg(any(feature = "atmega1284p", feature = "atmega164pa"))]
+avr_hal_generic::impl_usart_spi! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::USART0,
+    register_suffix: 0,
+    sclk: port::PB0,
+    mosi: port::PD1,
+    miso: port::PD0,
+}
+
+#[cfg(any(feature = "atmega1284p", feature = "atmega164pa"))]
+pub type Usart1Spi<CSPIN> = UsartSpi<
+    crate::Atmega,
+    crate::pac::USART1,
+    port::PD4,
+    port::PD3,
+    port::PD2,
+    CSPIN,
+>;
+#[cfg(any(feature = "atmega1284p", feature = "atmega164pa"))]
+avr_hal_generic::impl_usart_spi! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::USART1,
+    register_suffix: 1,
+    sclk: port::PD4,
+    mosi: port::PD3,
+    miso: port::PD2,
+}
+
+#[cfg(any(feature = "atmega32u4", feature = "atmega1280", feature = "atmega2560"))]
+pub type Usart1Spi<CSPIN> = UsartSpi<
+    crate::Atmega,
+    crate::pac::USART1,
+    port::PD5,
+    port::PD3,
+    port::PD2,
+    CSPIN,
+>;
+#[cfg(any(feature = "atmega32u4", feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_usart_spi! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::USART1,
+    register_suffix: 1,
+    sclk: port::PD5,
+    mosi: port::PD3,
+    miso: port::PD2,
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+pub type Usart0Spi<CSPIN> = UsartSpi<
+    crate::Atmega,
+    crate::pac::USART0,
+    port::PE2,
+    port::PE1,
+    port::PE0,
+    CSPIN,
+>;
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_usart_spi! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::USART0,
+    register_suffix: 0,
+    sclk: port::PE2,
+    mosi: port::PE1,
+    miso: port::PE0,
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+pub type Usart2Spi<CSPIN> = UsartSpi<
+    crate::Atmega,
+    crate::pac::USART2,
+    port::PH2,
+    port::PH1,
+    port::PH0,
+    CSPIN,
+>;
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_usart_spi! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::USART2,
+    register_suffix: 2,
+    sclk: port::PH2,
+    mosi: port::PH1,
+    miso: port::PH0,
+}
+
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+pub type Usart3Spi<CSPIN> = UsartSpi<
+    crate::Atmega,
+    crate::pac::USART3,
+    port::PJ2,
+    port::PJ1,
+    port::PJ0,
+    CSPIN,
+>;
+#[cfg(any(feature = "atmega1280", feature = "atmega2560"))]
+avr_hal_generic::impl_usart_spi! {
+    hal: crate::Atmega,
+    peripheral: crate::pac::USART3,
+    register_suffix: 3,
+    sclk: port::PJ2,
+    mosi: port::PJ1,
+    miso: port::PJ0,
+}
@@ -0,0 +1,109 @@
+//! External Interrupts
+//!
+//! # Example
+//!
+//! ```
+//! let dp = attiny_hal::Peripherals::take().unwrap();
+//! let pins = attiny_hal::pins!(dp);
+//! let mut exint = dp.EXINT;
+//!
+//! let mut int0 = pins
+//!     .pb2
+//!     .into_pull_up_input()
+//!     .enable_external_interrupt(&mut exint, Trigger::FallingEdge);
+//!
+//! // ...
+//!
+//! if int0.is_pending(&exint) {
+//!     int0.clear_pending(&mut exint);
+//! }
+//! ```
+
+#[allow(unused_imports)]
+use crate::port;
+pub use avr_hal_generic::exint::{ExintOps, Trigger};
+
+/// Check the [`avr_hal_generic::exint::ExternalInterrupt`] documentation.
+pub type ExternalInterrupt<PIN, IMODE> =
+    avr_hal_generic::exint::ExternalInterrupt<crate::Attiny, crate::pac::EXINT, PIN, IMODE>;
+
+#[cfg(any(feature = "attiny84", feature = "attiny85"))]
+avr_hal_generic::impl_exint! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::EXINT,
+    interrupts: {
+        // INT0
+        port::PB2: {
+            sense: mcucr[0],
+            mask: gimsk,
+            flag: gifr,
+            bit: 6,
+        },
+    },
+}
+
+#[cfg(feature = "attiny88")]
+avr_hal_generic::impl_exint! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::EXINT,
+    interrupts: {
+        // INT0
+        port::PD2: {
+            sense: eicra[0],
+            mask: eimsk,
+            flag: eifr,
+            bit: 0,
+        },
+        // INT1
+        port::PD3: {
+            sense: eicra[2],
+            mask: eimsk,
+            flag: eifr,
+            bit: 1,
+        },
+    },
+}
+
+#[cfg(feature = "attiny167")]
+avr_hal_generic::impl_exint! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::EXINT,
+    interrupts: {
+        // INT0
+        port::PB6: {
+            sense: eicra[0],
+            mask: eimsk,
+            flag: eifr,
+            bit: 0,
+        },
+        // INT1
+        port::PA3: {
+            sense: eicra[2],
+            mask: eimsk,
+            flag: eifr,
+            bit: 1,
+        },
+    },
+}
+
+#[cfg(feature = "attiny2313")]
+avr_hal_generic::impl_exint! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::EXINT,
+    interrupts: {
+        // INT0
+        port::PD2: {
+            sense: mcucr[0] in crate::pac::CPU,
+            mask: gimsk,
+            flag: eifr,
+            bit: 6,
+        },
+        // INT1
+        port::PD3: {
+            sense: mcucr[2] in crate::pac::CPU,
+            mask: gimsk,
+            flag: eifr,
+            bit: 7,
+        },
+    },
+}
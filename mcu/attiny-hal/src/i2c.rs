@@ -0,0 +1,21 @@
+//! I2C
+//!
+//! ATtiny MCUs have no TWI peripheral.  The bus can be driven in software using any two pins, or
+//! by the USI on MCUs which have one (see [`crate::usi::UsiI2c`]).
+//!
+//! # Example
+//!
+//! ```
+//! let dp = attiny_hal::Peripherals::take().unwrap();
+//! let pins = attiny_hal::pins!(dp);
+//!
+//! let mut i2c = i2c::SoftI2c::<_, _, attiny_hal::clock::MHz8>::new(
+//!     pins.pb0.into_opendrain_high(),
+//!     pins.pb2.into_opendrain_high(),
+//!     100_000,
+//! );
+//!
+//! i2c.i2cdetect(&mut serial, i2c::Direction::Write).unwrap();
+//! ```
+
+pub use avr_hal_generic::i2c::*;
@@ -0,0 +1,57 @@
+//! Input Capture
+//!
+//! # Example
+//!
+//! For a full example, please refer to the ATmega input capture documentation.
+
+#[allow(unused_imports)]
+use crate::port;
+pub use avr_hal_generic::input_capture::{Capture, Edge, InputCaptureOps, Measurement, Settings};
+
+/// Check the [`avr_hal_generic::input_capture::InputCapture`] documentation.
+pub type InputCapture<TC, ICP, CLOCK> =
+    avr_hal_generic::input_capture::InputCapture<crate::Attiny, TC, ICP, CLOCK>;
+
+#[cfg(feature = "attiny84")]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::TC1,
+    pin: port::PA7,
+    icr: icr1,
+    tccrb: tccr1b { edge: ices1, noise_canceler: icnc1 },
+    timsk: timsk1 { capture: icie1 },
+    tifr: tifr1 { capture: icf1 },
+}
+
+#[cfg(feature = "attiny88")]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::TC1,
+    pin: port::PB0,
+    icr: icr1,
+    tccrb: tccr1b { edge: ices1, noise_canceler: icnc1 },
+    timsk: timsk1 { capture: icie1 },
+    tifr: tifr1 { capture: icf1 },
+}
+
+#[cfg(feature = "attiny167")]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::TC1,
+    pin: port::PA4,
+    icr: icr1,
+    tccrb: tccr1b { edge: ices1, noise_canceler: icnc1 },
+    timsk: timsk1 { capture: icie1 },
+    tifr: tifr1 { capture: icf1 },
+}
+
+#[cfg(feature = "attiny2313")]
+avr_hal_generic::impl_input_capture! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::TC1,
+    pin: port::PD6,
+    icr: icr1,
+    tccrb: tccr1b { edge: ices1, noise_canceler: icnc1 },
+    timsk: timsk { capture: icie1 },
+    tifr: tifr { capture: icf1 },
+}
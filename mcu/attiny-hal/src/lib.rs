@@ -63,6 +63,7 @@ pub use pac::Peripherals;
 pub use avr_hal_generic::clock;
 pub use avr_hal_generic::delay;
 pub use avr_hal_generic::prelude;
+pub use avr_hal_generic::servo;
 
 // ATtiny2313 does not have ADC and will not compile with this module
 #[cfg(all(feature = "device-selected", not(feature = "attiny2313")))]
@@ -70,6 +71,18 @@ pub mod adc;
 #[cfg(all(feature = "device-selected", not(feature = "attiny2313")))]
 pub use adc::Adc;
 
+#[cfg(feature = "device-selected")]
+pub mod exint;
+
+#[cfg(feature = "device-selected")]
+pub mod i2c;
+
+#[cfg(feature = "device-selected")]
+pub mod input_capture;
+
+#[cfg(feature = "device-selected")]
+pub mod pcint;
+
 #[cfg(feature = "device-selected")]
 pub mod port;
 #[cfg(feature = "device-selected")]
@@ -78,6 +91,12 @@ pub use port::Pins;
 #[cfg(feature = "device-selected")]
 pub mod simple_pwm;
 
+#[cfg(feature = "device-selected")]
+pub mod timer;
+
+#[cfg(feature = "device-selected")]
+pub mod tone;
+
 #[cfg(feature = "device-selected")]
 pub mod wdt;
 #[cfg(feature = "device-selected")]
@@ -93,6 +112,12 @@ pub mod spi;
 #[cfg(feature = "device-selected")]
 pub use spi::Spi;
 
+#[cfg(any(feature = "attiny84", feature = "attiny85", feature = "attiny2313"))]
+pub mod usi;
+
+#[cfg(feature = "device-selected")]
+pub mod usart;
+
 pub struct Attiny;
 
 #[cfg(feature = "attiny84")]
@@ -0,0 +1,39 @@
+//! Pin-Change Interrupts
+//!
+//! # Example
+//!
+//! ```
+//! let dp = attiny_hal::Peripherals::take().unwrap();
+//! let pins = attiny_hal::pins!(dp);
+//! let mut exint = dp.EXINT;
+//!
+//! let a = pins.pb3.into_pull_up_input();
+//! let b = pins.pb4.into_pull_up_input();
+//!
+//! let mut bank = PcintBank::new(&mut exint);
+//! bank.listen(&a);
+//! bank.listen(&b);
+//!
+//! // In the interrupt handler of the bank:
+//! let changes = bank.changed();
+//! if changes.contains(&a) {
+//!     // ...
+//! }
+//! ```
+
+pub use avr_hal_generic::pcint::{PcintBankOps, PcintChanges, PcintPin};
+
+/// Check the [`avr_hal_generic::pcint::PcintBank`] documentation.
+pub type PcintBank<BANK> =
+    avr_hal_generic::pcint::PcintBank<crate::Attiny, crate::pac::EXINT, BANK>;
+
+// The banks are defined next to the ports in `crate::port`, together with the mapping of pins.
+
+#[cfg(any(feature = "attiny84", feature = "attiny167"))]
+pub use crate::port::{Pcint0, Pcint1};
+
+#[cfg(any(feature = "attiny85", feature = "attiny2313"))]
+pub use crate::port::Pcint0;
+
+#[cfg(feature = "attiny88")]
+pub use crate::port::{Pcint0, Pcint1, Pcint2, Pcint3};
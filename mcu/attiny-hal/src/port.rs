@@ -28,6 +28,23 @@ avr_hal_generic::impl_port_traditional! {
     }
 }
 
+#[cfg(feature = "attiny2313")]
+avr_hal_generic::impl_pcint! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::EXINT,
+    banks: {
+        /// Pin-change interrupt bank `PCINT`
+        Pcint0: {
+            control: gimsk[5],
+            flag: eifr[5],
+            mask: pcmsk in crate::pac::CPU,
+            pins: {
+                B: [0 => 0, 1 => 1, 2 => 2, 3 => 3, 4 => 4, 5 => 5, 6 => 6, 7 => 7],
+            },
+        },
+    },
+}
+
 #[cfg(feature = "attiny167")]
 avr_hal_generic::impl_port_traditional! {
     enum Ports {
@@ -36,6 +53,32 @@ avr_hal_generic::impl_port_traditional! {
     }
 }
 
+#[cfg(feature = "attiny167")]
+avr_hal_generic::impl_pcint! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::EXINT,
+    banks: {
+        /// Pin-change interrupt bank `PCINT0`
+        Pcint0: {
+            control: pcicr[0],
+            flag: pcifr[0],
+            mask: pcmsk0,
+            pins: {
+                A: [0 => 0, 1 => 1, 2 => 2, 3 => 3, 4 => 4, 5 => 5, 6 => 6, 7 => 7],
+            },
+        },
+        /// Pin-change interrupt bank `PCINT1`
+        Pcint1: {
+            control: pcicr[1],
+            flag: pcifr[1],
+            mask: pcmsk1,
+            pins: {
+                B: [0 => 0, 1 => 1, 2 => 2, 3 => 3, 4 => 4, 5 => 5, 6 => 6, 7 => 7],
+            },
+        },
+    },
+}
+
 #[cfg(feature = "attiny84")]
 avr_hal_generic::impl_port_traditional! {
     enum Ports {
@@ -44,6 +87,32 @@ avr_hal_generic::impl_port_traditional! {
     }
 }
 
+#[cfg(feature = "attiny84")]
+avr_hal_generic::impl_pcint! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::EXINT,
+    banks: {
+        /// Pin-change interrupt bank `PCINT0`
+        Pcint0: {
+            control: gimsk[4],
+            flag: gifr[4],
+            mask: pcmsk0,
+            pins: {
+                A: [0 => 0, 1 => 1, 2 => 2, 3 => 3, 4 => 4, 5 => 5, 6 => 6, 7 => 7],
+            },
+        },
+        /// Pin-change interrupt bank `PCINT1`
+        Pcint1: {
+            control: gimsk[5],
+            flag: gifr[5],
+            mask: pcmsk1,
+            pins: {
+                B: [0 => 0, 1 => 1, 2 => 2, 3 => 3],
+            },
+        },
+    },
+}
+
 #[cfg(feature = "attiny85")]
 avr_hal_generic::impl_port_traditional! {
     enum Ports {
@@ -51,6 +120,23 @@ avr_hal_generic::impl_port_traditional! {
     }
 }
 
+#[cfg(feature = "attiny85")]
+avr_hal_generic::impl_pcint! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::EXINT,
+    banks: {
+        /// Pin-change interrupt bank `PCINT0`
+        Pcint0: {
+            control: gimsk[5],
+            flag: gifr[5],
+            mask: pcmsk,
+            pins: {
+                B: [0 => 0, 1 => 1, 2 => 2, 3 => 3, 4 => 4, 5 => 5],
+            },
+        },
+    },
+}
+
 #[cfg(feature = "attiny88")]
 avr_hal_generic::impl_port_traditional! {
     enum Ports {
@@ -60,3 +146,47 @@ avr_hal_generic::impl_port_traditional! {
         D: crate::pac::PORTD = [0, 1, 2, 3, 4, 5, 6, 7],
     }
 }
+
+#[cfg(feature = "attiny88")]
+avr_hal_generic::impl_pcint! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::EXINT,
+    banks: {
+        /// Pin-change interrupt bank `PCINT0`
+        Pcint0: {
+            control: pcicr[0],
+            flag: pcifr[0],
+            mask: pcmsk0,
+            pins: {
+                B: [0 => 0, 1 => 1, 2 => 2, 3 => 3, 4 => 4, 5 => 5, 6 => 6, 7 => 7],
+            },
+        },
+        /// Pin-change interrupt bank `PCINT1`
+        Pcint1: {
+            control: pcicr[1],
+            flag: pcifr[1],
+            mask: pcmsk1,
+            pins: {
+                C: [0 => 0, 1 => 1, 2 => 2, 3 => 3, 4 => 4, 5 => 5, 6 => 6, 7 => 7],
+            },
+        },
+        /// Pin-change interrupt bank `PCINT2`
+        Pcint2: {
+            control: pcicr[2],
+            flag: pcifr[2],
+            mask: pcmsk2,
+            pins: {
+                D: [0 => 0, 1 => 1, 2 => 2, 3 => 3, 4 => 4, 5 => 5, 6 => 6, 7 => 7],
+            },
+        },
+        /// Pin-change interrupt bank `PCINT3`
+        Pcint3: {
+            control: pcicr[3],
+            flag: pcifr[3],
+            mask: pcmsk3,
+            pins: {
+                A: [0 => 0, 1 => 1, 2 => 2, 3 => 3],
+            },
+        },
+    },
+}
@@ -1,4 +1,4 @@
-pub use avr_hal_generic::simple_pwm::{IntoPwmPin, Prescaler, PwmPinOps};
+pub use avr_hal_generic::simple_pwm::{IntoPwmPin, Prescaler, Pwm16Mode, PwmPinOps};
 
 #[cfg(any(feature = "attiny85", feature = "attiny84", feature = "attiny88"))]
 use crate::port::*;
@@ -79,6 +79,48 @@ avr_hal_generic::impl_simple_pwm! {
     }
 }
 
+#[cfg(feature = "attiny84")]
+avr_hal_generic::impl_simple_pwm_16! {
+    /// Use `TC1` for 16-bit PWM with `ICR1` as TOP (pins `PA6`, `PA5`)
+    pub struct Timer1Pwm16 {
+        timer: crate::pac::TC1,
+        icr: icr1,
+        init: |tim, wgm, prescaler| {
+            tim.tccr1a.modify(|_r, w| w.wgm1().bits(wgm & 0b11));
+            tim.tccr1b.modify(|_r, w| {
+                w.wgm1().bits(wgm >> 2);
+
+                match prescaler {
+                    Prescaler::Direct => w.cs1().direct(),
+                    Prescaler::Prescale8 => w.cs1().prescale_8(),
+                    Prescaler::Prescale64 => w.cs1().prescale_64(),
+                    Prescaler::Prescale256 => w.cs1().prescale_256(),
+                    Prescaler::Prescale1024 => w.cs1().prescale_1024(),
+                }
+            });
+        },
+        pins: {
+            PA6: {
+                ocr: ocr1a,
+                into_pwm: |tim| if enable {
+                    tim.tccr1a.modify(|_, w| w.com1a().bits(0b10));
+                } else {
+                    tim.tccr1a.modify(|_, w| w.com1a().disconnected());
+                },
+            },
+
+            PA5: {
+                ocr: ocr1b,
+                into_pwm: |tim| if enable {
+                    tim.tccr1a.modify(|_, w| w.com1b().bits(0b10));
+                } else {
+                    tim.tccr1a.modify(|_, w| w.com1b().disconnected());
+                },
+            },
+        },
+    }
+}
+
 #[cfg(feature = "attiny85")]
 avr_hal_generic::impl_simple_pwm! {
     /// Use `TC0` for PWM (pins `PB0`, `PB1`)
@@ -215,3 +257,58 @@ avr_hal_generic::impl_simple_pwm! {
         },
     }
 }
+
+#[cfg(feature = "attiny88")]
+avr_hal_generic::impl_simple_pwm_16! {
+    /// Use `TC1` for 16-bit PWM with `ICR1` as TOP (pins `PB1`, `PB2`)
+    ///
+    /// # Example
+    /// ```
+    /// let mut timer1 =
+    ///     Timer1Pwm16::with_frequency::<MHz16>(dp.TC1, Pwm16Mode::Fast, 20_000).unwrap();
+    ///
+    /// let mut d9 = pins.d9.into_output().into_pwm(&mut timer1);
+    /// let mut d10 = pins.d10.into_output().into_pwm(&mut timer1);
+    ///
+    /// // 25% duty cycle
+    /// d9.set_duty(timer1.top() / 4);
+    /// d9.enable();
+    /// ```
+    pub struct Timer1Pwm16 {
+        timer: crate::pac::TC1,
+        icr: icr1,
+        init: |tim, wgm, prescaler| {
+            tim.tccr1a.modify(|_r, w| w.wgm1().bits(wgm & 0b11));
+            tim.tccr1b.modify(|_r, w| {
+                w.wgm1().bits(wgm >> 2);
+
+                match prescaler {
+                    Prescaler::Direct => w.cs1().direct(),
+                    Prescaler::Prescale8 => w.cs1().prescale_8(),
+                    Prescaler::Prescale64 => w.cs1().prescale_64(),
+                    Prescaler::Prescale256 => w.cs1().prescale_256(),
+                    Prescaler::Prescale1024 => w.cs1().prescale_1024(),
+                }
+            });
+        },
+        pins: {
+            PB1: {
+                ocr: ocr1a,
+                into_pwm: |tim| if enable {
+                    tim.tccr1a.modify(|_, w| w.com1a().bits(0b10));
+                } else {
+                    tim.tccr1a.modify(|_, w| w.com1a().disconnected());
+                },
+            },
+
+            PB2: {
+                ocr: ocr1b,
+                into_pwm: |tim| if enable {
+                    tim.tccr1a.modify(|_, w| w.com1b().bits(0b10));
+                } else {
+                    tim.tccr1a.modify(|_, w| w.com1b().disconnected());
+                },
+            },
+        },
+    }
+}
@@ -43,6 +43,15 @@ pub type Spi = avr_hal_generic::spi::Spi<
     port::PB2,
 >;
 #[cfg(feature = "attiny88")]
+pub type SpiPeripheral = avr_hal_generic::spi::SpiPeripheral<
+    crate::Attiny,
+    crate::pac::SPI,
+    port::PB5,
+    port::PB3,
+    port::PB4,
+    port::PB2,
+>;
+#[cfg(feature = "attiny88")]
 avr_hal_generic::impl_spi! {
     hal: crate::Attiny,
     peripheral: crate::pac::SPI,
@@ -62,6 +71,15 @@ pub type Spi = avr_hal_generic::spi::Spi<
     port::PA6,
 >;
 #[cfg(feature = "attiny167")]
+pub type SpiPeripheral = avr_hal_generic::spi::SpiPeripheral<
+    crate::Attiny,
+    crate::pac::SPI,
+    port::PA5,
+    port::PA4,
+    port::PA2,
+    port::PA6,
+>;
+#[cfg(feature = "attiny167")]
 avr_hal_generic::impl_spi! {
     hal: crate::Attiny,
     peripheral: crate::pac::SPI,
@@ -0,0 +1,223 @@
+//! Timer/Counter
+//!
+//! # Example
+//!
+//! For full source code, please refer to the ATmega timer example:
+//! [`uno-timer.rs`](https://github.com/Rahix/avr-hal/blob/main/examples/arduino-uno/src/bin/uno-timer.rs)
+//!
+//! ```
+//! let dp = attiny_hal::Peripherals::take().unwrap();
+//!
+//! let mut timer1 = Timer1::<crate::CoreClock>::new(dp.TC1, Mode::Ctc, Prescaler::Prescale256);
+//! timer1.set_compare_a(15624);
+//! timer1.listen(Event::CompareMatchA);
+//! ```
+//!
+//! Some timers do not fit the [`Mode`]s of this API and are not supported:
+//!
+//! - `TC0` of the ATtiny88 has no PWM modes.
+//! - `TC1` of the ATtiny85 counts up to its compare register C and has separate PWM modes for
+//!   each output.
+
+pub use avr_hal_generic::timer::{Event, Mode, Prescaler, TimerOps};
+
+/// Check the [`avr_hal_generic::timer::Timer`] documentation.
+pub type Timer<TC, CLOCK> = avr_hal_generic::timer::Timer<crate::Attiny, TC, CLOCK>;
+
+#[cfg(feature = "attiny84")]
+/// 8-bit timer/counter `TC0`
+pub type Timer0<CLOCK> = Timer<crate::pac::TC0, CLOCK>;
+#[cfg(feature = "attiny84")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::TC0,
+    counter: u8,
+    tcnt: tcnt0,
+    ocra: ocr0a,
+    ocrb: ocr0b,
+    timsk: timsk0 { overflow: toie0, compare_a: ocie0a, compare_b: ocie0b },
+    tifr: tifr0 { overflow: tov0, compare_a: ocf0a, compare_b: ocf0b },
+    set_mode: |tim, mode| {
+        tim.tccr0a.modify(|_r, w| match mode {
+            Mode::Normal => w.wgm0().normal_top(),
+            Mode::Ctc => w.wgm0().ctc(),
+            Mode::FastPwm => w.wgm0().pwm_fast(),
+            Mode::PhaseCorrectPwm => w.wgm0().pwm_phase(),
+        });
+        tim.tccr0b.modify(|_r, w| w.wgm02().clear_bit());
+    },
+    start: |tim, prescaler| {
+        tim.tccr0b.modify(|_r, w| match prescaler {
+            Prescaler::Direct => w.cs0().direct(),
+            Prescaler::Prescale8 => w.cs0().prescale_8(),
+            Prescaler::Prescale64 => w.cs0().prescale_64(),
+            Prescaler::Prescale256 => w.cs0().prescale_256(),
+            Prescaler::Prescale1024 => w.cs0().prescale_1024(),
+        });
+    },
+    stop: |tim| {
+        tim.tccr0b.modify(|_r, w| w.cs0().no_clock());
+    },
+}
+
+#[cfg(any(
+    feature = "attiny85",
+    feature = "attiny2313"
+))]
+/// 8-bit timer/counter `TC0`
+pub type Timer0<CLOCK> = Timer<crate::pac::TC0, CLOCK>;
+#[cfg(any(
+    feature = "attiny85",
+    feature = "attiny2313"
+))]
+avr_hal_generic::impl_timer! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::TC0,
+    counter: u8,
+    tcnt: tcnt0,
+    ocra: ocr0a,
+    ocrb: ocr0b,
+    timsk: timsk { overflow: toie0, compare_a: ocie0a, compare_b: ocie0b },
+    tifr: tifr { overflow: tov0, compare_a: ocf0a, compare_b: ocf0b },
+    set_mode: |tim, mode| {
+        tim.tccr0a.modify(|_r, w| match mode {
+            Mode::Normal => w.wgm0().normal_top(),
+            Mode::Ctc => w.wgm0().ctc(),
+            Mode::FastPwm => w.wgm0().pwm_fast(),
+            Mode::PhaseCorrectPwm => w.wgm0().pwm_phase(),
+        });
+        tim.tccr0b.modify(|_r, w| w.wgm02().clear_bit());
+    },
+    start: |tim, prescaler| {
+        tim.tccr0b.modify(|_r, w| match prescaler {
+            Prescaler::Direct => w.cs0().direct(),
+            Prescaler::Prescale8 => w.cs0().prescale_8(),
+            Prescaler::Prescale64 => w.cs0().prescale_64(),
+            Prescaler::Prescale256 => w.cs0().prescale_256(),
+            Prescaler::Prescale1024 => w.cs0().prescale_1024(),
+        });
+    },
+    stop: |tim| {
+        tim.tccr0b.modify(|_r, w| w.cs0().no_clock());
+    },
+}
+
+#[cfg(feature = "attiny167")]
+/// 8-bit timer/counter `TC0`
+///
+/// `TC0` only has a single compare register `OCR0A`, which is used as compare register A.
+pub type Timer0<CLOCK> = Timer<crate::pac::TC0, CLOCK>;
+#[cfg(feature = "attiny167")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::TC0,
+    counter: u8,
+    tcnt: tcnt0,
+    ocra: ocr0a,
+    timsk: timsk0 { overflow: toie0, compare_a: ocie0a },
+    tifr: tifr0 { overflow: tov0, compare_a: ocf0a },
+    set_mode: |tim, mode| {
+        tim.tccr0a.modify(|_r, w| match mode {
+            Mode::Normal => w.wgm0().bits(0b00),
+            Mode::Ctc => w.wgm0().bits(0b10),
+            Mode::FastPwm => w.wgm0().bits(0b11),
+            Mode::PhaseCorrectPwm => w.wgm0().bits(0b01),
+        });
+    },
+    start: |tim, prescaler| {
+        tim.tccr0b.modify(|_r, w| match prescaler {
+            Prescaler::Direct => w.cs0().direct(),
+            Prescaler::Prescale8 => w.cs0().prescale_8(),
+            Prescaler::Prescale64 => w.cs0().prescale_64(),
+            Prescaler::Prescale256 => w.cs0().prescale_256(),
+            Prescaler::Prescale1024 => w.cs0().prescale_1024(),
+        });
+    },
+    stop: |tim| {
+        tim.tccr0b.modify(|_r, w| w.cs0().no_clock());
+    },
+}
+
+#[cfg(any(
+    feature = "attiny84",
+    feature = "attiny88",
+    feature = "attiny167"
+))]
+/// 16-bit timer/counter `TC1`
+pub type Timer1<CLOCK> = Timer<crate::pac::TC1, CLOCK>;
+#[cfg(any(
+    feature = "attiny84",
+    feature = "attiny88",
+    feature = "attiny167"
+))]
+avr_hal_generic::impl_timer! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::TC1,
+    counter: u16,
+    tcnt: tcnt1,
+    ocra: ocr1a,
+    ocrb: ocr1b,
+    timsk: timsk1 { overflow: toie1, compare_a: ocie1a, compare_b: ocie1b },
+    tifr: tifr1 { overflow: tov1, compare_a: ocf1a, compare_b: ocf1b },
+    set_mode: |tim, mode| {
+        // WGM1[1:0] live in TCCR1A, WGM1[3:2] in TCCR1B.
+        let (wgm_a, wgm_b) = match mode {
+            Mode::Normal => (0b00, 0b00),
+            Mode::Ctc => (0b00, 0b01),
+            Mode::FastPwm => (0b01, 0b01),
+            Mode::PhaseCorrectPwm => (0b01, 0b00),
+        };
+        tim.tccr1a.modify(|_r, w| w.wgm1().bits(wgm_a));
+        tim.tccr1b.modify(|_r, w| w.wgm1().bits(wgm_b));
+    },
+    start: |tim, prescaler| {
+        tim.tccr1b.modify(|_r, w| match prescaler {
+            Prescaler::Direct => w.cs1().direct(),
+            Prescaler::Prescale8 => w.cs1().prescale_8(),
+            Prescaler::Prescale64 => w.cs1().prescale_64(),
+            Prescaler::Prescale256 => w.cs1().prescale_256(),
+            Prescaler::Prescale1024 => w.cs1().prescale_1024(),
+        });
+    },
+    stop: |tim| {
+        tim.tccr1b.modify(|_r, w| w.cs1().no_clock());
+    },
+}
+
+#[cfg(feature = "attiny2313")]
+/// 16-bit timer/counter `TC1`
+pub type Timer1<CLOCK> = Timer<crate::pac::TC1, CLOCK>;
+#[cfg(feature = "attiny2313")]
+avr_hal_generic::impl_timer! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::TC1,
+    counter: u16,
+    tcnt: tcnt1,
+    ocra: ocr1a,
+    ocrb: ocr1b,
+    timsk: timsk { overflow: toie1, compare_a: ocie1a, compare_b: ocie1b },
+    tifr: tifr { overflow: tov1, compare_a: ocf1a, compare_b: ocf1b },
+    set_mode: |tim, mode| {
+        // WGM1[1:0] live in TCCR1A, WGM1[3:2] in TCCR1B.
+        let (wgm_a, wgm_b) = match mode {
+            Mode::Normal => (0b00, 0b00),
+            Mode::Ctc => (0b00, 0b01),
+            Mode::FastPwm => (0b01, 0b01),
+            Mode::PhaseCorrectPwm => (0b01, 0b00),
+        };
+        tim.tccr1a.modify(|_r, w| w.wgm1().bits(wgm_a));
+        tim.tccr1b.modify(|_r, w| w.wgm1().bits(wgm_b));
+    },
+    start: |tim, prescaler| {
+        tim.tccr1b.modify(|_r, w| match prescaler {
+            Prescaler::Direct => w.cs1().direct(),
+            Prescaler::Prescale8 => w.cs1().prescale_8(),
+            Prescaler::Prescale64 => w.cs1().prescale_64(),
+            Prescaler::Prescale256 => w.cs1().prescale_256(),
+            Prescaler::Prescale1024 => w.cs1().prescale_1024(),
+        });
+    },
+    stop: |tim| {
+        tim.tccr1b.modify(|_r, w| w.cs1().no_clock());
+    },
+}
@@ -0,0 +1,76 @@
+//! Tone Generators
+//!
+//! # Example
+//!
+//! ```
+//! let dp = attiny_hal::Peripherals::take().unwrap();
+//! let pins = attiny_hal::pins!(dp);
+//!
+//! let mut tone = Tone::<_, _, attiny_hal::clock::MHz16>::new(dp.TC0, pins.pb0.into_output());
+//! tone.play(440).unwrap();
+//! ```
+
+#[allow(unused_imports)]
+use crate::port;
+pub use avr_hal_generic::tone::{ToneError, ToneOps};
+
+/// Check the [`avr_hal_generic::tone::Tone`] documentation.
+pub type Tone<TC, PIN, CLOCK> = avr_hal_generic::tone::Tone<crate::Attiny, TC, PIN, CLOCK>;
+
+/// Check the [`avr_hal_generic::tone::SoftTone`] documentation.
+pub type SoftTone<TC, PIN, CLOCK> = avr_hal_generic::tone::SoftTone<crate::Attiny, TC, PIN, CLOCK>;
+
+// The compare outputs of `TC1` on the ATtiny167 are routed through `TCCR1D`, which is not
+// supported yet.
+
+#[cfg(feature = "attiny85")]
+avr_hal_generic::impl_tone! {
+    hal: crate::Attiny,
+    timers: {
+        crate::pac::TC0: {
+            pin: port::PB0,
+            com: tccr0a[6],
+        },
+    },
+}
+
+#[cfg(feature = "attiny84")]
+avr_hal_generic::impl_tone! {
+    hal: crate::Attiny,
+    timers: {
+        crate::pac::TC0: {
+            pin: port::PB2,
+            com: tccr0a[6],
+        },
+        crate::pac::TC1: {
+            pin: port::PA6,
+            com: tccr1a[6],
+        },
+    },
+}
+
+#[cfg(feature = "attiny88")]
+avr_hal_generic::impl_tone! {
+    hal: crate::Attiny,
+    timers: {
+        crate::pac::TC1: {
+            pin: port::PB1,
+            com: tccr1a[6],
+        },
+    },
+}
+
+#[cfg(feature = "attiny2313")]
+avr_hal_generic::impl_tone! {
+    hal: crate::Attiny,
+    timers: {
+        crate::pac::TC0: {
+            pin: port::PB2,
+            com: tccr0a[6],
+        },
+        crate::pac::TC1: {
+            pin: port::PB3,
+            com: tccr1a[6],
+        },
+    },
+}
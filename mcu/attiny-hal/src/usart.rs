@@ -0,0 +1,21 @@
+//! Serial
+//!
+//! Most ATtiny MCUs have no USART, so a serial port is bit-banged on two arbitrary pins instead.
+//! Check the [`SoftSerial`] documentation for details.
+//!
+//! # Example
+//!
+//! ```
+//! let dp = attiny_hal::Peripherals::take().unwrap();
+//! let pins = attiny_hal::pins!(dp);
+//!
+//! let mut serial = SoftSerial::<_, _, attiny_hal::clock::MHz8>::new(
+//!     pins.pb2.into_pull_up_input(),
+//!     pins.pb0.into_output(),
+//!     9600,
+//! );
+//!
+//! ufmt::uwriteln!(&mut serial, "Hello from ATtiny!").unwrap();
+//! ```
+
+pub use avr_hal_generic::usart::{Error, SoftSerial};
@@ -0,0 +1,129 @@
+//! Universal Serial Interface (USI)
+//!
+//! The USI can act as an SPI controller ([`UsiSpi`]) or as an I2C controller ([`UsiI2c`]).
+//! Both are regular [`Spi`][avr_hal_generic::spi::Spi] and [`I2c`][avr_hal_generic::i2c::I2c]
+//! types, so check their documentation for details.
+//!
+//! # Example
+//!
+//! ```
+//! let dp = attiny_hal::Peripherals::take().unwrap();
+//! let pins = attiny_hal::pins!(dp);
+//!
+//! let mut i2c = usi::UsiI2c::<attiny_hal::clock::MHz8>::new(
+//!     dp.USI,
+//!     pins.pb0.into_pull_up_input(),
+//!     pins.pb2.into_pull_up_input(),
+//!     100_000,
+//! );
+//!
+//! i2c.i2cdetect(&mut serial, i2c::Direction::Write).unwrap();
+//! ```
+
+#[allow(unused_imports)]
+use crate::port;
+
+#[cfg(feature = "attiny84")]
+pub type UsiSpi<CSPIN> = avr_hal_generic::spi::Spi<
+    crate::Attiny,
+    crate::pac::USI,
+    port::PA4,
+    port::PA5,
+    port::PA6,
+    CSPIN,
+>;
+#[cfg(feature = "attiny84")]
+avr_hal_generic::impl_usi_spi! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::USI,
+    port: A: crate::pac::PORTA,
+    sclk: port::PA4 = 4,
+    mosi: port::PA5,
+    miso: port::PA6,
+}
+#[cfg(feature = "attiny84")]
+pub type UsiI2c<CLOCK> = avr_hal_generic::i2c::I2c<
+    crate::Attiny,
+    crate::pac::USI,
+    port::Pin<port::mode::Input, port::PA6>,
+    port::Pin<port::mode::Input, port::PA4>,
+    CLOCK,
+>;
+#[cfg(feature = "attiny84")]
+avr_hal_generic::impl_usi_i2c! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::USI,
+    port: A: crate::pac::PORTA,
+    sda: port::PA6 = 6,
+    scl: port::PA4 = 4,
+}
+
+#[cfg(feature = "attiny85")]
+pub type UsiSpi<CSPIN> = avr_hal_generic::spi::Spi<
+    crate::Attiny,
+    crate::pac::USI,
+    port::PB2,
+    port::PB1,
+    port::PB0,
+    CSPIN,
+>;
+#[cfg(feature = "attiny85")]
+avr_hal_generic::impl_usi_spi! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::USI,
+    port: B: crate::pac::PORTB,
+    sclk: port::PB2 = 2,
+    mosi: port::PB1,
+    miso: port::PB0,
+}
+#[cfg(feature = "attiny85")]
+pub type UsiI2c<CLOCK> = avr_hal_generic::i2c::I2c<
+    crate::Attiny,
+    crate::pac::USI,
+    port::Pin<port::mode::Input, port::PB0>,
+    port::Pin<port::mode::Input, port::PB2>,
+    CLOCK,
+>;
+#[cfg(feature = "attiny85")]
+avr_hal_generic::impl_usi_i2c! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::USI,
+    port: B: crate::pac::PORTB,
+    sda: port::PB0 = 0,
+    scl: port::PB2 = 2,
+}
+
+#[cfg(feature = "attiny2313")]
+pub type UsiSpi<CSPIN> = avr_hal_generic::spi::Spi<
+    crate::Attiny,
+    crate::pac::USI,
+    port::PB7,
+    port::PB6,
+    port::PB5,
+    CSPIN,
+>;
+#[cfg(feature = "attiny2313")]
+avr_hal_generic::impl_usi_spi! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::USI,
+    port: B: crate::pac::PORTB,
+    sclk: port::PB7 = 7,
+    mosi: port::PB6,
+    miso: port::PB5,
+}
+#[cfg(feature = "attiny2313")]
+pub type UsiI2c<CLOCK> = avr_hal_generic::i2c::I2c<
+    crate::Attiny,
+    crate::pac::USI,
+    port::Pin<port::mode::Input, port::PB5>,
+    port::Pin<port::mode::Input, port::PB7>,
+    CLOCK,
+>;
+#[cfg(feature = "attiny2313")]
+avr_hal_generic::impl_usi_i2c! {
+    hal: crate::Attiny,
+    peripheral: crate::pac::USI,
+    port: B: crate::pac::PORTB,
+    sda: port::PB5 = 5,
+    scl: port::PB7 = 7,
+}